@@ -100,6 +100,120 @@ impl UntypedModule {
             })
             .collect()
     }
+
+    /// Every type annotation written in this module, including nested ones.
+    pub fn annotations(&self) -> Vec<&Annotation> {
+        fn from_args(args: &[UntypedArg]) -> impl Iterator<Item = &Annotation> {
+            args.iter().filter_map(|arg| arg.annotation.as_ref())
+        }
+
+        let mut roots = Vec::new();
+        let mut bodies = Vec::new();
+
+        for def in self.definitions() {
+            match def {
+                Definition::Fn(Function {
+                    arguments,
+                    return_annotation,
+                    ..
+                }) => {
+                    roots.extend(from_args(arguments));
+                    roots.extend(return_annotation);
+                }
+                Definition::Test(Function {
+                    arguments,
+                    return_annotation,
+                    ..
+                }) => {
+                    for ArgVia { arg, via } in arguments {
+                        roots.extend(&arg.annotation);
+                        bodies.push(via);
+                    }
+                    roots.extend(return_annotation);
+                }
+                Definition::Validator(Validator {
+                    params,
+                    handlers,
+                    fallback,
+                    ..
+                }) => {
+                    roots.extend(from_args(params));
+                    for handler in handlers.iter().chain(std::iter::once(fallback)) {
+                        roots.extend(from_args(&handler.arguments));
+                        roots.extend(&handler.return_annotation);
+                    }
+                }
+                Definition::DataType(DataType { constructors, .. }) => {
+                    roots.extend(
+                        constructors
+                            .iter()
+                            .flat_map(|constructor| &constructor.arguments)
+                            .map(|arg| &arg.annotation),
+                    );
+                }
+                Definition::TypeAlias(TypeAlias { annotation, .. }) => roots.push(annotation),
+                Definition::ModuleConstant(ModuleConstant { annotation, .. }) => {
+                    roots.extend(annotation)
+                }
+                Definition::Use(..) => (),
+            }
+
+            bodies.extend(def.bodies());
+        }
+
+        for body in bodies {
+            body.walk(&mut |expr| match expr {
+                UntypedExpr::Assignment { patterns, .. } => {
+                    roots.extend(patterns.iter().filter_map(|p| p.annotation.as_ref()))
+                }
+                UntypedExpr::Fn {
+                    arguments,
+                    return_annotation,
+                    ..
+                } => {
+                    roots.extend(from_args(arguments));
+                    roots.extend(return_annotation);
+                }
+                _ => (),
+            });
+        }
+
+        let mut annotations = Vec::new();
+        for root in roots {
+            root.walk(&mut |annotation| annotations.push(annotation));
+        }
+        annotations
+    }
+
+    /// Private and non-parameterized type aliases referred to only once in this module,
+    /// alongside the location of that single use.
+    pub fn single_use_type_aliases(&self) -> Vec<(&TypeAlias<()>, Span)> {
+        let annotations = self.annotations();
+
+        self.definitions()
+            .filter_map(|def| match def {
+                Definition::TypeAlias(alias) if !alias.public && alias.parameters.is_empty() => {
+                    let uses = annotations
+                        .iter()
+                        .filter(|annotation| {
+                            matches!(
+                                annotation,
+                                Annotation::Constructor { module: None, name, .. }
+                                    if name == &alias.alias
+                            )
+                        })
+                        .map(|annotation| annotation.location())
+                        .collect::<Vec<_>>();
+
+                    match uses[..] {
+                        [location] => Some((alias, location)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl TypedModule {
@@ -121,6 +235,35 @@ impl TypedModule {
         })
     }
 
+    /// Whether the module exports a type, either a data-type or an alias, of the given name.
+    /// Unlike 'has_definition', values (i.e. functions, constants and constructors) never match.
+    pub fn has_type(&self, name: &str) -> bool {
+        self.definitions.iter().any(|def| match def {
+            Definition::TypeAlias(alias) => alias.public && alias.alias == name,
+            Definition::DataType(t) => t.public && t.name == name,
+            Definition::Fn(_) => false,
+            Definition::ModuleConstant(_) => false,
+            Definition::Use(_) => false,
+            Definition::Test(_) => false,
+            Definition::Validator(_) => false,
+        })
+    }
+
+    /// Location of a private definition of the given name, which other modules can't refer to.
+    pub fn private_definition(&self, name: &str) -> Option<Span> {
+        self.definitions.iter().find_map(|def| match def {
+            Definition::Fn(f) if !f.public && f.name == name => Some(f.location),
+            Definition::TypeAlias(alias) if !alias.public && alias.alias == name => {
+                Some(alias.location)
+            }
+            Definition::ModuleConstant(cst) if !cst.public && cst.name == name => {
+                Some(cst.location)
+            }
+            Definition::DataType(t) if !t.public && t.name == name => Some(t.location),
+            _ => None,
+        })
+    }
+
     pub fn has_constructor(&self, name: &str) -> bool {
         self.definitions.iter().any(|def| match def {
             Definition::DataType(t) if t.public && !t.opaque => t
@@ -840,6 +983,23 @@ impl<A, B, C, D> Definition<A, B, C, D> {
         }
     }
 
+    /// The top-level expressions of this definition, if any.
+    pub fn bodies(&self) -> Vec<&C> {
+        match self {
+            Definition::Fn(Function { body, .. }) | Definition::Test(Function { body, .. }) => {
+                vec![body]
+            }
+            Definition::Validator(validator) => validator
+                .handlers
+                .iter()
+                .chain(std::iter::once(&validator.fallback))
+                .map(|handler| &handler.body)
+                .collect(),
+            Definition::ModuleConstant(constant) => vec![&constant.value],
+            Definition::DataType(_) | Definition::TypeAlias(_) | Definition::Use(_) => vec![],
+        }
+    }
+
     pub fn doc(&self) -> Option<String> {
         match self {
             Definition::Use { .. } => None,
@@ -1266,6 +1426,27 @@ pub enum Annotation {
 }
 
 impl Annotation {
+    /// Visit this annotation and all its sub-annotations, parents first.
+    pub fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a Self)) {
+        visit(self);
+
+        match self {
+            Annotation::Var { .. } | Annotation::Hole { .. } => (),
+            Annotation::Constructor { arguments, .. } => {
+                arguments.iter().for_each(|arg| arg.walk(visit))
+            }
+            Annotation::Fn { arguments, ret, .. } => {
+                arguments.iter().for_each(|arg| arg.walk(visit));
+                ret.walk(visit);
+            }
+            Annotation::Tuple { elems, .. } => elems.iter().for_each(|elem| elem.walk(visit)),
+            Annotation::Pair { fst, snd, .. } => {
+                fst.walk(visit);
+                snd.walk(visit);
+            }
+        }
+    }
+
     pub fn location(&self) -> Span {
         match self {
             Annotation::Fn { location, .. }
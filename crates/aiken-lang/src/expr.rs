@@ -411,6 +411,86 @@ impl TypedExpr {
         }
     }
 
+    /// Visit this expression and all its sub-expressions, parents first.
+    pub fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a Self)) {
+        visit(self);
+
+        match self {
+            Self::UInt { .. }
+            | Self::String { .. }
+            | Self::ByteArray { .. }
+            | Self::CurvePoint { .. }
+            | Self::Var { .. }
+            | Self::ModuleSelect { .. }
+            | Self::ErrorTerm { .. } => (),
+
+            Self::Sequence { expressions, .. } | Self::Pipeline { expressions, .. } => {
+                expressions.iter().for_each(|e| e.walk(visit))
+            }
+
+            Self::Tuple { elems, .. } => elems.iter().for_each(|e| e.walk(visit)),
+
+            Self::Fn { body, .. } => body.walk(visit),
+
+            Self::List { elements, tail, .. } => {
+                elements.iter().for_each(|e| e.walk(visit));
+                if let Some(tail) = tail {
+                    tail.walk(visit);
+                }
+            }
+
+            Self::Call { fun, args, .. } => {
+                fun.walk(visit);
+                args.iter().for_each(|arg| arg.value.walk(visit));
+            }
+
+            Self::BinOp { left, right, .. } => {
+                left.walk(visit);
+                right.walk(visit);
+            }
+
+            Self::Pair { fst, snd, .. } => {
+                fst.walk(visit);
+                snd.walk(visit);
+            }
+
+            Self::Assignment { value, .. } | Self::UnOp { value, .. } => value.walk(visit),
+
+            Self::Trace { then, text, .. } => {
+                text.walk(visit);
+                then.walk(visit);
+            }
+
+            Self::When {
+                subject, clauses, ..
+            } => {
+                subject.walk(visit);
+                clauses.iter().for_each(|clause| clause.then.walk(visit));
+            }
+
+            Self::If {
+                branches,
+                final_else,
+                ..
+            } => {
+                branches.iter().for_each(|branch| {
+                    branch.condition.walk(visit);
+                    branch.body.walk(visit);
+                });
+                final_else.walk(visit);
+            }
+
+            Self::RecordAccess { record, .. } => record.walk(visit),
+
+            Self::TupleIndex { tuple, .. } => tuple.walk(visit),
+
+            Self::RecordUpdate { spread, args, .. } => {
+                spread.walk(visit);
+                args.iter().for_each(|arg| arg.value.walk(visit));
+            }
+        }
+    }
+
     // This could be optimised in places to exit early if the first of a series
     // of expressions is after the byte index.
     pub fn find_node(&self, byte_index: usize) -> Option<Located<'_>> {
@@ -1442,6 +1522,101 @@ impl UntypedExpr {
         }
     }
 
+    /// Visit this expression and all its sub-expressions, parents first.
+    pub fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a Self)) {
+        visit(self);
+
+        match self {
+            Self::UInt { .. }
+            | Self::String { .. }
+            | Self::Var { .. }
+            | Self::ByteArray { .. }
+            | Self::CurvePoint { .. }
+            | Self::ErrorTerm { .. } => (),
+
+            Self::Sequence { expressions, .. } | Self::LogicalOpChain { expressions, .. } => {
+                expressions.iter().for_each(|e| e.walk(visit))
+            }
+
+            Self::PipeLine { expressions, .. } => expressions.iter().for_each(|e| e.walk(visit)),
+
+            Self::Tuple { elems, .. } => elems.iter().for_each(|e| e.walk(visit)),
+
+            Self::Fn { body, .. } => body.walk(visit),
+
+            Self::List { elements, tail, .. } => {
+                elements.iter().for_each(|e| e.walk(visit));
+                if let Some(tail) = tail {
+                    tail.walk(visit);
+                }
+            }
+
+            Self::Call { fun, arguments, .. } => {
+                fun.walk(visit);
+                arguments.iter().for_each(|arg| arg.value.walk(visit));
+            }
+
+            Self::BinOp { left, right, .. } => {
+                left.walk(visit);
+                right.walk(visit);
+            }
+
+            Self::Pair { fst, snd, .. } => {
+                fst.walk(visit);
+                snd.walk(visit);
+            }
+
+            Self::Assignment { value, .. }
+            | Self::TraceIfFalse { value, .. }
+            | Self::UnOp { value, .. } => value.walk(visit),
+
+            Self::Trace {
+                label,
+                arguments,
+                then,
+                ..
+            } => {
+                label.walk(visit);
+                arguments.iter().for_each(|e| e.walk(visit));
+                then.walk(visit);
+            }
+
+            Self::When {
+                subject, clauses, ..
+            } => {
+                subject.walk(visit);
+                clauses.iter().for_each(|clause| clause.then.walk(visit));
+            }
+
+            Self::If {
+                branches,
+                final_else,
+                ..
+            } => {
+                branches.iter().for_each(|branch| {
+                    branch.condition.walk(visit);
+                    branch.body.walk(visit);
+                });
+                final_else.walk(visit);
+            }
+
+            Self::FieldAccess { container, .. } => container.walk(visit),
+
+            Self::TupleIndex { tuple, .. } => tuple.walk(visit),
+
+            Self::RecordUpdate {
+                constructor,
+                spread,
+                arguments,
+                ..
+            } => {
+                constructor.walk(visit);
+                spread.base.walk(visit);
+                arguments.iter().for_each(|arg| arg.value.walk(visit));
+            }
+        }
+    }
+
     pub fn binop_precedence(&self) -> u8 {
         match self {
             Self::BinOp { name, .. } => name.precedence(),
@@ -12,7 +12,8 @@ use crate::{
     expr::{FnStyle, TypedExpr, UntypedExpr, DEFAULT_ERROR_STR, DEFAULT_TODO_STR},
     parser::{
         extra::{Comment, ModuleExtra},
-        token::Base,
+        lexer::{self, LexInfo},
+        token::{Base, Token},
     },
     pretty::{
         break_, concat, flex_break, join, line, lines, nil, prebreak, Document, Documentable,
@@ -22,7 +23,7 @@ use crate::{
 use itertools::Itertools;
 use num_bigint::BigInt;
 use ordinal::Ordinal;
-use std::rc::Rc;
+use std::{collections::HashMap, rc::Rc};
 use vec1::Vec1;
 
 pub const INDENT: isize = 2;
@@ -1383,8 +1384,8 @@ impl<'comments> Formatter<'comments> {
     ) -> Document<'a> {
         let precedence = name.precedence();
 
-        let left_precedence = left.binop_precedence();
-        let right_precedence = right.binop_precedence();
+        let left_precedence = operand_precedence(name, left, true);
+        let right_precedence = operand_precedence(name, right, false);
 
         let mut left = self.expr(left, false);
         if left.fits(MAX_COLUMNS) {
@@ -1396,27 +1397,11 @@ impl<'comments> Formatter<'comments> {
             right = right.force_unbroken()
         }
 
-        self.operator_side(
-            left,
-            precedence,
-            if matches!(name, BinOp::Or | BinOp::And) {
-                left_precedence.saturating_sub(1)
-            } else {
-                left_precedence
-            },
-        )
-        .append(" ")
-        .append(name)
-        .append(" ")
-        .append(self.operator_side(
-            right,
-            precedence,
-            if matches!(name, BinOp::Or | BinOp::And) {
-                right_precedence
-            } else {
-                right_precedence.saturating_sub(1)
-            },
-        ))
+        self.operator_side(left, precedence, left_precedence)
+            .append(" ")
+            .append(name)
+            .append(" ")
+            .append(self.operator_side(right, precedence, right_precedence))
     }
 
     pub fn operator_side<'a>(&mut self, doc: Document<'a>, op: u8, side: u8) -> Document<'a> {
@@ -1777,6 +1762,7 @@ impl<'comments> Formatter<'comments> {
         args: &'a [TypedArg],
         return_annotation: &'a Option<Annotation>,
         return_type: Rc<Type>,
+        max_columns: isize,
     ) -> Document<'a> {
         let head = name.to_doc().append(self.docs_fn_args(args)).append(" -> ");
 
@@ -1785,7 +1771,7 @@ impl<'comments> Formatter<'comments> {
         let doc = head.append(tail.clone()).group();
 
         // Wrap arguments on multi-lines if they are lengthy.
-        if doc.clone().to_pretty_string(MAX_COLUMNS).contains('\n') {
+        if doc.clone().to_pretty_string(max_columns).contains('\n') {
             let head = name
                 .to_doc()
                 .append(self.docs_fn_args(args).force_break())
@@ -1803,10 +1789,15 @@ impl<'comments> Formatter<'comments> {
 
     fn docs_fn_arg<'a>(&mut self, arg: &'a TypedArg) -> Document<'a> {
         self.docs_fn_arg_name(&arg.arg_name)
-            .append(self.type_or_annotation(&arg.annotation, &arg.tipo))
+            .append(self.docs_fn_arg_type(arg))
             .group()
     }
 
+    // Type of a function argument, as displayed in its signature.
+    pub fn docs_fn_arg_type<'a>(&mut self, arg: &'a TypedArg) -> Document<'a> {
+        self.type_or_annotation(&arg.annotation, &arg.tipo)
+    }
+
     fn docs_fn_arg_name<'a>(&mut self, arg_name: &'a ArgName) -> Document<'a> {
         match arg_name {
             ArgName::Discarded { .. } => "".to_doc(),
@@ -2102,6 +2093,127 @@ impl<'a> Documentable<'a> for &'a BinOp {
     }
 }
 
+/// The precedence an operand of a binary operator is compared against when deciding whether it
+/// must be wrapped in parentheses. Boolean operators are right-associative, while all others are
+/// left-associative.
+fn operand_precedence(name: &BinOp, operand: &UntypedExpr, is_left: bool) -> u8 {
+    let precedence = operand.binop_precedence();
+    let is_right_associative = matches!(name, BinOp::Or | BinOp::And);
+    if is_left == is_right_associative {
+        precedence.saturating_sub(1)
+    } else {
+        precedence
+    }
+}
+
+/// Whether an operand of a binary operator must be wrapped in parentheses to preserve the
+/// meaning of the expression.
+pub fn needs_parentheses(name: &BinOp, operand: &UntypedExpr, is_left: bool) -> bool {
+    name.precedence() > operand_precedence(name, operand, is_left)
+}
+
+/// Spans of matching pairs of parentheses, by span of the tokens they enclose.
+pub fn parentheses(tokens: &[(Token, Span)]) -> HashMap<(usize, usize), Span> {
+    let mut parentheses = HashMap::new();
+    let mut opened = Vec::new();
+
+    for (ix, (token, span)) in tokens.iter().enumerate() {
+        match token {
+            Token::LeftParen | Token::NewLineLeftParen => opened.push(ix),
+            Token::RightParen => {
+                if let Some(open) = opened.pop() {
+                    if ix > open + 1 {
+                        parentheses.insert(
+                            (tokens[open + 1].1.start, tokens[ix - 1].1.end),
+                            Span {
+                                start: tokens[open].1.start,
+                                end: span.end,
+                            },
+                        );
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    parentheses
+}
+
+/// Extend a span to the outermost parentheses wrapping it, if any (see 'parentheses').
+pub fn with_parentheses(parentheses: &HashMap<(usize, usize), Span>, mut span: Span) -> Span {
+    while let Some(outer) = parentheses.get(&(span.start, span.end)) {
+        span = *outer;
+    }
+    span
+}
+
+/// The span of an expression in the source code. Unlike its location, which leaves out
+/// parentheses around the leftmost and rightmost operands of operators, it covers the whole
+/// expression.
+pub fn expression_span(parentheses: &HashMap<(usize, usize), Span>, expr: &UntypedExpr) -> Span {
+    let operand = |expr| with_parentheses(parentheses, expression_span(parentheses, expr));
+
+    match expr {
+        UntypedExpr::BinOp { left, right, .. } => Span {
+            start: operand(left).start,
+            end: operand(right).end,
+        },
+        UntypedExpr::UnOp {
+            location, value, ..
+        } => Span {
+            start: location.start,
+            end: operand(value).end,
+        },
+        _ => expr.location(),
+    }
+}
+
+/// Find parentheses wrapping operands of binary operators which the formatter would drop.
+/// Parentheses aren't preserved in the AST, so we look for a matching pair of parentheses tokens
+/// enclosing exactly the tokens of each operand. Parentheses holding comments are left alone.
+pub fn redundant_parentheses(module: &UntypedModule, src: &str) -> Vec<Span> {
+    let Ok(LexInfo { tokens, extra }) = lexer::run(src) else {
+        return Vec::new();
+    };
+
+    let parentheses = parentheses(&tokens);
+
+    let mut spans = Vec::new();
+
+    for body in module.definitions().flat_map(|def| def.bodies()) {
+        body.walk(&mut |expr| {
+            if let UntypedExpr::BinOp {
+                name, left, right, ..
+            } = expr
+            {
+                for (operand, is_left) in [(left, true), (right, false)] {
+                    if needs_parentheses(name, operand, is_left) {
+                        continue;
+                    }
+
+                    let location = expression_span(&parentheses, operand);
+
+                    let span = with_parentheses(&parentheses, location);
+
+                    if span != location {
+                        let has_comments = extra
+                            .comments
+                            .iter()
+                            .any(|comment| span.start < comment.start && comment.end < span.end);
+
+                        if !has_comments {
+                            spans.push(span);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    spans
+}
+
 pub fn wrap_args<'a, I>(args: I) -> Document<'a>
 where
     I: IntoIterator<Item = (Document<'a>, bool)>,
@@ -2284,6 +2284,49 @@ fn forbid_importing_or_using_opaque_constructors() {
     ));
 }
 
+#[test]
+fn duplicate_import_reports_both_modules() {
+    let dependency = r#"
+        pub fn double(n: Int) -> Int {
+          n * 2
+        }
+    "#;
+
+    let source_code = r#"
+        use foo/left.{double}
+        use foo/right.{double}
+
+        fn bar(n: Int) {
+          double(n)
+        }
+    "#;
+
+    let result = check_with_deps(
+        parse(source_code),
+        vec![
+            ("foo/left".to_string(), parse(dependency)),
+            ("foo/right".to_string(), parse(dependency)),
+        ],
+    );
+
+    match result {
+        Err((
+            _,
+            Error::DuplicateImport {
+                name,
+                module,
+                previous_module,
+                ..
+            },
+        )) => {
+            assert_eq!(name, "double");
+            assert_eq!(module, vec!["foo".to_string(), "right".to_string()]);
+            assert_eq!(previous_module, "foo/left");
+        }
+        result => panic!("expected a duplicate import, got: {result:#?}"),
+    }
+}
+
 #[test]
 fn forbid_expect_into_opaque_type_constructor_with_typecasting() {
     let source_code = r#"
@@ -3276,3 +3319,140 @@ fn wrong_arity_on_known_builtin() {
         Err((_, Error::IncorrectFunctionCallArity { .. }))
     ))
 }
+
+#[test]
+fn single_use_type_aliases() {
+    let source_code = r#"
+        type Once = List<(Int, ByteArray)>
+
+        type Twice = Int
+
+        pub type Exported = Bool
+
+        type Generic<a> = List<a>
+
+        fn foo(xs: Once, n: Twice) -> Twice {
+          let m: Twice = n
+          when xs is {
+            [] -> m
+            _ -> 0
+          }
+        }
+
+        fn bar(x: Exported, y: Generic<Int>) -> Exported {
+          x
+        }
+    "#;
+
+    let module = parse(source_code);
+
+    let aliases = module
+        .single_use_type_aliases()
+        .into_iter()
+        .map(|(alias, location)| {
+            (
+                alias.alias.as_str(),
+                &source_code[location.start..location.end],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(aliases, vec![("Once", "Once")]);
+}
+
+#[test]
+fn verbose_equality() {
+    let source_code = r#"
+        pub type Point {
+          x: Int,
+          y: Int,
+        }
+
+        pub fn same(a: Point, b: Point) -> Bool {
+          a.x == b.x && a.y == b.y
+        }
+
+        pub fn also_same(a: Point, b: Point) -> Bool {
+          and {
+            a.y == b.y,
+            a.x == b.x,
+          }
+        }
+
+        pub fn same_x(a: Point, b: Point) -> Bool {
+          a.x == b.x && a.x == b.x
+        }
+
+        pub fn mixed(a: Point, b: Point, c: Point) -> Bool {
+          a.x == b.x && a.y == c.y
+        }
+    "#;
+
+    let (warnings, _) = check(parse(source_code)).unwrap();
+
+    let verbose = warnings
+        .iter()
+        .filter_map(|warning| match warning {
+            Warning::VerboseEquality { left, right, .. } => Some((left.as_str(), right.as_str())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(verbose, vec![("a", "b"), ("a", "b")]);
+}
+
+#[test]
+fn has_type_ignores_values() {
+    let source_code = r#"
+        pub type Wrapper {
+          Token(Int)
+        }
+
+        pub type Amount =
+          Int
+
+        pub fn token() -> Wrapper {
+          Token(1)
+        }
+
+        type Secret {
+          Hidden
+        }
+
+        pub const amount: Amount = 42
+    "#;
+
+    let (_, module) = check(parse(source_code)).unwrap();
+
+    assert!(module.has_type("Wrapper"));
+    assert!(module.has_type("Amount"));
+
+    // Constructors, functions and constants are values, not types.
+    assert!(module.has_constructor("Token"));
+    assert!(!module.has_type("Token"));
+    assert!(module.has_definition("token"));
+    assert!(!module.has_type("token"));
+    assert!(!module.has_type("amount"));
+
+    // Only exported types are candidates to import.
+    assert!(!module.has_type("Secret"));
+}
+
+#[test]
+fn discarded_failure() {
+    let source_code = r#"
+        pub fn foo(n: Int) -> Int {
+          let m = n + 1
+          fail @"nope"
+          let k = m * 2
+          k
+        }
+    "#;
+
+    match check(parse(source_code)) {
+        Err((_, Error::ImplicitlyDiscardedExpression { location })) => {
+            assert_eq!(&source_code[location.start..location.end], "fail @\"nope\"")
+        }
+        result => panic!("unexpected result: {result:?}"),
+    }
+}
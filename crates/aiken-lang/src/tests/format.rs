@@ -1420,3 +1420,59 @@ fn multiline_if_is_2() {
         "#
     );
 }
+
+fn redundant_parentheses(src: &str) -> Vec<&str> {
+    let (module, _) = crate::parser::module(src, crate::ast::ModuleKind::Lib).unwrap();
+    crate::format::redundant_parentheses(&module, src)
+        .into_iter()
+        .map(|span| &src[span.start..span.end])
+        .collect()
+}
+
+#[test]
+fn redundant_parentheses_arithmetic() {
+    assert_eq!(
+        redundant_parentheses("fn foo() { (1 * 2) + 3 }"),
+        vec!["(1 * 2)"]
+    );
+    assert_eq!(
+        redundant_parentheses("fn foo() { ( 1 - 2 ) - 3 }"),
+        vec!["( 1 - 2 )"]
+    );
+    assert_eq!(redundant_parentheses("fn foo() { 1 + (2) }"), vec!["(2)"]);
+    assert!(redundant_parentheses("fn foo() { (1 + 2) * 3 }").is_empty());
+    assert!(redundant_parentheses("fn foo() { 1 - (2 - 3) }").is_empty());
+    assert!(redundant_parentheses("fn foo() { (1 + 2) }").is_empty());
+    assert_eq!(
+        redundant_parentheses("fn foo() { ((1 * 2)) + 3 }"),
+        vec!["((1 * 2))"]
+    );
+    assert_eq!(
+        redundant_parentheses("fn foo() { (\n  1 * 2\n) + 3 }"),
+        vec!["(\n  1 * 2\n)"]
+    );
+    assert!(redundant_parentheses("fn foo() { (\n  // why\n  1 * 2\n) + 3 }").is_empty());
+    assert_eq!(
+        redundant_parentheses("fn foo() { ((1 + 2) * 3) + 4 }"),
+        vec!["((1 + 2) * 3)"]
+    );
+}
+
+#[test]
+fn redundant_parentheses_boolean() {
+    assert_eq!(
+        redundant_parentheses("fn foo(a, b, c) { (a && b) || c }"),
+        vec!["(a && b)"]
+    );
+    assert_eq!(
+        redundant_parentheses("fn foo(a, b, c) { a && (b && c) }"),
+        vec!["(b && c)"]
+    );
+    assert_eq!(
+        redundant_parentheses("fn foo(a, b) { (a == 1) && (b < 2) }"),
+        vec!["(a == 1)", "(b < 2)"]
+    );
+    assert!(redundant_parentheses("fn foo(a, b, c) { (a || b) && c }").is_empty());
+    assert!(redundant_parentheses("fn foo(a, b, c) { (a && b) && c }").is_empty());
+    assert!(redundant_parentheses("fn foo(a, b) { (a |> b) == 1 }").is_empty());
+}
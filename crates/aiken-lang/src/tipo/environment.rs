@@ -74,8 +74,9 @@ pub struct Environment<'a> {
     pub ungeneralised_functions: HashSet<String>,
 
     /// Names of types or values that have been imported an unqualified fashion
-    /// from other modules. Used to prevent multiple imports using the same name.
-    pub unqualified_imported_names: HashMap<String, Span>,
+    /// from other modules, alongside the module they come from. Used to prevent
+    /// multiple imports using the same name.
+    pub unqualified_imported_names: HashMap<String, (Span, String)>,
 
     pub unused_modules: HashMap<String, Span>,
 
@@ -866,19 +867,22 @@ impl<'a> Environment<'a> {
                     let imported_name = as_name.as_ref().unwrap_or(name);
 
                     // Check if value already was imported
-                    if let Some(previous) = self.unqualified_imported_names.get(imported_name) {
+                    if let Some((previous_location, previous_module)) =
+                        self.unqualified_imported_names.get(imported_name)
+                    {
                         return Err(Error::DuplicateImport {
                             location: *location,
-                            previous_location: *previous,
+                            previous_location: *previous_location,
                             name: name.to_string(),
                             module: module.clone(),
+                            previous_module: previous_module.clone(),
                         });
                     }
 
                     // Register the name as imported so it can't be imported a
                     // second time in future
                     self.unqualified_imported_names
-                        .insert(imported_name.clone(), *location);
+                        .insert(imported_name.clone(), (*location, module.join("/")));
 
                     // Register the unqualified import if it is a value
                     if let Some(value) = module_info.values.get(name) {
@@ -957,19 +961,22 @@ impl<'a> Environment<'a> {
                 }
 
                 // Check if a module was already imported with this name
-                if let Some((previous_location, _)) = self.imported_modules.get(&module_name) {
+                if let Some((previous_location, previous_module)) =
+                    self.imported_modules.get(&module_name)
+                {
                     return Err(Error::DuplicateImport {
                         location: *location,
                         previous_location: *previous_location,
                         name: module_name,
                         module: module.clone(),
+                        previous_module: previous_module.name.clone(),
                     });
                 }
 
                 // Register the name as imported so it can't be imported a
                 // second time in future
                 self.unqualified_imported_names
-                    .insert(module_name.clone(), *location);
+                    .insert(module_name.clone(), (*location, module.join("/")));
 
                 // Insert imported module into scope
                 self.imported_modules
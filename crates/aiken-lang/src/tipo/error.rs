@@ -187,6 +187,8 @@ Otherwise, just remove the redundant import."#
         module: Vec<String>,
         #[label("imported here as '{name}'")]
         previous_location: Span,
+        /// Module of the first import, e.g. 'aiken/collection/list'.
+        previous_module: String,
     },
 
     #[error(
@@ -1108,7 +1110,6 @@ impl ExtraData for Error {
             | Error::DuplicateArgument { .. }
             | Error::DuplicateConstName { .. }
             | Error::DuplicateField { .. }
-            | Error::DuplicateImport { .. }
             | Error::DuplicateName { .. }
             | Error::DuplicateTypeName { .. }
             | Error::DuplicateVarInPattern { .. }
@@ -1128,7 +1129,6 @@ impl ExtraData for Error {
             | Error::LogicalOpChainMissingExpr { .. }
             | Error::MissingVarInAlternativePattern { .. }
             | Error::NotIndexable { .. }
-            | Error::NotExhaustivePatternMatch { .. }
             | Error::NotFn { .. }
             | Error::PositionalArgumentAfterLabeled { .. }
             | Error::PrivateTypeLeak { .. }
@@ -1144,7 +1144,6 @@ impl ExtraData for Error {
             | Error::UnknownModuleField { .. }
             | Error::UnknownModuleType { .. }
             | Error::UnknownModuleValue { .. }
-            | Error::UnknownRecordField { .. }
             | Error::UnknownEnvironment { .. }
             | Error::UnnecessarySpreadOperator { .. }
             | Error::UpdateMultiConstructorType { .. }
@@ -1164,6 +1163,27 @@ impl ExtraData for Error {
             | Error::UnknownTypeConstructor { name, .. }
             | Error::UnknownVariable { name, .. }
             | Error::UnknownModule { name, .. } => Some(name.clone()),
+
+            Error::DuplicateImport {
+                name,
+                module,
+                previous_module,
+                ..
+            } => Some([name.clone(), previous_module.clone(), module.join("/")].join("\n")),
+
+            Error::UnknownRecordField { label, fields, .. } => {
+                Some([vec![label.clone()], fields.clone()].concat().join("\n"))
+            }
+
+            Error::NotExhaustivePatternMatch {
+                unmatched, is_let, ..
+            } => {
+                if *is_let {
+                    None
+                } else {
+                    Some(unmatched.join("\n"))
+                }
+            }
         }
     }
 }
@@ -1823,6 +1843,47 @@ pub enum Warning {
         location: Span,
         value: String,
     },
+
+    #[error("I found redundant parentheses around an operand.\n")]
+    #[diagnostic(
+        code("syntax::redundant_parentheses"),
+        help("Operator precedence already groups this operand; the parentheses can be removed.")
+    )]
+    RedundantParentheses {
+        #[label("redundant parentheses")]
+        location: Span,
+    },
+
+    #[error(
+        "I noticed a type alias only used once: {}",
+        name.if_supports_color(Stderr, |s| s.purple())
+    )]
+    #[diagnostic(
+        code("single_use_type_alias"),
+        help("This alias adds a level of indirection for no reuse; consider inlining it.")
+    )]
+    SingleUseTypeAlias {
+        #[label("only used once")]
+        location: Span,
+        #[label("here")]
+        use_location: Span,
+        name: String,
+    },
+
+    #[error("I found a field-by-field comparison of two values of the same type.\n")]
+    #[diagnostic(
+        code("verbose_equality"),
+        help(
+            "Values are compared structurally; you can simply write:\n\n{}",
+            format!("{left} == {right}").if_supports_color(Stderr, |s| s.bold())
+        )
+    )]
+    VerboseEquality {
+        #[label("compares every field")]
+        location: Span,
+        left: String,
+        right: String,
+    },
 }
 
 impl ExtraData for Warning {
@@ -1839,11 +1900,18 @@ impl ExtraData for Warning {
             | Warning::UnusedPrivateFunction { .. }
             | Warning::UnusedPrivateModuleConstant { .. }
             | Warning::UnusedType { .. }
-            | Warning::UnusedVariable { .. }
             | Warning::DiscardedLetAssignment { .. }
             | Warning::ValidatorInLibraryModule { .. }
-            | Warning::UseWhenInstead { .. } => None,
+            | Warning::UseWhenInstead { .. }
+            | Warning::RedundantParentheses { .. } => None,
             Warning::Utf8ByteArrayIsValidHexString { value, .. } => Some(value.clone()),
+            Warning::VerboseEquality { left, right, .. } => Some(format!("{left} == {right}")),
+            Warning::SingleUseTypeAlias { use_location, .. } => {
+                Some(format!("{},{}", use_location.start, use_location.end))
+            }
+            Warning::UnusedVariable { name, location } => {
+                Some(format!("{name},{},{}", location.start, location.end))
+            }
             Warning::UnusedImportedModule { location, .. } => {
                 Some(format!("{},{}", false, location.start))
             }
@@ -762,13 +762,126 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         )
         .map_err(|e| e.operator_situation(name))?;
 
-        Ok(TypedExpr::BinOp {
+        let expr = TypedExpr::BinOp {
             location,
             name,
             tipo: output_type,
             left: Box::new(left),
             right: Box::new(right),
-        })
+        };
+
+        self.check_verbose_equality(&expr);
+
+        Ok(expr)
+    }
+
+    /// Warn about conjunctions comparing, one by one, every field of two variables holding
+    /// the same single-constructor type; which is just what structural equality does.
+    fn check_verbose_equality(&mut self, expr: &TypedExpr) {
+        let TypedExpr::BinOp {
+            name: BinOp::And,
+            location,
+            ..
+        } = expr
+        else {
+            return;
+        };
+
+        let mut operands = None;
+        let mut labels = BTreeSet::new();
+
+        for conjunct in conjuncts(expr) {
+            let TypedExpr::BinOp {
+                name: BinOp::Eq,
+                left,
+                right,
+                ..
+            } = conjunct
+            else {
+                return;
+            };
+
+            let (
+                TypedExpr::RecordAccess {
+                    label: left_label,
+                    record: left_record,
+                    ..
+                },
+                TypedExpr::RecordAccess {
+                    label: right_label,
+                    record: right_record,
+                    ..
+                },
+            ) = (left.as_ref(), right.as_ref())
+            else {
+                return;
+            };
+
+            let (
+                TypedExpr::Var { name: left_var, .. },
+                TypedExpr::Var {
+                    name: right_var, ..
+                },
+            ) = (left_record.as_ref(), right_record.as_ref())
+            else {
+                return;
+            };
+
+            if left_label != right_label || left_var == right_var {
+                return;
+            }
+
+            match operands {
+                None => operands = Some((left_var, right_var, left_record.tipo())),
+                Some((left, right, _)) if (left, right) == (left_var, right_var) => (),
+                Some(_) => return,
+            }
+
+            labels.insert(left_label.as_str());
+        }
+
+        let Some((left, right, tipo)) = operands else {
+            return;
+        };
+
+        let Type::App { module, name, .. } = collapse_links(tipo.clone()).as_ref().clone() else {
+            return;
+        };
+
+        let (constructors, accessors) = if module == *self.environment.current_module {
+            (
+                self.environment.module_types_constructors.get(&name),
+                self.environment.accessors.get(&name),
+            )
+        } else {
+            match self.environment.importable_modules.get(&module) {
+                Some(info) => (
+                    info.types_constructors.get(&name),
+                    info.accessors.get(&name),
+                ),
+                None => return,
+            }
+        };
+
+        let is_exhaustive = match (constructors, accessors) {
+            (Some(constructors), Some(accessors)) => {
+                constructors.len() == 1
+                    && accessors.accessors.len() == labels.len()
+                    && accessors
+                        .accessors
+                        .keys()
+                        .all(|label| labels.contains(label.as_str()))
+            }
+            _ => false,
+        };
+
+        if is_exhaustive && ensure_serialisable(false, tipo.clone(), *location).is_ok() {
+            self.environment.warnings.push(Warning::VerboseEquality {
+                location: *location,
+                left: left.clone(),
+                right: right.clone(),
+            });
+        }
     }
 
     fn infer_record_update(
@@ -1917,6 +2030,8 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             })
             .expect("should have at least two");
 
+        self.check_verbose_equality(&chain);
+
         Ok(chain)
     }
 
@@ -2634,6 +2749,23 @@ fn recover_from_no_assignment(
     result.map(|()| None)
 }
 
+/// Operands of a (possibly nested) chain of '&&'.
+fn conjuncts(expr: &TypedExpr) -> Vec<&TypedExpr> {
+    match expr {
+        TypedExpr::BinOp {
+            name: BinOp::And,
+            left,
+            right,
+            ..
+        } => {
+            let mut operands = conjuncts(left);
+            operands.extend(conjuncts(right));
+            operands
+        }
+        _ => vec![expr],
+    }
+}
+
 fn assert_no_assignment(expr: &UntypedExpr) -> Result<(), Error> {
     match expr {
         UntypedExpr::Assignment {
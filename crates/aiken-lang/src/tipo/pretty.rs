@@ -1,5 +1,6 @@
 use super::{Type, TypeVar};
 use crate::{
+    builtins::PRELUDE,
     docvec, format,
     pretty::{nil, *},
     tipo::{Annotation, TypeAliasAnnotation},
@@ -16,6 +17,44 @@ pub struct Printer {
     uid: u64,
     // A mapping of printd type names to the module that they are defined in.
     printed_types: HashMap<String, String>,
+    // How types of other modules are referred to, when printing annotations for a module.
+    qualifiers: Option<Qualifiers>,
+}
+
+/// How a module refers to the types of other modules, as told by its imports.
+#[derive(Debug, Default, Clone)]
+pub struct Qualifiers {
+    /// The module itself, whose types need no qualification.
+    pub module: String,
+    /// Names modules are imported under, by module.
+    pub aliases: HashMap<String, String>,
+    /// Names types are imported under, when imported unqualified, by module and type.
+    pub unqualified: HashMap<(String, String), String>,
+}
+
+impl Qualifiers {
+    /// The name a type of the given module is referred to by. Types of modules which aren't
+    /// imported are qualified after the last segment of the module's name.
+    pub fn qualify(&self, module: &str, name: &str) -> String {
+        if module.is_empty() || module == PRELUDE || module == self.module {
+            return name.to_string();
+        }
+
+        if let Some(unqualified) = self
+            .unqualified
+            .get(&(module.to_string(), name.to_string()))
+        {
+            return unqualified.clone();
+        }
+
+        let alias = self
+            .aliases
+            .get(module)
+            .map(String::as_str)
+            .unwrap_or_else(|| module.rsplit('/').next().unwrap_or(module));
+
+        format!("{alias}.{name}")
+    }
 }
 
 impl Printer {
@@ -27,6 +66,12 @@ impl Printer {
         self.names = names;
     }
 
+    /// Print types as annotations of a module would refer to them, given its imports. Type
+    /// aliases are expanded, as they don't tell which module defines them.
+    pub fn with_qualifiers(&mut self, qualifiers: Qualifiers) {
+        self.qualifiers = Some(qualifiers);
+    }
+
     /// Render a Type as a well formatted string.
     ///
     pub fn pretty_print(&mut self, typ: &Type, initial_indent: usize) -> String {
@@ -51,7 +96,7 @@ impl Printer {
             alias,
             parameters,
             annotation,
-        }) = typ.alias().as_deref()
+        }) = typ.alias().as_deref().filter(|_| self.qualifiers.is_none())
         {
             if let Some(resolved_parameters) = resolve_alias(parameters, annotation, typ) {
                 return self.type_alias_doc(typ, alias.to_string(), resolved_parameters);
@@ -62,7 +107,9 @@ impl Printer {
             Type::App {
                 name, args, module, ..
             } => {
-                let doc = if self.name_clashes_if_unqualified(name, module) {
+                let doc = if let Some(qualifiers) = &self.qualifiers {
+                    Document::String(qualifiers.qualify(module, name))
+                } else if self.name_clashes_if_unqualified(name, module) {
                     qualify_type_name(module, name)
                 } else {
                     self.printed_types.insert(name.clone(), module.clone());
@@ -703,6 +750,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn qualified_test() {
+        let app = |module: &str, name: &str, args| {
+            Rc::new(Type::App {
+                public: true,
+                contains_opaque: false,
+                module: module.to_string(),
+                name: name.to_string(),
+                args,
+                alias: None,
+            })
+        };
+
+        let mut printer = Printer::new();
+        printer.with_qualifiers(Qualifiers {
+            module: "foo/bar".to_string(),
+            aliases: HashMap::from([
+                ("aiken/collection/dict".to_string(), "dict".to_string()),
+                ("cardano/assets".to_string(), "a".to_string()),
+            ]),
+            unqualified: HashMap::from([(
+                ("cardano/assets".to_string(), "Value".to_string()),
+                "V".to_string(),
+            )]),
+        });
+
+        assert_eq!(
+            printer.pretty_print(
+                &app(
+                    "aiken/collection/dict",
+                    "Dict",
+                    vec![
+                        app("cardano/assets", "PolicyId", vec![]),
+                        Type::list(app("cardano/assets", "Value", vec![])),
+                    ]
+                ),
+                0
+            ),
+            "dict.Dict<a.PolicyId, List<V>>"
+        );
+        assert_eq!(
+            printer.pretty_print(
+                &Type::tuple(vec![
+                    app("foo/bar", "Baz", vec![]),
+                    app("aiken/interval", "Interval", vec![Type::int()]),
+                ]),
+                0
+            ),
+            "(Baz, interval.Interval<Int>)"
+        );
+    }
+
     fn pretty_print(typ: Rc<Type>) -> String {
         Printer::new().pretty_print(&typ, 0)
     }
@@ -1,21 +1,41 @@
 use crate::utils::span_to_lsp_range;
 use aiken_lang::{
-    ast::{Definition, ModuleKind, Span, UntypedDefinition, Use},
+    ast::{
+        Annotation, ArgBy, ArgName, AssignmentKind, AssignmentPattern, BinOp, CallArg, Definition,
+        ModuleKind, Pattern, Span, TraceKind, UnOp, UntypedArg, UntypedClause, UntypedDefinition,
+        UntypedFunction, UntypedModule, UntypedPattern, Use,
+    },
+    expr::{FnStyle, UntypedExpr},
+    format::{self, Formatter, MAX_COLUMNS},
     line_numbers::LineNumbers,
+    parser::{lexer, token::Base},
+    tipo::pretty::Qualifiers,
 };
 use aiken_project::module::CheckedModule;
 use itertools::Itertools;
-use std::fs;
+use std::{collections::BTreeMap, fs};
+use vec1::Vec1;
+
+/// Module of the standard library providing combinators over lists.
+pub const LIST_MODULE: &str = "aiken/collection/list";
 
 /// A freshly parsed module alongside its line numbers.
 pub struct ParsedDocument {
-    definitions: Vec<UntypedDefinition>,
+    module: UntypedModule,
     line_numbers: LineNumbers,
     source_code: String,
 }
 
 pub type AnnotatedEdit = (String, lsp_types::TextEdit);
 
+/// A type annotation one could add, whose type is to be found in the checked module.
+pub enum MissingAnnotation {
+    /// A 'let' binding, identified by the location of the assignment.
+    Binding { location: Span, pattern_end: usize },
+    /// The return type of a function, identified by the location of its signature.
+    ReturnType { location: Span },
+}
+
 /// Parse the target document as an 'UntypedModule' alongside its line numbers. This is useful in
 /// case we need to manipulate the AST for a quickfix.
 pub fn parse_document(document: &lsp_types::TextDocumentIdentifier) -> Option<ParsedDocument> {
@@ -26,19 +46,237 @@ pub fn parse_document(document: &lsp_types::TextDocumentIdentifier) -> Option<Pa
 
     let source_code = fs::read_to_string(file_path).ok()?;
 
-    let line_numbers = LineNumbers::new(&source_code);
+    ParsedDocument::new(source_code)
+}
+
+/// Value of an arithmetic expression made only of integer literals. Division and modulo follow
+/// Plutus semantics, rounding towards negative infinity.
+fn eval_constant_arithmetic(expr: &UntypedExpr) -> Option<i128> {
+    match expr {
+        UntypedExpr::UInt {
+            value,
+            base: Base::Decimal { .. },
+            ..
+        } => value.parse().ok(),
+        UntypedExpr::UnOp {
+            op: UnOp::Negate,
+            value,
+            ..
+        } => eval_constant_arithmetic(value)?.checked_neg(),
+        UntypedExpr::BinOp {
+            name, left, right, ..
+        } => {
+            let left = eval_constant_arithmetic(left)?;
+            let right = eval_constant_arithmetic(right)?;
+            match name {
+                BinOp::AddInt => left.checked_add(right),
+                BinOp::SubInt => left.checked_sub(right),
+                BinOp::MultInt => left.checked_mul(right),
+                BinOp::DivInt => {
+                    let quotient = left.checked_div(right)?;
+                    if left % right != 0 && (left < 0) != (right < 0) {
+                        Some(quotient - 1)
+                    } else {
+                        Some(quotient)
+                    }
+                }
+                BinOp::ModInt => {
+                    let remainder = left.checked_rem(right)?;
+                    if remainder != 0 && (remainder < 0) != (right < 0) {
+                        Some(remainder + right)
+                    } else {
+                        Some(remainder)
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Variables bound by a pattern, with their location.
+fn pattern_bindings<'a>(pattern: &'a UntypedPattern, bindings: &mut Vec<(&'a str, Span)>) {
+    match pattern {
+        Pattern::Var { name, location } => bindings.push((name, *location)),
+        Pattern::Assign {
+            name,
+            location,
+            pattern,
+        } => {
+            bindings.push((name, *location));
+            pattern_bindings(pattern, bindings);
+        }
+        Pattern::List { elements, tail, .. } => {
+            elements
+                .iter()
+                .chain(tail.as_deref())
+                .for_each(|element| pattern_bindings(element, bindings));
+        }
+        Pattern::Constructor { arguments, .. } => arguments
+            .iter()
+            .for_each(|arg| pattern_bindings(&arg.value, bindings)),
+        Pattern::Pair { fst, snd, .. } => {
+            pattern_bindings(fst, bindings);
+            pattern_bindings(snd, bindings);
+        }
+        Pattern::Tuple { elems, .. } => elems
+            .iter()
+            .for_each(|elem| pattern_bindings(elem, bindings)),
+        Pattern::Int { .. } | Pattern::ByteArray { .. } | Pattern::Discard { .. } => (),
+    }
+}
+
+/// Whether a pattern, or any of its sub-patterns, is a constructor qualified by the given module
+/// alias (e.g. 'option.Some(x)').
+fn pattern_references_module(pattern: &UntypedPattern, alias: &str) -> bool {
+    match pattern {
+        Pattern::Constructor {
+            module, arguments, ..
+        } => {
+            module.as_deref() == Some(alias)
+                || arguments
+                    .iter()
+                    .any(|arg| pattern_references_module(&arg.value, alias))
+        }
+        Pattern::Assign { pattern, .. } => pattern_references_module(pattern, alias),
+        Pattern::List { elements, tail, .. } => elements
+            .iter()
+            .chain(tail.as_deref())
+            .any(|element| pattern_references_module(element, alias)),
+        Pattern::Pair { fst, snd, .. } => {
+            pattern_references_module(fst, alias) || pattern_references_module(snd, alias)
+        }
+        Pattern::Tuple { elems, .. } => elems
+            .iter()
+            .any(|elem| pattern_references_module(elem, alias)),
+        Pattern::Var { .. }
+        | Pattern::Int { .. }
+        | Pattern::ByteArray { .. }
+        | Pattern::Discard { .. } => false,
+    }
+}
+
+/// Locations of the names of constructors, not qualified by any module, referred to by a
+/// pattern or any of its sub-patterns.
+fn pattern_constructors(pattern: &UntypedPattern, name: &str, locations: &mut Vec<Span>) {
+    match pattern {
+        Pattern::Constructor {
+            module,
+            name: constructor,
+            arguments,
+            location,
+            ..
+        } => {
+            if module.is_none() && constructor == name {
+                locations.push(Span {
+                    start: location.start,
+                    end: location.start + name.len(),
+                });
+            }
+            arguments
+                .iter()
+                .for_each(|arg| pattern_constructors(&arg.value, name, locations));
+        }
+        Pattern::Assign { pattern, .. } => pattern_constructors(pattern, name, locations),
+        Pattern::List { elements, tail, .. } => elements
+            .iter()
+            .chain(tail.as_deref())
+            .for_each(|element| pattern_constructors(element, name, locations)),
+        Pattern::Pair { fst, snd, .. } => {
+            pattern_constructors(fst, name, locations);
+            pattern_constructors(snd, name, locations);
+        }
+        Pattern::Tuple { elems, .. } => elems
+            .iter()
+            .for_each(|elem| pattern_constructors(elem, name, locations)),
+        Pattern::Var { .. }
+        | Pattern::Int { .. }
+        | Pattern::ByteArray { .. }
+        | Pattern::Discard { .. } => (),
+    }
+}
 
-    // NOTE: The 'ModuleKind' second argument doesn't matter. This is just added to the final
-    // object but has no influence on the parsing.
-    let (untyped_module, _) = aiken_lang::parser::module(&source_code, ModuleKind::Lib).ok()?;
+/// Variables bound by arguments, with their location.
+fn argument_bindings<'a>(arguments: &'a [UntypedArg], bindings: &mut Vec<(&'a str, Span)>) {
+    for arg in arguments {
+        match &arg.by {
+            ArgBy::ByName(ArgName::Named { name, location, .. }) => {
+                bindings.push((name.as_str(), *location))
+            }
+            ArgBy::ByName(ArgName::Discarded { .. }) => (),
+            ArgBy::ByPattern(pattern) => pattern_bindings(pattern, bindings),
+        }
+    }
+}
 
-    Some(ParsedDocument {
-        definitions: untyped_module.definitions,
-        line_numbers,
-        source_code,
+/// Patterns destructuring arguments in place, if any.
+fn argument_patterns(arguments: &[UntypedArg]) -> impl Iterator<Item = &UntypedPattern> {
+    arguments.iter().filter_map(|arg| match &arg.by {
+        ArgBy::ByPattern(pattern) => Some(pattern),
+        ArgBy::ByName(..) => None,
     })
 }
 
+/// Whether a pattern binds, at the given location, a variable through a record field shorthand
+/// such as 'Foo { bar }'.
+fn binds_field_shorthand(pattern: &UntypedPattern, location: Span) -> bool {
+    match pattern {
+        Pattern::Constructor { arguments, .. } => arguments.iter().any(|arg| {
+            let is_shorthand = arg.label.is_some()
+                && arg.location == location
+                && matches!(arg.value, Pattern::Var { location: var, .. } if var == location);
+            is_shorthand || binds_field_shorthand(&arg.value, location)
+        }),
+        Pattern::Assign { pattern, .. } => binds_field_shorthand(pattern, location),
+        Pattern::List { elements, tail, .. } => elements
+            .iter()
+            .chain(tail.as_deref())
+            .any(|element| binds_field_shorthand(element, location)),
+        Pattern::Pair { fst, snd, .. } => {
+            binds_field_shorthand(fst, location) || binds_field_shorthand(snd, location)
+        }
+        Pattern::Tuple { elems, .. } => elems
+            .iter()
+            .any(|elem| binds_field_shorthand(elem, location)),
+        Pattern::Var { .. }
+        | Pattern::Int { .. }
+        | Pattern::ByteArray { .. }
+        | Pattern::Discard { .. } => false,
+    }
+}
+
+/// Whether an expression is a call to the given function, with the given variable as only
+/// argument.
+fn is_recursion(expr: &UntypedExpr, (function, arg): (&str, &str)) -> bool {
+    match expr {
+        UntypedExpr::Call { fun, arguments, .. } => {
+            matches!(fun.as_ref(), UntypedExpr::Var { name, .. } if name == function)
+                && matches!(
+                    arguments.as_slice(),
+                    [CallArg { label: None, value: UntypedExpr::Var { name, .. }, .. }]
+                        if name == arg
+                )
+        }
+        _ => false,
+    }
+}
+
+/// The single element prepended to a recursive call, in a list expression like '[x, ..f(rest)]'.
+fn prepended_to_recursion<'a>(
+    expr: &'a UntypedExpr,
+    recursion: (&str, &str),
+) -> Option<&'a UntypedExpr> {
+    match expr {
+        UntypedExpr::List {
+            elements,
+            tail: Some(tail),
+            ..
+        } if elements.len() == 1 && is_recursion(tail, recursion) => elements.first(),
+        _ => None,
+    }
+}
+
 /// Insert some text at the given location.
 fn insert_text(at: usize, line_numbers: &LineNumbers, new_text: String) -> lsp_types::TextEdit {
     let range = span_to_lsp_range(Span { start: at, end: at }, line_numbers);
@@ -49,205 +287,3543 @@ fn insert_text(at: usize, line_numbers: &LineNumbers, new_text: String) -> lsp_t
 /// whether the import is a newline or not. It is set to 'false' when adding a qualified import
 /// to an existing list.
 impl ParsedDocument {
-    pub fn import(
-        &self,
-        import: &CheckedModule,
-        unqualified: Option<&str>,
-    ) -> Option<AnnotatedEdit> {
-        let import_path = import.name.split('/').collect_vec();
+    pub fn new(source_code: String) -> Option<Self> {
+        let line_numbers = LineNumbers::new(&source_code);
 
-        let mut last_import = None;
+        // NOTE: The 'ModuleKind' second argument doesn't matter. This is just added to the final
+        // object but has no influence on the parsing.
+        let (untyped_module, _) = aiken_lang::parser::module(&source_code, ModuleKind::Lib).ok()?;
 
-        for def in self.definitions.iter() {
-            match def {
-                Definition::Use(Use {
-                    location,
-                    module: existing_module,
-                    unqualified: unqualified_list,
-                    ..
-                }) => {
-                    last_import = Some(*location);
+        Some(ParsedDocument {
+            module: untyped_module,
+            line_numbers,
+            source_code,
+        })
+    }
 
-                    if import_path != existing_module.as_slice() {
-                        continue;
+    /// Convert an LSP position back into a byte offset in the source code.
+    pub fn byte_index(&self, position: lsp_types::Position) -> usize {
+        self.line_numbers
+            .byte_index(position.line as usize, position.character as usize)
+    }
+
+    /// Collect every expression of the document, parents first.
+    pub fn expressions(&self) -> Vec<&UntypedExpr> {
+        let mut expressions = Vec::new();
+
+        for body in self.module.definitions.iter().flat_map(|def| def.bodies()) {
+            body.walk(&mut |expr| expressions.push(expr));
+        }
+
+        expressions
+    }
+
+    /// Add new clauses at the end of the 'when/is' expression starting at the given location,
+    /// aligned with existing clauses.
+    pub fn add_missing_clauses(&self, start: usize, patterns: &[&str]) -> Option<AnnotatedEdit> {
+        let last_clause = self.expressions().into_iter().find_map(|expr| match expr {
+            UntypedExpr::When {
+                location, clauses, ..
+            } if location.start == start => clauses.last(),
+            _ => None,
+        })?;
+
+        let indent = " ".repeat(
+            self.line_numbers
+                .line_and_column_number(last_clause.location.start)?
+                .column
+                - 1,
+        );
+
+        let new_clauses = patterns
+            .iter()
+            .map(|pattern| format!("\n{indent}{pattern} -> todo"))
+            .join("");
+
+        Some((
+            format!("Add missing clauses: {}", patterns.join(", ")),
+            insert_text(last_clause.location.end, &self.line_numbers, new_clauses),
+        ))
+    }
+
+    /// Remove the 'let' assignment whose (single) pattern, starting at the given location,
+    /// discards its value. Such assignments are erased by the compiler and never evaluate their
+    /// value, so removing them changes nothing. Assignments ending their block are left alone,
+    /// since the block would then lose its value.
+    pub fn remove_discarded_let(&self, pattern_start: usize) -> Option<AnnotatedEdit> {
+        self.expressions().into_iter().find_map(|expr| match expr {
+            UntypedExpr::Sequence { expressions, .. } => {
+                let (_, init) = expressions.split_last()?;
+
+                let location = init.iter().find_map(|expr| match expr {
+                    UntypedExpr::Assignment {
+                        location,
+                        patterns,
+                        kind: AssignmentKind::Let { backpassing: false },
+                        ..
+                    } if patterns.len() == 1
+                        && patterns.first().location.start == pattern_start =>
+                    {
+                        Some(*location)
                     }
+                    _ => None,
+                })?;
 
-                    match unqualified {
-                        // There's already a matching qualified import, so we have nothing to do.
-                        None => return None,
-                        Some(unqualified) => {
-                            let mut last_unqualified = None;
+                Some((
+                    "Remove the discarded assignment".to_string(),
+                    lsp_types::TextEdit {
+                        range: span_to_lsp_range(
+                            self.line_removal_span(location),
+                            &self.line_numbers,
+                        ),
+                        new_text: String::new(),
+                    },
+                ))
+            }
+            _ => None,
+        })
+    }
 
-                            // Insert lexicographically, assuming unqualified imports are already
-                            // ordered. If they are not, it doesn't really matter where we insert
-                            // anyway.
-                            for existing_unqualified in unqualified_list {
-                                last_unqualified = Some(existing_unqualified.location);
+    /// Prefix the name of the unused variable bound at the given location with an underscore, so
+    /// that it's explicitly discarded. Variables bound by a record field shorthand (e.g. 'Foo {
+    /// bar }') keep the field's label.
+    pub fn prefix_unused_variable(&self, name: &str, location: Span) -> Option<AnnotatedEdit> {
+        let binding = self.source_code.get(location.start..location.end)?;
 
-                                let existing_name = existing_unqualified
-                                    .as_name
-                                    .as_ref()
-                                    .unwrap_or(&existing_unqualified.name);
+        // Arguments may carry a label in front of their name.
+        let location = match binding.strip_suffix(name)? {
+            "" => location,
+            label if label.ends_with(char::is_whitespace) => Span {
+                start: location.end - name.len(),
+                end: location.end,
+            },
+            _ => return None,
+        };
 
-                                // The unqualified import already exist, nothing to do.
-                                if unqualified == existing_name {
-                                    return None;
-                                // Current import is lexicographically greater, we can insert before
-                                } else if unqualified < existing_name.as_str() {
-                                    return Some(self.insert_qualified_before(
-                                        import,
-                                        unqualified,
-                                        existing_unqualified.location,
-                                    ));
-                                } else {
-                                    continue;
-                                }
-                            }
+        let is_field_shorthand = self
+            .patterns(location.start)
+            .into_iter()
+            .any(|pattern| binds_field_shorthand(pattern, location));
 
-                            return match last_unqualified {
-                                // Only happens if 'unqualified_list' is empty, in which case, we
-                                // simply create a new unqualified list of import.
-                                None => {
-                                    Some(self.add_new_qualified(import, unqualified, *location))
-                                }
-                                // Happens if the new qualified import is lexicographically after
-                                // all existing ones.
-                                Some(location) => {
-                                    Some(self.insert_qualified_after(import, unqualified, location))
-                                }
-                            };
+        Some((
+            "Prefix unused variable with underscore".to_string(),
+            lsp_types::TextEdit {
+                range: span_to_lsp_range(location, &self.line_numbers),
+                new_text: if is_field_shorthand {
+                    format!("{name}: _{name}")
+                } else {
+                    format!("_{name}")
+                },
+            },
+        ))
+    }
+
+    /// Every pattern of the function (or validator handler) enclosing the given location, be it
+    /// in its arguments or its body.
+    fn patterns(&self, at: usize) -> Vec<&UntypedPattern> {
+        let Some(function) = self.enclosing_function(at) else {
+            return vec![];
+        };
+
+        let mut patterns = argument_patterns(&function.arguments).collect::<Vec<_>>();
+
+        function.body.walk(&mut |expr| match expr {
+            UntypedExpr::Assignment {
+                patterns: assigned, ..
+            } => patterns.extend(assigned.iter().map(|pattern| &pattern.pattern)),
+            UntypedExpr::When { clauses, .. } => {
+                patterns.extend(clauses.iter().flat_map(|clause| clause.patterns.iter()))
+            }
+            UntypedExpr::Fn { arguments, .. } => patterns.extend(argument_patterns(arguments)),
+            _ => (),
+        });
+
+        patterns
+    }
+
+    /// Names of values one may refer to at the given location: those defined or imported by the
+    /// module, and those bound earlier within the enclosing function.
+    pub fn names_in_scope(&self, at: usize) -> Vec<&str> {
+        let mut names = Vec::new();
+
+        for def in self.module.definitions.iter() {
+            match def {
+                Definition::Fn(function) => names.push(function.name.as_str()),
+                Definition::ModuleConstant(constant) => names.push(constant.name.as_str()),
+                Definition::Use(import) => names.extend(
+                    import
+                        .unqualified
+                        .iter()
+                        .map(|unqualified| unqualified.variable_name()),
+                ),
+                _ => (),
+            }
+        }
+
+        names.extend(self.local_names(at));
+
+        names
+    }
+
+    /// Names of the variables one may refer to at the given location within the enclosing
+    /// function.
+    fn local_names(&self, at: usize) -> Vec<&str> {
+        self.bindings_in_scope(at)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Variables one may refer to at the given location within the enclosing function, with the
+    /// location they're bound at. Those are its arguments, and the bindings of the expressions
+    /// enclosing the location: earlier assignments of the same block, patterns of the enclosing
+    /// 'when' clause or 'if/is' branch, and arguments of enclosing anonymous functions. Bindings of
+    /// sibling branches or of blocks which already ended are therefore out of scope.
+    fn bindings_in_scope(&self, at: usize) -> Vec<(&str, Span)> {
+        let mut bindings = Vec::new();
+
+        let Some(function) = self.enclosing_function(at) else {
+            return bindings;
+        };
+
+        argument_bindings(&function.arguments, &mut bindings);
+
+        let encloses = |location: Span| location.start <= at && at <= location.end;
+
+        function.body.walk(&mut |expr| {
+            if !encloses(expr.location()) {
+                return;
+            }
+
+            match expr {
+                UntypedExpr::Sequence { expressions, .. } => {
+                    for expr in expressions {
+                        if let UntypedExpr::Assignment {
+                            patterns, location, ..
+                        } = expr
+                        {
+                            if location.end <= at {
+                                patterns.iter().for_each(|pattern| {
+                                    pattern_bindings(&pattern.pattern, &mut bindings)
+                                });
+                            }
                         }
                     }
                 }
-                _ => continue,
+                UntypedExpr::When { clauses, .. } => clauses
+                    .iter()
+                    .filter(|clause| encloses(clause.location))
+                    .flat_map(|clause| clause.patterns.iter())
+                    .for_each(|pattern| pattern_bindings(pattern, &mut bindings)),
+                UntypedExpr::If { branches, .. } => {
+                    for branch in branches.iter() {
+                        if let Some(is) = &branch.is {
+                            if encloses(branch.body.location()) {
+                                pattern_bindings(&is.pattern, &mut bindings);
+                            }
+                        }
+                    }
+                }
+                UntypedExpr::Fn { arguments, .. } => argument_bindings(arguments, &mut bindings),
+                _ => (),
             }
-        }
+        });
 
-        // If the search above didn't lead to anything, we simply insert the import either:
-        //
-        // (a) After the last import statement if any;
-        // (b) As the first statement in the module.
-        Some(self.add_new_import_line(import, unqualified, last_import))
+        bindings
     }
 
-    pub fn remove_import(&self, start: usize, is_qualified: bool) -> AnnotatedEdit {
-        let offset = if is_qualified {
-            let import_len = self
-                .source_code
-                .chars()
-                .skip(start)
-                .take_while(|c| c != &',' && c != &'}')
-                .count();
+    /// The definition enclosing the given location. Definitions follow one another, so that's
+    /// the last one to start before it.
+    fn enclosing_definition(&self, at: usize) -> Option<&UntypedDefinition> {
+        self.module
+            .definitions
+            .iter()
+            .rfind(|def| def.location().start <= at)
+    }
 
-            let has_trailing_comma = self
-                .source_code
-                .chars()
-                .skip(start + import_len)
-                .collect::<String>()
-                .starts_with(',');
+    /// The function, or validator handler, enclosing the given location.
+    fn enclosing_function(&self, at: usize) -> Option<&UntypedFunction> {
+        match self.enclosing_definition(at)? {
+            Definition::Fn(function) => Some(function),
+            Definition::Validator(validator) => validator
+                .handlers
+                .iter()
+                .chain(std::iter::once(&validator.fallback))
+                .filter(|handler| handler.location.start <= at)
+                .max_by_key(|handler| handler.location.start),
+            _ => None,
+        }
+    }
 
-            import_len + if has_trailing_comma { 1 } else { 0 }
-        } else {
-            1 + self
-                .source_code
-                .chars()
-                .skip(start)
-                .take_while(|c| c != &'\n')
-                .count()
+    /// Remove every 'trace' of the function (or validator handler) enclosing the given location,
+    /// keeping what follows each of them. Traces ending a function are left untouched, since
+    /// removing them would leave the function without a body.
+    pub fn remove_traces(&self, at: usize) -> Vec<AnnotatedEdit> {
+        let body = match self.enclosing_definition(at) {
+            Some(Definition::Test(test)) => Some(&test.body),
+            _ => self.enclosing_function(at).map(|function| &function.body),
         };
 
-        let range = span_to_lsp_range(
-            Span {
-                start,
-                end: start + offset,
-            },
-            &self.line_numbers,
-        );
+        let Some(body) = body else {
+            return vec![];
+        };
 
-        let new_text = String::new();
+        let mut edits = Vec::new();
 
-        (
-            "Remove redundant import".to_string(),
-            lsp_types::TextEdit { range, new_text },
-        )
+        body.walk(&mut |expr| {
+            if let UntypedExpr::Trace {
+                kind: TraceKind::Trace,
+                location,
+                then,
+                ..
+            } = expr
+            {
+                if then.location() != *location {
+                    edits.push((
+                        "Remove trace".to_string(),
+                        lsp_types::TextEdit {
+                            range: span_to_lsp_range(
+                                self.trace_removal_span(*location, then),
+                                &self.line_numbers,
+                            ),
+                            new_text: String::new(),
+                        },
+                    ));
+                }
+            }
+        });
+
+        edits
     }
 
-    fn insert_qualified_before(
-        &self,
-        import: &CheckedModule,
-        unqualified: &str,
-        location: Span,
-    ) -> AnnotatedEdit {
-        let title = format!("Use '{}' from {}", unqualified, import.name);
-        (
-            title,
-            insert_text(
-                location.start,
-                &self.line_numbers,
-                format!("{}, ", unqualified),
-            ),
-        )
+    /// Remove the trace starting at the given location, keeping whatever expression follows it.
+    pub fn remove_trace(&self, start: usize) -> Option<AnnotatedEdit> {
+        self.expressions().into_iter().find_map(|expr| match expr {
+            UntypedExpr::Trace {
+                kind: TraceKind::Trace,
+                location,
+                then,
+                ..
+            } if location.start == start => Some((
+                "Remove trace".to_string(),
+                lsp_types::TextEdit {
+                    range: span_to_lsp_range(
+                        self.trace_removal_span(*location, then),
+                        &self.line_numbers,
+                    ),
+                    new_text: String::new(),
+                },
+            )),
+            _ => None,
+        })
     }
 
-    fn insert_qualified_after(
-        &self,
-        import: &CheckedModule,
-        unqualified: &str,
-        location: Span,
-    ) -> AnnotatedEdit {
-        let title = format!("Use '{}' from {}", unqualified, import.name);
-        (
-            title,
-            insert_text(
-                location.end,
-                &self.line_numbers,
-                format!(", {}", unqualified),
-            ),
-        )
+    /// Remove the statements which follow an unconditional failure ending at the given location,
+    /// up to the end of their block; the closing brace of the block is left untouched.
+    pub fn remove_unreachable_code(&self, failure_end: usize) -> Option<AnnotatedEdit> {
+        self.expressions().into_iter().find_map(|expr| match expr {
+            UntypedExpr::Sequence { expressions, .. } => {
+                let failure = expressions.iter().position(|expr| {
+                    expr.location().end == failure_end
+                        && matches!(
+                            expr,
+                            UntypedExpr::ErrorTerm { .. }
+                                | UntypedExpr::Trace {
+                                    kind: TraceKind::Error | TraceKind::Todo,
+                                    ..
+                                }
+                        )
+                })?;
+
+                let last = expressions.last()?;
+
+                if failure + 1 == expressions.len() {
+                    return None;
+                }
+
+                Some((
+                    "Remove unreachable code".to_string(),
+                    lsp_types::TextEdit {
+                        range: span_to_lsp_range(
+                            Span {
+                                start: failure_end,
+                                end: last.location().end,
+                            },
+                            &self.line_numbers,
+                        ),
+                        new_text: String::new(),
+                    },
+                ))
+            }
+            _ => None,
+        })
     }
 
-    fn add_new_qualified(
-        &self,
-        import: &CheckedModule,
-        unqualified: &str,
-        location: Span,
-    ) -> AnnotatedEdit {
-        let title = format!("Use '{}' from {}", unqualified, import.name);
-        (
-            title,
-            insert_text(
-                location.end,
-                &self.line_numbers,
-                format!(".{{{}}}", unqualified),
-            ),
-        )
+    /// The prefix of a trace, up to the expression following it. A trace with nothing after it
+    /// (i.e. 'trace msg') is removed entirely, along with its line when it stands on its own; and
+    /// a block left with nothing in it collapses into '{}'.
+    fn trace_removal_span(&self, location: Span, then: &UntypedExpr) -> Span {
+        if then.location() != location {
+            return Span {
+                start: location.start,
+                end: then.start_byte_index(),
+            };
+        }
+
+        let before = self.source_code[..location.start].trim_end();
+        let after = self.source_code[location.end..].trim_start();
+
+        if before.ends_with('{') && after.starts_with('}') {
+            return Span {
+                start: before.len(),
+                end: self.source_code.len() - after.len(),
+            };
+        }
+
+        self.line_removal_span(location)
     }
 
-    fn add_new_import_line(
-        &self,
-        import: &CheckedModule,
-        unqualified: Option<&str>,
-        location: Option<Span>,
-    ) -> AnnotatedEdit {
-        let import_line = format!(
-            "use {}{}",
-            import.name,
-            match unqualified {
-                Some(unqualified) => format!(".{{{}}}", unqualified),
-                None => String::new(),
+    /// The span of an expression, extended to its whole line(s) when it stands on its own.
+    fn line_removal_span(&self, location: Span) -> Span {
+        let line_start = self.source_code[..location.start]
+            .rfind('\n')
+            .map(|ix| ix + 1)
+            .unwrap_or(0);
+
+        if self.source_code[line_start..location.start]
+            .trim()
+            .is_empty()
+            && self.source_code[location.end..].starts_with('\n')
+        {
+            Span {
+                start: line_start,
+                end: location.end + 1,
             }
-        );
+        } else {
+            location
+        }
+    }
 
-        let title = format!("Add new import line: {import_line}");
+    /// Move into the signature the destructuring of a parameter done by a 'let' right at the
+    /// start of the function enclosing the given location. Parameters otherwise used as a whole
+    /// are left untouched.
+    pub fn destructure_parameter(&self, at: usize) -> Vec<AnnotatedEdit> {
+        let Some(function) = self.enclosing_function(at) else {
+            return vec![];
+        };
 
-        (
-            title,
-            match location {
-                None => insert_text(0, &self.line_numbers, format!("{import_line}\n")),
-                Some(Span { end, .. }) => {
-                    insert_text(end, &self.line_numbers, format!("\n{import_line}"))
+        let (assignment, next) = match &function.body {
+            UntypedExpr::Sequence { expressions, .. } if expressions.len() > 1 => {
+                (&expressions[0], &expressions[1])
+            }
+            _ => return vec![],
+        };
+
+        let UntypedExpr::Assignment {
+            location,
+            value,
+            patterns,
+            kind: AssignmentKind::Let { backpassing: false },
+        } = assignment
+        else {
+            return vec![];
+        };
+
+        let (UntypedExpr::Var { name, .. }, [pattern]) = (value.as_ref(), patterns.as_slice())
+        else {
+            return vec![];
+        };
+
+        if pattern.annotation.is_some()
+            || !matches!(
+                pattern.pattern,
+                Pattern::Constructor { .. } | Pattern::Tuple { .. } | Pattern::Pair { .. }
+            )
+        {
+            return vec![];
+        }
+
+        // Labelled parameters can't be patterns, so they're out of the picture.
+        let parameter = function.arguments.iter().find_map(|arg| match &arg.by {
+            ArgBy::ByName(ArgName::Named {
+                name: parameter,
+                label,
+                location,
+            }) if parameter == name && label == name => Some(*location),
+            _ => None,
+        });
+
+        let Some(parameter) = parameter else {
+            return vec![];
+        };
+
+        let mut uses = 0;
+        function.body.walk(&mut |expr| {
+            if matches!(expr, UntypedExpr::Var { name: var, .. } if var == name) {
+                uses += 1;
+            }
+        });
+
+        if uses > 1 {
+            return vec![];
+        }
+
+        let pattern_location = pattern.pattern.location();
+
+        vec![
+            (
+                format!("Destructure '{name}' in the signature"),
+                lsp_types::TextEdit {
+                    range: span_to_lsp_range(parameter, &self.line_numbers),
+                    new_text: self.source_code[pattern_location.start..pattern_location.end]
+                        .to_string(),
+                },
+            ),
+            (
+                format!("Remove the destructuring of '{name}'"),
+                lsp_types::TextEdit {
+                    range: span_to_lsp_range(
+                        Span {
+                            start: location.start,
+                            end: next.start_byte_index(),
+                        },
+                        &self.line_numbers,
+                    ),
+                    new_text: String::new(),
+                },
+            ),
+        ]
+    }
+
+    /// Rewrite the body of the function enclosing the given location with 'list.map' or
+    /// 'list.filter', when it is a plain recursion over its only argument equivalent to either.
+    /// Only the most direct shapes are recognized, so that semantics are surely preserved.
+    pub fn list_combinator(&self, at: usize) -> Option<AnnotatedEdit> {
+        let Some(Definition::Fn(function)) = self.enclosing_definition(at) else {
+            return None;
+        };
+
+        let [UntypedArg {
+            by: ArgBy::ByName(ArgName::Named { name: list, .. }),
+            ..
+        }] = function.arguments.as_slice()
+        else {
+            return None;
+        };
+
+        let UntypedExpr::When {
+            location,
+            subject,
+            clauses,
+        } = &function.body
+        else {
+            return None;
+        };
+
+        if !matches!(subject.as_ref(), UntypedExpr::Var { name, .. } if name == list) {
+            return None;
+        }
+
+        let is_empty_clause = |clause: &UntypedClause| {
+            matches!(
+                clause.patterns.as_slice(),
+                [Pattern::List { elements, tail: None, .. }] if elements.is_empty()
+            ) && matches!(
+                &clause.then,
+                UntypedExpr::List { elements, tail: None, .. } if elements.is_empty()
+            )
+        };
+
+        let [first, second] = clauses.as_slice() else {
+            return None;
+        };
+
+        let (empty, cons) = if is_empty_clause(first) {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        if !is_empty_clause(empty) {
+            return None;
+        }
+
+        let [Pattern::List {
+            elements,
+            tail: Some(tail),
+            ..
+        }] = cons.patterns.as_slice()
+        else {
+            return None;
+        };
+
+        let ([Pattern::Var { name: elem, .. }], Pattern::Var { name: rest, .. }) =
+            (elements.as_slice(), tail.as_ref())
+        else {
+            return None;
+        };
+
+        let then = &cons.then;
+
+        let recursion = (function.name.as_str(), rest.as_str());
+
+        let (combinator, lambda_body) = match then {
+            UntypedExpr::If {
+                branches,
+                final_else,
+                ..
+            } if branches.len() == 1 && is_recursion(final_else, recursion) => {
+                let branch = branches.first();
+                match prepended_to_recursion(&branch.body, recursion) {
+                    Some(UntypedExpr::Var { name, .. }) if name == elem && branch.is.is_none() => {
+                        ("filter", &branch.condition)
+                    }
+                    _ => return None,
+                }
+            }
+            _ => ("map", prepended_to_recursion(then, recursion)?),
+        };
+
+        let mut self_contained = true;
+        lambda_body.walk(&mut |expr| {
+            if let UntypedExpr::Var { name, .. } = expr {
+                if name == rest || name == list || name == &function.name {
+                    self_contained = false;
+                }
+            }
+        });
+
+        if !self_contained {
+            return None;
+        }
+
+        let qualifier = self
+            .module
+            .definitions
+            .iter()
+            .find_map(|def| match def {
+                Definition::Use(Use {
+                    module, as_name, ..
+                }) if module.join("/") == LIST_MODULE => as_name.clone(),
+                _ => None,
+            })
+            .unwrap_or_else(|| "list".to_string());
+
+        let body = lambda_body.location();
+
+        Some((
+            format!("Replace with '{qualifier}.{combinator}'"),
+            lsp_types::TextEdit {
+                range: span_to_lsp_range(*location, &self.line_numbers),
+                new_text: format!(
+                    "{qualifier}.{combinator}({list}, fn({elem}) {{ {} }})",
+                    &self.source_code[body.start..body.end]
+                ),
+            },
+        ))
+    }
+
+    /// Rename the variable bound by the 'let' at the given location, when it shadows another
+    /// binding in scope, along with every reference to it. Names rebound
+    /// over and over are deliberately left alone, as that's an idiom for successive updates.
+    pub fn rename_shadowing_binding(&self, at: usize) -> Vec<AnnotatedEdit> {
+        let Some(function) = self.enclosing_function(at) else {
+            return vec![];
+        };
+
+        let mut shadowing = None;
+        function.body.walk(&mut |expr| {
+            if let UntypedExpr::Sequence { expressions, .. } = expr {
+                for (ix, expr) in expressions.iter().enumerate() {
+                    if let UntypedExpr::Assignment {
+                        location,
+                        patterns,
+                        kind: AssignmentKind::Let { backpassing: false },
+                        ..
+                    } = expr
+                    {
+                        if let [AssignmentPattern {
+                            pattern:
+                                Pattern::Var {
+                                    name,
+                                    location: var,
+                                },
+                            ..
+                        }] = patterns.as_slice()
+                        {
+                            if location.start <= at && at <= location.end {
+                                shadowing = Some((name, *var, &expressions[ix + 1..]));
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let Some((name, var, scope)) = shadowing else {
+            return vec![];
+        };
+
+        let mut bindings = Vec::new();
+        argument_bindings(&function.arguments, &mut bindings);
+
+        let mut references = Vec::new();
+        // Variables given to a record through the field shorthand (e.g. 'Foo { x }'), whose
+        // label must be kept.
+        let mut shorthands = Vec::new();
+        function.body.walk(&mut |expr| match expr {
+            UntypedExpr::Assignment { patterns, .. } => patterns
+                .iter()
+                .for_each(|pattern| pattern_bindings(&pattern.pattern, &mut bindings)),
+            UntypedExpr::When { clauses, .. } => clauses
+                .iter()
+                .flat_map(|clause| clause.patterns.iter())
+                .for_each(|pattern| pattern_bindings(pattern, &mut bindings)),
+            UntypedExpr::Fn { arguments, .. } => {
+                for arg in arguments {
+                    if let ArgBy::ByName(ArgName::Named { name, location, .. }) = &arg.by {
+                        bindings.push((name.as_str(), *location));
+                    }
+                }
+            }
+            UntypedExpr::Var { name, location } => references.push((name.as_str(), *location)),
+            UntypedExpr::Call { arguments, .. } => shorthands.extend(
+                arguments
+                    .iter()
+                    .filter(|arg| {
+                        matches!(&arg.value, UntypedExpr::Var { name, location }
+                            if arg.label.as_ref() == Some(name) && arg.location == *location)
+                    })
+                    .map(|arg| arg.location),
+            ),
+            _ => (),
+        });
+
+        let shadowed = self
+            .bindings_in_scope(var.start)
+            .iter()
+            .filter(|(other, _)| other == name)
+            .count();
+
+        let rebound_in_scope = bindings.iter().any(|(other, location)| {
+            other == name
+                && scope
+                    .iter()
+                    .any(|expr| expr.location().contains(location.start))
+        });
+
+        if shadowed == 0 || shadowed > 1 || rebound_in_scope {
+            return vec![];
+        }
+
+        let new_name = (2..)
+            .map(|n| format!("{name}_{n}"))
+            .find(|candidate| {
+                bindings
+                    .iter()
+                    .chain(references.iter())
+                    .all(|(other, _)| other != candidate)
+            })
+            .expect("infinite candidates");
+
+        let title = format!("Rename shadowing '{name}' to '{new_name}'");
+
+        references
+            .iter()
+            .filter(|(other, location)| {
+                other == name
+                    && scope
+                        .iter()
+                        .any(|expr| expr.location().contains(location.start))
+            })
+            .map(|(_, location)| *location)
+            .chain(std::iter::once(var))
+            .map(|location| {
+                (
+                    title.clone(),
+                    lsp_types::TextEdit {
+                        range: span_to_lsp_range(location, &self.line_numbers),
+                        new_text: if shorthands.contains(&location) {
+                            format!("{name}: {new_name}")
+                        } else {
+                            new_name.clone()
+                        },
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Replace the outermost arithmetic expression around the given location that only involves
+    /// integer literals with its (digit-grouped) value.
+    pub fn fold_constant_arithmetic(&self, at: usize) -> Option<AnnotatedEdit> {
+        let (expr, value) = self.expressions().into_iter().find_map(|expr| match expr {
+            UntypedExpr::BinOp { location, .. } if location.start <= at && at <= location.end => {
+                Some((expr, eval_constant_arithmetic(expr)?))
+            }
+            _ => None,
+        })?;
+
+        // NOTE: Operators' locations don't include the parentheses around their leftmost or
+        // rightmost operands, which we find back from the tokens.
+        let tokens = lexer::run(&self.source_code).ok()?.tokens;
+        let location = format::expression_span(&format::parentheses(&tokens), expr);
+
+        let value = Formatter::new()
+            .int(
+                &value.to_string(),
+                &Base::Decimal {
+                    numeric_underscore: true,
+                },
+            )
+            .to_pretty_string(MAX_COLUMNS);
+
+        Some((
+            format!("Fold into {value}"),
+            lsp_types::TextEdit {
+                range: span_to_lsp_range(location, &self.line_numbers),
+                new_text: value,
+            },
+        ))
+    }
+
+    /// Annotate the 'let' or 'expect' assignment of an empty list literal found at the given
+    /// location, so that its element type no longer depends on how the binding is used.
+    pub fn annotate_empty_list(&self, at: usize, annotation: &str) -> Option<AnnotatedEdit> {
+        let pattern = self.expressions().into_iter().find_map(|expr| match expr {
+            UntypedExpr::Assignment {
+                value, patterns, ..
+            } if patterns.len() == 1 && patterns.first().annotation.is_none() => {
+                match value.as_ref() {
+                    UntypedExpr::List {
+                        location,
+                        elements,
+                        tail: None,
+                    } if elements.is_empty() && location.start <= at && at <= location.end => {
+                        Some(&patterns.first().pattern)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })?;
+
+        let title = format!("Annotate as {annotation}");
+
+        Some((
+            title,
+            insert_text(
+                pattern.location().end,
+                &self.line_numbers,
+                format!(": {annotation}"),
+            ),
+        ))
+    }
+
+    /// The type annotation missing around the given location, if any: that of the innermost 'let'
+    /// binding, or otherwise the return annotation of the function whose signature it is in.
+    pub fn missing_annotation(&self, at: usize) -> Option<MissingAnnotation> {
+        let binding = self
+            .expressions()
+            .into_iter()
+            .rev()
+            .find_map(|expr| match expr {
+                UntypedExpr::Assignment {
+                    location,
+                    patterns,
+                    kind,
+                    ..
+                } if kind.is_let()
+                    && !kind.is_backpassing()
+                    && patterns.len() == 1
+                    && patterns.first().annotation.is_none()
+                    && location.start <= at
+                    && at <= location.end =>
+                {
+                    Some(MissingAnnotation::Binding {
+                        location: *location,
+                        pattern_end: patterns.first().pattern.location().end,
+                    })
+                }
+                _ => None,
+            });
+
+        binding.or_else(|| match self.enclosing_definition(at)? {
+            Definition::Fn(function)
+                if function.return_annotation.is_none()
+                    && function.location.start <= at
+                    && at <= function.location.end =>
+            {
+                Some(MissingAnnotation::ReturnType {
+                    location: function.location,
+                })
+            }
+            _ => None,
+        })
+    }
+
+    /// Insert the given type as annotation of a binding, or as return annotation of a function.
+    pub fn add_annotation(&self, missing: &MissingAnnotation, annotation: &str) -> AnnotatedEdit {
+        match missing {
+            MissingAnnotation::Binding { pattern_end, .. } => (
+                format!("Annotate as {annotation}"),
+                insert_text(*pattern_end, &self.line_numbers, format!(": {annotation}")),
+            ),
+            MissingAnnotation::ReturnType { location } => (
+                format!("Annotate return type as {annotation}"),
+                insert_text(
+                    location.end,
+                    &self.line_numbers,
+                    format!(" -> {annotation}"),
+                ),
+            ),
+        }
+    }
+
+    /// Rewrite the innermost 'if/else' chain around the given location into a 'when' expression,
+    /// when each of its conditions compares the same subject against a literal or a constructor.
+    pub fn if_to_when(&self, at: usize) -> Option<AnnotatedEdit> {
+        let (location, branches, final_else) =
+            self.expressions()
+                .into_iter()
+                .rev()
+                .find_map(|expr| match expr {
+                    UntypedExpr::If {
+                        location,
+                        branches,
+                        final_else,
+                    } if location.start <= at && at <= location.end => {
+                        Some((*location, branches, final_else))
+                    }
+                    _ => None,
+                })?;
+
+        let text = |span: Span| &self.source_code[span.start..span.end];
+
+        // NOTE: The formatter has no knowledge of comments here, so we'd rather not offer
+        // anything than silently drop some.
+        if text(location).contains("//") {
+            return None;
+        }
+
+        let as_pattern = |expr: &UntypedExpr| match expr {
+            UntypedExpr::UInt {
+                location,
+                value,
+                base,
+            } => Some(Pattern::Int {
+                location: *location,
+                value: value.clone(),
+                base: *base,
+            }),
+            UntypedExpr::ByteArray {
+                location,
+                bytes,
+                preferred_format,
+            } => Some(Pattern::ByteArray {
+                location: *location,
+                value: bytes.clone(),
+                preferred_format: *preferred_format,
+            }),
+            UntypedExpr::Var { location, name } if name.starts_with(char::is_uppercase) => {
+                Some(Pattern::Constructor {
+                    is_record: false,
+                    location: *location,
+                    name: name.clone(),
+                    arguments: vec![],
+                    module: None,
+                    constructor: (),
+                    spread_location: None,
+                    tipo: (),
+                })
+            }
+            _ => None,
+        };
+
+        let mut subject = None;
+        let mut clauses = Vec::new();
+
+        for branch in branches.iter() {
+            let UntypedExpr::BinOp {
+                name: BinOp::Eq,
+                left,
+                right,
+                ..
+            } = &branch.condition
+            else {
+                return None;
+            };
+
+            if branch.is.is_some() {
+                return None;
+            }
+
+            let (scrutinee, pattern) = match (as_pattern(left), as_pattern(right)) {
+                (_, Some(pattern)) => (left.as_ref(), pattern),
+                (Some(pattern), None) => (right.as_ref(), pattern),
+                (None, None) => return None,
+            };
+
+            match subject {
+                None => subject = Some(scrutinee),
+                Some(subject) if text(subject.location()) == text(scrutinee.location()) => (),
+                Some(_) => return None,
+            }
+
+            if clauses.iter().any(|clause: &UntypedClause| {
+                text(clause.patterns.first().location()) == text(pattern.location())
+            }) {
+                return None;
+            }
+
+            clauses.push(UntypedClause {
+                location: branch.location,
+                patterns: Vec1::new(pattern),
+                then: branch.body.clone(),
+            });
+        }
+
+        clauses.push(UntypedClause {
+            location: final_else.location(),
+            patterns: Vec1::new(Pattern::Discard {
+                name: "_".to_string(),
+                location: final_else.location(),
+            }),
+            then: final_else.as_ref().clone(),
+        });
+
+        let when = UntypedExpr::When {
+            location,
+            subject: Box::new(subject?.clone()),
+            clauses,
+        };
+
+        let line_start = self.source_code[..location.start]
+            .rfind('\n')
+            .map(|ix| ix + 1)
+            .unwrap_or(0);
+
+        let indentation = self.source_code[line_start..location.start]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect::<String>();
+
+        let new_text = Formatter::new()
+            .expr(&when, false)
+            .to_pretty_string(MAX_COLUMNS)
+            .lines()
+            .join(&format!("\n{indentation}"));
+
+        Some((
+            "Convert to when expression".to_string(),
+            lsp_types::TextEdit {
+                range: span_to_lsp_range(location, &self.line_numbers),
+                new_text,
+            },
+        ))
+    }
+
+    /// Collapse the innermost 'when' around the given location into an equivalent boolean
+    /// expression, when it has two clauses returning 'True' and 'False', one of which matches a
+    /// constructor without fields, and the other all remaining values.
+    pub fn when_to_boolean(&self, at: usize) -> Option<AnnotatedEdit> {
+        let when = self
+            .expressions()
+            .into_iter()
+            .rev()
+            .find_map(|expr| match expr {
+                UntypedExpr::When {
+                    location,
+                    subject,
+                    clauses,
+                } if location.start <= at && at <= location.end => {
+                    Some((*location, subject, clauses.as_slice()))
+                }
+                _ => None,
+            });
+
+        let (location, subject, [first, second]) = when? else {
+            return None;
+        };
+
+        let as_bool = |expr: &UntypedExpr| match expr {
+            UntypedExpr::Var { name, .. } if name == "True" => Some(true),
+            UntypedExpr::Var { name, .. } if name == "False" => Some(false),
+            _ => None,
+        };
+
+        let ([first_pattern], [second_pattern], Some(first_then), Some(second_then)) = (
+            first.patterns.as_slice(),
+            second.patterns.as_slice(),
+            as_bool(&first.then),
+            as_bool(&second.then),
+        ) else {
+            return None;
+        };
+
+        if first_then == second_then {
+            return None;
+        }
+
+        let is_fieldless = |pattern: &Pattern<(), ()>| {
+            matches!(
+                pattern,
+                Pattern::Constructor {
+                    arguments,
+                    spread_location: None,
+                    ..
+                } if arguments.is_empty()
+            )
+        };
+
+        let is_catch_all = |pattern: &Pattern<(), ()>| match pattern {
+            Pattern::Discard { .. } | Pattern::Var { .. } => true,
+            Pattern::Constructor { arguments, .. } => arguments
+                .iter()
+                .all(|arg| matches!(arg.value, Pattern::Discard { .. } | Pattern::Var { .. })),
+            _ => false,
+        };
+
+        let (constructor, then) = if is_fieldless(first_pattern) && is_catch_all(second_pattern) {
+            (first_pattern, first_then)
+        } else if is_fieldless(second_pattern) && is_catch_all(first_pattern) {
+            (second_pattern, second_then)
+        } else {
+            return None;
+        };
+
+        let text = |span: Span| &self.source_code[span.start..span.end];
+
+        let subject_text = match subject.as_ref() {
+            UntypedExpr::Var { .. }
+            | UntypedExpr::Call { .. }
+            | UntypedExpr::FieldAccess { .. }
+            | UntypedExpr::TupleIndex { .. } => text(subject.location()).to_string(),
+            _ => format!("({})", text(subject.location())),
+        };
+
+        let new_text = match constructor {
+            Pattern::Constructor {
+                name, module: None, ..
+            } if name == "True" || name == "False" => {
+                if (name == "True") == then {
+                    subject_text
+                } else {
+                    format!("!{subject_text}")
+                }
+            }
+            _ => format!(
+                "{subject_text} {} {}",
+                if then { "==" } else { "!=" },
+                text(constructor.location())
+            ),
+        };
+
+        Some((
+            format!("Replace with '{new_text}'"),
+            lsp_types::TextEdit {
+                range: span_to_lsp_range(location, &self.line_numbers),
+                new_text,
+            },
+        ))
+    }
+
+    /// Replace the innermost anonymous function around the given location by the function it
+    /// forwards all its arguments to, in order (e.g. 'fn(x) { f(x) }' becomes 'f'); provided that
+    /// function is referred to by name, possibly qualified by its module.
+    pub fn point_free(&self, at: usize) -> Option<AnnotatedEdit> {
+        let (location, arguments, body) =
+            self.expressions()
+                .into_iter()
+                .rev()
+                .find_map(|expr| match expr {
+                    UntypedExpr::Fn {
+                        location,
+                        fn_style: FnStyle::Plain,
+                        arguments,
+                        body,
+                        ..
+                    } if location.start <= at && at <= location.end => {
+                        Some((*location, arguments, body))
+                    }
+                    _ => None,
+                })?;
+
+        let UntypedExpr::Call {
+            fun,
+            arguments: call_arguments,
+            ..
+        } = body.as_ref()
+        else {
+            return None;
+        };
+
+        let parameters = arguments
+            .iter()
+            .map(|arg| match &arg.by {
+                ArgBy::ByName(ArgName::Named { name, .. }) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let forwarded = call_arguments
+            .iter()
+            .map(|arg| match arg {
+                CallArg {
+                    label: None,
+                    value: UntypedExpr::Var { name, .. },
+                    ..
+                } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        if parameters.is_empty() || parameters != forwarded {
+            return None;
+        }
+
+        // Only functions referred to by name are passed as is. Anything else (e.g. a partial
+        // application) would be evaluated once, ahead of any call, rather than on each call.
+        let is_reference = match fun.as_ref() {
+            UntypedExpr::Var { name, .. } => !parameters.contains(&name.as_str()),
+            UntypedExpr::FieldAccess { container, .. } => matches!(
+                container.as_ref(),
+                UntypedExpr::Var { name, .. } if self.imports_module(name)
+            ),
+            _ => false,
+        };
+
+        if !is_reference {
+            return None;
+        }
+
+        let fun = fun.location();
+        let new_text = self.source_code[fun.start..fun.end].to_string();
+
+        Some((
+            format!("Replace with '{new_text}'"),
+            lsp_types::TextEdit {
+                range: span_to_lsp_range(location, &self.line_numbers),
+                new_text,
+            },
+        ))
+    }
+
+    /// Whether a module is imported under the given name, be it its alias or last segment.
+    fn imports_module(&self, name: &str) -> bool {
+        self.module.definitions.iter().any(|def| match def {
+            Definition::Use(Use {
+                module, as_name, ..
+            }) => as_name.as_deref().or(module.last().map(String::as_str)) == Some(name),
+            _ => false,
+        })
+    }
+
+    /// Substitute the (private, non-parameterized) type alias defined at the given location by
+    /// its definition at its single use site, and remove the alias altogether.
+    pub fn inline_type_alias(&self, alias_start: usize, use_location: Span) -> Vec<AnnotatedEdit> {
+        let alias = self.module.definitions.iter().find_map(|def| match def {
+            Definition::TypeAlias(alias)
+                if alias.location.start == alias_start
+                    && !alias.public
+                    && alias.parameters.is_empty() =>
+            {
+                Some(alias)
+            }
+            _ => None,
+        });
+
+        let Some(alias) = alias else {
+            return vec![];
+        };
+
+        let annotation = alias.annotation.location();
+
+        vec![
+            (
+                format!("Inline type alias '{}'", alias.alias),
+                lsp_types::TextEdit {
+                    range: span_to_lsp_range(use_location, &self.line_numbers),
+                    new_text: self.source_code[annotation.start..annotation.end].to_string(),
+                },
+            ),
+            (
+                format!("Remove type alias '{}'", alias.alias),
+                lsp_types::TextEdit {
+                    range: span_to_lsp_range(
+                        self.definition_removal_span(alias.location),
+                        &self.line_numbers,
+                    ),
+                    new_text: String::new(),
+                },
+            ),
+        ]
+    }
+
+    /// Remove the definitions starting at each of the given locations. Edits are ordered from
+    /// the bottom of the document up, so that applying one doesn't shift the next ones.
+    pub fn remove_definitions(&self, starts: &[usize]) -> Vec<AnnotatedEdit> {
+        self.module
+            .definitions
+            .iter()
+            .filter(|def| starts.contains(&def.location().start))
+            .map(|def| {
+                let location = match def {
+                    Definition::Fn(function) => Span {
+                        start: function.location.start,
+                        end: function.end_position + 1,
+                    },
+                    _ => def.location(),
+                };
+
+                (
+                    "Remove unused definition".to_string(),
+                    lsp_types::TextEdit {
+                        range: span_to_lsp_range(
+                            self.definition_removal_span(location),
+                            &self.line_numbers,
+                        ),
+                        new_text: String::new(),
+                    },
+                )
+            })
+            .sorted_by(|(_, a), (_, b)| b.range.start.cmp(&a.range.start))
+            .collect()
+    }
+
+    /// Extend the location of a definition to also cover its doc comments and the blank line
+    /// after it; so that removing it leaves no trace.
+    fn definition_removal_span(&self, location: Span) -> Span {
+        let mut start = location.start;
+        while let Some(line) = self.source_code[..start]
+            .strip_suffix('\n')
+            .and_then(|preceding| preceding.lines().last())
+            .filter(|line| line.trim_start().starts_with("///"))
+        {
+            start -= line.len() + 1;
+        }
+
+        let end = location.end
+            + self.source_code[location.end..]
+                .chars()
+                .take_while(|c| *c == '\n')
+                .take(2)
+                .count();
+
+        Span { start, end }
+    }
+
+    /// Replace parentheses wrapping an operand of a binary operator by the operand itself. Nothing
+    /// is done unless the range is made of parentheses (and whitespace) around such an operand.
+    pub fn remove_parentheses(&self, range: lsp_types::Range) -> Option<AnnotatedEdit> {
+        let start = self.byte_index(range.start);
+        let end = self.byte_index(range.end);
+
+        let operand = self.expressions().into_iter().find_map(|expr| match expr {
+            UntypedExpr::BinOp { left, right, .. } => {
+                [left, right].into_iter().find_map(|operand| {
+                    let location = operand.location();
+                    (start < location.start && location.end < end).then_some(location)
+                })
+            }
+            _ => None,
+        })?;
+
+        let is_parentheses = |text: &str| {
+            text.chars()
+                .all(|c| c.is_whitespace() || c == '(' || c == ')')
+        };
+
+        if !is_parentheses(self.source_code.get(start..operand.start)?)
+            || !is_parentheses(self.source_code.get(operand.end..end)?)
+        {
+            return None;
+        }
+
+        Some((
+            "Remove redundant parentheses".to_string(),
+            lsp_types::TextEdit {
+                range,
+                new_text: self
+                    .source_code
+                    .get(operand.start..operand.end)?
+                    .to_string(),
+            },
+        ))
+    }
+
+    pub fn import(
+        &self,
+        import: &CheckedModule,
+        unqualified: Option<&str>,
+    ) -> Option<AnnotatedEdit> {
+        let import_path = import.name.split('/').collect_vec();
+
+        let mut last_import = None;
+
+        for def in self.module.definitions.iter() {
+            match def {
+                Definition::Use(Use {
+                    location,
+                    module: existing_module,
+                    unqualified: unqualified_list,
+                    ..
+                }) => {
+                    last_import = Some(*location);
+
+                    if import_path != existing_module.as_slice() {
+                        continue;
+                    }
+
+                    match unqualified {
+                        // There's already a matching qualified import, so we have nothing to do.
+                        None => return None,
+                        Some(unqualified) => {
+                            let mut last_unqualified = None;
+
+                            // Insert lexicographically, assuming unqualified imports are already
+                            // ordered. If they are not, it doesn't really matter where we insert
+                            // anyway.
+                            for existing_unqualified in unqualified_list {
+                                last_unqualified = Some(existing_unqualified.location);
+
+                                let existing_name = existing_unqualified
+                                    .as_name
+                                    .as_ref()
+                                    .unwrap_or(&existing_unqualified.name);
+
+                                // The unqualified import already exist, nothing to do.
+                                if unqualified == existing_name {
+                                    return None;
+                                // Current import is lexicographically greater, we can insert before
+                                } else if unqualified < existing_name.as_str() {
+                                    return Some(self.insert_qualified_before(
+                                        import,
+                                        unqualified,
+                                        existing_unqualified.location,
+                                    ));
+                                } else {
+                                    continue;
+                                }
+                            }
+
+                            return match last_unqualified {
+                                // Only happens if 'unqualified_list' is empty, in which case, we
+                                // simply create a new unqualified list of import.
+                                None => {
+                                    Some(self.add_new_qualified(import, unqualified, *location))
+                                }
+                                // Happens if the new qualified import is lexicographically after
+                                // all existing ones.
+                                Some(location) => {
+                                    Some(self.insert_qualified_after(import, unqualified, location))
+                                }
+                            };
+                        }
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        // If the search above didn't lead to anything, we simply insert the import either:
+        //
+        // (a) After the last import statement if any;
+        // (b) As the first statement in the module.
+        Some(self.add_new_import_line(import, unqualified, last_import))
+    }
+
+    /// Qualify a name with the alias its module is already imported under, at the given usage
+    /// site; rather than importing the module a second time. Nothing is done for modules which
+    /// aren't imported under an alias.
+    pub fn qualify(&self, module: &str, name: &str, at: usize) -> Option<AnnotatedEdit> {
+        self.module.definitions.iter().find_map(|def| match def {
+            Definition::Use(Use {
+                module: existing_module,
+                as_name: Some(alias),
+                ..
+            }) if existing_module.join("/") == module => Some((
+                format!("Use {alias}.{name}"),
+                insert_text(at, &self.line_numbers, format!("{alias}.")),
+            )),
+            _ => None,
+        })
+    }
+
+    /// Refer to a name imported, unqualified, from several modules through the given one instead.
+    /// The name is dropped from the module's unqualified imports, and every usage of it not
+    /// shadowed by a local binding gets qualified with the module's alias; be it in expressions,
+    /// or as a type in annotations or a constructor in patterns. Edits are ordered from
+    /// the bottom of the document up.
+    pub fn qualify_ambiguous(&self, module: &str, name: &str) -> Vec<AnnotatedEdit> {
+        let found = self.module.definitions.iter().find_map(|def| match def {
+            Definition::Use(import) if import.module.join("/") == module => {
+                // NOTE: The parser merges imports of the same module into the first one, so we
+                // only consider those actually within the statement.
+                let within = import
+                    .unqualified
+                    .iter()
+                    .filter(|unqualified| {
+                        unqualified.location.start >= import.location.start
+                            && unqualified.location.end <= import.location.end
+                    })
+                    .collect_vec();
+
+                within
+                    .iter()
+                    .find(|unqualified| unqualified.variable_name() == name)
+                    .map(|unqualified| (import, *unqualified, within.len() == 1))
+            }
+            _ => None,
+        });
+
+        let Some((import, unqualified, is_only_one)) = found else {
+            return vec![];
+        };
+
+        let alias = import
+            .as_name
+            .as_deref()
+            .or_else(|| import.module.last().map(String::as_str))
+            .unwrap_or(module);
+
+        // Names imported under another name get qualified after the one they're defined with.
+        let qualified = format!("{alias}.{}", unqualified.name);
+
+        let title = format!("Use {qualified} from {module}");
+
+        let mut edits = self
+            .expressions()
+            .into_iter()
+            .filter_map(|expr| match expr {
+                UntypedExpr::Var {
+                    name: var,
+                    location,
+                } if var == name && !self.local_names(location.start).contains(&name) => {
+                    Some(*location)
+                }
+                _ => None,
+            })
+            .chain(self.type_references(name))
+            .chain(self.constructor_references(name))
+            .sorted_by_key(|location| location.start)
+            .rev()
+            .map(|location| {
+                (
+                    title.clone(),
+                    lsp_types::TextEdit {
+                        range: span_to_lsp_range(location, &self.line_numbers),
+                        new_text: qualified.clone(),
+                    },
+                )
+            })
+            .collect_vec();
+
+        // Without any other unqualified import, the statement goes back to importing the module
+        // alone rather than keeping an empty list around.
+        let (span, new_text) = if is_only_one {
+            let new_text = match import.as_name {
+                Some(ref alias) => format!("use {module} as {alias}"),
+                None => format!("use {module}"),
+            };
+            (import.location, new_text)
+        } else {
+            (
+                self.import_removal_span(unqualified.location.start, true),
+                String::new(),
+            )
+        };
+
+        edits.push((
+            title,
+            lsp_types::TextEdit {
+                range: span_to_lsp_range(span, &self.line_numbers),
+                new_text,
+            },
+        ));
+
+        edits
+    }
+
+    /// Locations of the names of types referred to, without qualification, in annotations.
+    fn type_references(&self, name: &str) -> Vec<Span> {
+        self.module
+            .annotations()
+            .into_iter()
+            .filter_map(|annotation| match annotation {
+                Annotation::Constructor {
+                    module: None,
+                    name: other,
+                    location,
+                    ..
+                } if other == name => Some(Span {
+                    start: location.start,
+                    end: location.start + name.len(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Locations of the names of constructors referred to, without qualification, in patterns of
+    /// the whole document.
+    fn constructor_references(&self, name: &str) -> Vec<Span> {
+        let mut patterns = Vec::new();
+
+        for def in self.module.definitions.iter() {
+            match def {
+                Definition::Fn(function) => patterns.extend(argument_patterns(&function.arguments)),
+                Definition::Validator(validator) => {
+                    for handler in validator
+                        .handlers
+                        .iter()
+                        .chain(std::iter::once(&validator.fallback))
+                    {
+                        patterns.extend(argument_patterns(&handler.arguments))
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        for expr in self.expressions() {
+            match expr {
+                UntypedExpr::Assignment {
+                    patterns: assigned, ..
+                } => patterns.extend(assigned.iter().map(|pattern| &pattern.pattern)),
+                UntypedExpr::When { clauses, .. } => {
+                    patterns.extend(clauses.iter().flat_map(|clause| clause.patterns.iter()))
+                }
+                UntypedExpr::If { branches, .. } => patterns.extend(
+                    branches
+                        .iter()
+                        .filter_map(|branch| branch.is.as_ref().map(|is| &is.pattern)),
+                ),
+                UntypedExpr::Fn { arguments, .. } => patterns.extend(argument_patterns(arguments)),
+                _ => (),
+            }
+        }
+
+        let mut locations = Vec::new();
+        for pattern in patterns {
+            pattern_constructors(pattern, name, &mut locations);
+        }
+        locations
+    }
+
+    /// Import an entire module, possibly under an alias. Unlike 'import', the edit is titled after
+    /// the module's full path, so that modules sharing a name can be told apart.
+    pub fn import_module(&self, module: &str, alias: Option<&str>) -> Option<AnnotatedEdit> {
+        let mut last_import = None;
+
+        for def in self.module.definitions.iter() {
+            if let Definition::Use(Use {
+                location,
+                module: existing_module,
+                ..
+            }) = def
+            {
+                if existing_module.join("/") == module {
+                    return None;
+                }
+
+                last_import = Some(*location);
+            }
+        }
+
+        let import_line = match alias {
+            Some(alias) => format!("use {module} as {alias}"),
+            None => format!("use {module}"),
+        };
+
+        Some((
+            format!("Import {module}"),
+            self.insert_import_line(&import_line, last_import),
+        ))
+    }
+
+    /// Rewrite the path of the import statement of the given module.
+    pub fn rename_import(&self, old_module: &str, new_module: &str) -> Option<AnnotatedEdit> {
+        self.module.definitions.iter().find_map(|def| match def {
+            Definition::Use(Use {
+                location, module, ..
+            }) if module.join("/") == old_module => {
+                let offset = self.source_code[location.start..location.end].find(old_module)?;
+                let start = location.start + offset;
+
+                Some((
+                    format!("Replace with 'use {new_module}'"),
+                    lsp_types::TextEdit {
+                        range: span_to_lsp_range(
+                            Span {
+                                start,
+                                end: start + old_module.len(),
+                            },
+                            &self.line_numbers,
+                        ),
+                        new_text: new_module.to_string(),
+                    },
+                ))
+            }
+            _ => None,
+        })
+    }
+
+    /// Make public the definition starting at the given location, for other modules to refer to.
+    pub fn make_public(&self, start: usize) -> AnnotatedEdit {
+        (
+            "Make public".to_string(),
+            insert_text(start, &self.line_numbers, "pub ".to_string()),
+        )
+    }
+
+    /// Remove the imports starting at each of the given locations; either unqualified values or
+    /// types of an import (qualified), or entire import lines. Edits are ordered from the bottom
+    /// of the document up and never overlap, removals touching one another being merged.
+    pub fn remove_imports(&self, imports: &[(usize, bool)]) -> Vec<AnnotatedEdit> {
+        let spans = imports
+            .iter()
+            .map(|(start, is_qualified)| self.import_removal_span(*start, *is_qualified))
+            .sorted_by_key(|span| span.start)
+            .fold(Vec::<Span>::new(), |mut spans, span| {
+                match spans.last_mut() {
+                    Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                    _ => spans.push(span),
+                }
+                spans
+            });
+
+        spans
+            .into_iter()
+            .rev()
+            .map(|span| {
+                (
+                    "Remove redundant import".to_string(),
+                    lsp_types::TextEdit {
+                        range: span_to_lsp_range(span, &self.line_numbers),
+                        new_text: String::new(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Rewrite the list of unqualified imports of each import statement where only some are
+    /// unused (found at the given locations), keeping the used ones alone. Statements where
+    /// nothing is used are left to 'remove_imports'.
+    pub fn keep_used_imports(&self, unused: &[usize]) -> Vec<AnnotatedEdit> {
+        self.module
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Use(import) => Some(import),
+                _ => None,
+            })
+            .filter_map(|import| {
+                let location = import.location;
+
+                // NOTE: The parser merges imports of the same module into the first one, so we
+                // only consider those actually within the statement.
+                let (unused_imports, used): (Vec<_>, Vec<_>) = import
+                    .unqualified
+                    .iter()
+                    .filter(|unqualified| {
+                        unqualified.location.start >= location.start
+                            && unqualified.location.end <= location.end
+                    })
+                    .partition(|unqualified| unused.contains(&unqualified.location.start));
+
+                if unused_imports.is_empty() || used.is_empty() {
+                    return None;
+                }
+
+                let statement = &self.source_code[location.start..location.end];
+                let open = location.start + statement.find('{')? + 1;
+                let close = location.start + statement.rfind('}')?;
+
+                Some((
+                    format!("Keep only used imports from {}", import.module.join("/")),
+                    lsp_types::TextEdit {
+                        range: span_to_lsp_range(
+                            Span {
+                                start: open,
+                                end: close,
+                            },
+                            &self.line_numbers,
+                        ),
+                        new_text: used
+                            .iter()
+                            .map(|unqualified| {
+                                &self.source_code
+                                    [unqualified.location.start..unqualified.location.end]
+                            })
+                            .join(", "),
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    fn import_removal_span(&self, start: usize, is_qualified: bool) -> Span {
+        if !is_qualified {
+            let line_len = self.source_code[start..]
+                .find('\n')
+                .map(|len| len + 1)
+                .unwrap_or(self.source_code.len() - start);
+
+            return Span {
+                start,
+                end: start + line_len,
+            };
+        }
+
+        let rest = &self.source_code[start..];
+        let import_len = rest.find([',', '}']).unwrap_or(rest.len());
+
+        // Remove the import along with its trailing comma when there's one. Otherwise, it is the
+        // last of the list and we remove the comma preceding it instead.
+        if rest[import_len..].starts_with(',') {
+            let whitespace = rest[import_len + 1..]
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .map(char::len_utf8)
+                .sum::<usize>();
+
+            Span {
+                start,
+                end: start + import_len + 1 + whitespace,
+            }
+        } else {
+            let preceding = self.source_code[..start].trim_end();
+
+            Span {
+                start: preceding.strip_suffix(',').map(str::len).unwrap_or(start),
+                end: start + rest[..import_len].trim_end().len(),
+            }
+        }
+    }
+
+    /// Rewrite all import statements of the document at once: sorted by module, with imports of
+    /// the same module merged together and unused ones (found at the given locations) dropped.
+    /// Comment lines above an import move along with it.
+    ///
+    /// Note that the parser already merges imports of the same module, losing track of where
+    /// duplicates were. So we rely on the source code instead, and parse imports one by one.
+    pub fn organize_imports(&self, unused: &[usize]) -> Option<AnnotatedEdit> {
+        let first = self
+            .module
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Use(import) => Some(import.location.start),
+                _ => None,
+            })
+            .min()?;
+
+        let start = self.leading_comments_start(first);
+
+        let mut organized = BTreeMap::new();
+
+        let mut end = start;
+        for (comments, location) in self.import_statements(start) {
+            end = location.end;
+
+            let (statement, _) = aiken_lang::parser::module(
+                &self.source_code[location.start..location.end],
+                ModuleKind::Lib,
+            )
+            .ok()?;
+
+            let Some(Definition::Use(import)) = statement.definitions.into_iter().next() else {
+                return None;
+            };
+
+            if import.unqualified.is_empty() && unused.contains(&location.start) {
+                continue;
+            }
+
+            let (merged_comments, merged, imports_module) = organized
+                .entry((import.module.clone(), import.as_name.clone()))
+                .or_insert_with(|| {
+                    (
+                        String::new(),
+                        Use {
+                            unqualified: Vec::new(),
+                            ..import.clone()
+                        },
+                        false,
+                    )
+                });
+
+            merged_comments.push_str(&comments);
+
+            *imports_module |= import.unqualified.is_empty();
+
+            for unqualified in import.unqualified {
+                let is_unused = unused.contains(&(location.start + unqualified.location.start));
+
+                let is_duplicate = merged.unqualified.iter().any(|existing| {
+                    existing.name == unqualified.name && existing.as_name == unqualified.as_name
+                });
+
+                if !is_unused && !is_duplicate {
+                    merged.unqualified.push(unqualified);
+                }
+            }
+        }
+
+        // Statements left without any unqualified import would only import the module, which is
+        // then unused unless referred to in a qualified way.
+        let new_text = organized
+            .into_values()
+            .filter(|(_, import, imports_module)| {
+                let alias = import
+                    .as_name
+                    .as_deref()
+                    .or_else(|| import.module.last().map(String::as_str))
+                    .unwrap_or_default();
+                !import.unqualified.is_empty() || *imports_module || self.references_module(alias)
+            })
+            .map(|(comments, import, _)| {
+                let import = Formatter::new()
+                    .definitions(&[Definition::Use(import)])
+                    .to_pretty_string(MAX_COLUMNS);
+                format!("{comments}{import}")
+            })
+            .join("\n");
+
+        if new_text == self.source_code[start..end] {
+            return None;
+        }
+
+        Some((
+            "Organize imports".to_string(),
+            lsp_types::TextEdit {
+                range: span_to_lsp_range(Span { start, end }, &self.line_numbers),
+                new_text,
+            },
+        ))
+    }
+
+    /// How the document, i.e. the given module, refers to types of other modules given its
+    /// imports; so that annotations can be printed the way it would write them.
+    pub fn qualifiers(&self, module: &str) -> Qualifiers {
+        let mut qualifiers = Qualifiers {
+            module: module.to_string(),
+            ..Qualifiers::default()
+        };
+
+        for def in self.module.definitions.iter() {
+            if let Definition::Use(import) = def {
+                let module = import.module.join("/");
+
+                qualifiers.aliases.insert(
+                    module.clone(),
+                    import
+                        .as_name
+                        .clone()
+                        .or_else(|| import.module.last().cloned())
+                        .unwrap_or_default(),
+                );
+
+                for unqualified in import.unqualified.iter() {
+                    if unqualified.name.starts_with(char::is_uppercase) {
+                        qualifiers.unqualified.insert(
+                            (module.clone(), unqualified.name.clone()),
+                            unqualified.variable_name().to_string(),
+                        );
+                    }
+                }
+            }
+        }
+
+        qualifiers
+    }
+
+    /// Whether the document refers to a module through the given alias, in qualified values,
+    /// constructors or types (e.g. 'list.map', 'option.Some' or 'dict.Dict').
+    fn references_module(&self, alias: &str) -> bool {
+        let in_annotations = self.module.annotations().into_iter().any(|annotation| {
+            matches!(annotation, Annotation::Constructor { module: Some(module), .. } if module == alias)
+        });
+
+        in_annotations
+            || self.expressions().into_iter().any(|expr| match expr {
+                UntypedExpr::FieldAccess { container, .. } => {
+                    matches!(container.as_ref(), UntypedExpr::Var { name, .. } if name == alias)
+                }
+                UntypedExpr::Assignment { patterns, .. } => patterns
+                    .iter()
+                    .any(|pattern| pattern_references_module(&pattern.pattern, alias)),
+                UntypedExpr::When { clauses, .. } => clauses
+                    .iter()
+                    .flat_map(|clause| clause.patterns.iter())
+                    .any(|pattern| pattern_references_module(pattern, alias)),
+                _ => false,
+            })
+    }
+
+    /// Import statements from the given location onwards, along with the comment lines above
+    /// each of them.
+    fn import_statements(&self, start: usize) -> Vec<(String, Span)> {
+        let mut statements = Vec::new();
+
+        let mut comments = String::new();
+        let mut statement_start = None;
+        let mut depth = 0;
+        let mut offset = start;
+
+        for line in self.source_code[start..].split_inclusive('\n') {
+            let trimmed = line.trim();
+            let line_start = offset;
+            offset += line.len();
+
+            if statement_start.is_none() {
+                if trimmed.is_empty() {
+                    continue;
+                } else if trimmed.starts_with("//") && !trimmed.starts_with("///") {
+                    comments.push_str(trimmed);
+                    comments.push('\n');
+                    continue;
+                } else if trimmed == "use" || trimmed.starts_with("use ") {
+                    statement_start = Some(line_start + line.find("use").unwrap_or_default());
+                } else {
+                    break;
+                }
+            }
+
+            depth += trimmed.matches('{').count() as isize - trimmed.matches('}').count() as isize;
+
+            if depth <= 0 {
+                if let Some(statement_start) = statement_start.take() {
+                    statements.push((
+                        std::mem::take(&mut comments),
+                        Span {
+                            start: statement_start,
+                            end: line_start + line.trim_end().len(),
+                        },
+                    ));
+                }
+                depth = 0;
+            }
+        }
+
+        statements
+    }
+
+    /// Start of the comment lines right above the given location, if any.
+    fn leading_comments_start(&self, location: usize) -> usize {
+        let mut start = location;
+        while let Some(line) = self.source_code[..start]
+            .strip_suffix('\n')
+            .and_then(|preceding| preceding.lines().last())
+            .filter(|line| {
+                let line = line.trim_start();
+                line.starts_with("//") && !line.starts_with("///")
+            })
+        {
+            start -= line.len() + 1;
+        }
+
+        start
+    }
+
+    fn insert_qualified_before(
+        &self,
+        import: &CheckedModule,
+        unqualified: &str,
+        location: Span,
+    ) -> AnnotatedEdit {
+        let title = format!("Use '{}' from {}", unqualified, import.name);
+        (
+            title,
+            insert_text(
+                location.start,
+                &self.line_numbers,
+                format!("{}, ", unqualified),
+            ),
+        )
+    }
+
+    fn insert_qualified_after(
+        &self,
+        import: &CheckedModule,
+        unqualified: &str,
+        location: Span,
+    ) -> AnnotatedEdit {
+        let title = format!("Use '{}' from {}", unqualified, import.name);
+        (
+            title,
+            insert_text(
+                location.end,
+                &self.line_numbers,
+                format!(", {}", unqualified),
+            ),
+        )
+    }
+
+    fn add_new_qualified(
+        &self,
+        import: &CheckedModule,
+        unqualified: &str,
+        location: Span,
+    ) -> AnnotatedEdit {
+        let title = format!("Use '{}' from {}", unqualified, import.name);
+        (
+            title,
+            insert_text(
+                location.end,
+                &self.line_numbers,
+                format!(".{{{}}}", unqualified),
+            ),
+        )
+    }
+
+    fn add_new_import_line(
+        &self,
+        import: &CheckedModule,
+        unqualified: Option<&str>,
+        location: Option<Span>,
+    ) -> AnnotatedEdit {
+        let import_line = format!(
+            "use {}{}",
+            import.name,
+            match unqualified {
+                Some(unqualified) => format!(".{{{}}}", unqualified),
+                None => String::new(),
+            }
+        );
+
+        let title = format!("Add new import line: {import_line}");
+
+        (title, self.insert_import_line(&import_line, location))
+    }
+
+    fn insert_import_line(&self, import_line: &str, location: Option<Span>) -> lsp_types::TextEdit {
+        match location {
+            None => insert_text(0, &self.line_numbers, format!("{import_line}\n")),
+            Some(Span { end, .. }) => {
+                insert_text(end, &self.line_numbers, format!("\n{import_line}"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aiken_lang::{
+        ast::TypedModule,
+        expr::TypedExpr,
+        tipo::{error::Warning, pretty::Printer, Type},
+    };
+
+    /// Apply edits one after the other, in the given order.
+    fn apply(document: &ParsedDocument, edits: Vec<AnnotatedEdit>) -> String {
+        let mut source_code = document.source_code.clone();
+
+        for (_, edit) in edits {
+            let line_numbers = LineNumbers::new(&source_code);
+            let start = line_numbers.byte_index(
+                edit.range.start.line as usize,
+                edit.range.start.character as usize,
+            );
+            let end = line_numbers.byte_index(
+                edit.range.end.line as usize,
+                edit.range.end.character as usize,
+            );
+            source_code.replace_range(start..end, &edit.new_text);
+        }
+
+        source_code
+    }
+
+    /// Type-check some source code, alongside the given modules it may import; giving the
+    /// warnings raised, or the error preventing compilation.
+    fn check_with_deps(
+        source_code: &str,
+        deps: &[(&str, &str)],
+    ) -> Result<Vec<Warning>, Box<aiken_lang::tipo::error::Error>> {
+        infer_with_deps(source_code, deps).map(|(_, warnings)| warnings)
+    }
+
+    /// Like 'check_with_deps', but also giving the typed module, named 'test/module'.
+    fn infer_with_deps(
+        source_code: &str,
+        deps: &[(&str, &str)],
+    ) -> Result<(TypedModule, Vec<Warning>), Box<aiken_lang::tipo::error::Error>> {
+        use aiken_lang::{
+            ast::{TraceLevel, Tracing},
+            builtins, IdGenerator,
+        };
+        use std::collections::HashMap;
+
+        let id_gen = IdGenerator::new();
+
+        let mut modules = HashMap::new();
+        modules.insert("aiken".to_string(), builtins::prelude(&id_gen));
+        modules.insert("aiken/builtin".to_string(), builtins::plutus(&id_gen));
+
+        for (name, dep) in deps {
+            let (mut ast, _) = aiken_lang::parser::module(dep, ModuleKind::Lib).unwrap();
+            ast.name = name.to_string();
+            let typed = ast
+                .infer(
+                    &id_gen,
+                    ModuleKind::Lib,
+                    name,
+                    &modules,
+                    Tracing::All(TraceLevel::Verbose),
+                    &mut vec![],
+                    None,
+                )
+                .expect("dependency did not compile");
+            modules.insert(name.to_string(), typed.type_info);
+        }
+
+        let (mut ast, _) = aiken_lang::parser::module(source_code, ModuleKind::Lib)
+            .expect("edited source code did not parse");
+        ast.name = "test/module".to_string();
+
+        let mut warnings = vec![];
+        ast.infer(
+            &id_gen,
+            ModuleKind::Lib,
+            "test/project",
+            &modules,
+            Tracing::All(TraceLevel::Verbose),
+            &mut warnings,
+            None,
+        )
+        .map(|module| (module, warnings))
+        .map_err(Box::new)
+    }
+
+    fn check(source_code: &str) -> Result<Vec<Warning>, Box<aiken_lang::tipo::error::Error>> {
+        check_with_deps(source_code, &[])
+    }
+
+    #[test]
+    fn remove_discarded_let() {
+        let document = ParsedDocument::new(
+            r#"pub fn foo(n: Int) -> Int {
+  let _ = n + 1
+  let _unused = n * 2
+  n
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            check(&document.source_code).unwrap().as_slice(),
+            [
+                Warning::DiscardedLetAssignment { .. },
+                Warning::DiscardedLetAssignment { .. }
+            ]
+        ));
+
+        let first = document.source_code.find("_ =").unwrap();
+        let second = document.source_code.find("_unused").unwrap();
+
+        let edits = vec![
+            document.remove_discarded_let(second).expect("no edit"),
+            document.remove_discarded_let(first).expect("no edit"),
+        ];
+        assert_eq!(edits[0].0, "Remove the discarded assignment");
+
+        let fixed = apply(&document, edits);
+        assert_eq!(
+            fixed,
+            r#"pub fn foo(n: Int) -> Int {
+  n
+}
+"#
+        );
+        assert!(check(&fixed).unwrap().is_empty());
+    }
+
+    #[test]
+    fn remove_unreachable_code() {
+        let document = ParsedDocument::new(
+            r#"fn foo(n: Int) -> Int {
+  let m = n + 1
+  fail @"nope"
+  let k = m * 2
+  k
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let failure_end = document.source_code.find("@\"nope\"").unwrap() + 7;
+
+        let edit = document
+            .remove_unreachable_code(failure_end)
+            .expect("no edit");
+
+        assert_eq!(edit.0, "Remove unreachable code");
+        assert_eq!(
+            apply(&document, vec![edit]),
+            r#"fn foo(n: Int) -> Int {
+  let m = n + 1
+  fail @"nope"
+}
+"#
+        );
+
+        let last_end = document.source_code.find("  k\n").unwrap() + 3;
+        assert!(document.remove_unreachable_code(last_end).is_none());
+
+        let let_end = document.source_code.find("n + 1").unwrap() + 5;
+        assert!(document.remove_unreachable_code(let_end).is_none());
+    }
+
+    #[test]
+    fn qualify_ambiguous() {
+        let document = ParsedDocument::new(
+            r#"use foo/left.{double, triple}
+use foo/right.{double}
+
+fn bar(n: Int) -> Int {
+  let m = double(n)
+  let f = fn(double) { double + 1 }
+  triple(m) + f(double(1))
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let edits = document.qualify_ambiguous("foo/left", "double");
+        assert_eq!(edits[0].0, "Use left.double from foo/left");
+        assert_eq!(
+            apply(&document, edits),
+            r#"use foo/left.{triple}
+use foo/right.{double}
+
+fn bar(n: Int) -> Int {
+  let m = left.double(n)
+  let f = fn(double) { double + 1 }
+  triple(m) + f(left.double(1))
+}
+"#
+        );
+
+        assert_eq!(
+            apply(&document, document.qualify_ambiguous("foo/right", "double")),
+            r#"use foo/left.{double, triple}
+use foo/right
+
+fn bar(n: Int) -> Int {
+  let m = right.double(n)
+  let f = fn(double) { double + 1 }
+  triple(m) + f(right.double(1))
+}
+"#
+        );
+
+        assert!(document.qualify_ambiguous("foo/right", "triple").is_empty());
+    }
+
+    #[test]
+    fn qualify_ambiguous_types_and_constructors() {
+        let document = ParsedDocument::new(
+            r#"use foo/left.{Color, Red}
+use foo/right.{Color}
+
+fn bar(c: Color, xs: List<Color>) -> Option<Color> {
+  when c is {
+    Red -> None
+    _ -> Some(Red)
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            apply(&document, document.qualify_ambiguous("foo/right", "Color")),
+            r#"use foo/left.{Color, Red}
+use foo/right
+
+fn bar(c: right.Color, xs: List<right.Color>) -> Option<right.Color> {
+  when c is {
+    Red -> None
+    _ -> Some(Red)
+  }
+}
+"#
+        );
+
+        assert_eq!(
+            apply(&document, document.qualify_ambiguous("foo/left", "Red")),
+            r#"use foo/left.{Color}
+use foo/right.{Color}
+
+fn bar(c: Color, xs: List<Color>) -> Option<Color> {
+  when c is {
+    left.Red -> None
+    _ -> Some(left.Red)
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn qualify_ambiguous_out_of_scope_bindings() {
+        let document = ParsedDocument::new(
+            r#"use foo/left.{double}
+use foo/right.{double}
+
+fn bar(n: Option<Int>) -> Int {
+  when n is {
+    Some(double) -> double
+    None -> double(1)
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            apply(&document, document.qualify_ambiguous("foo/left", "double")),
+            r#"use foo/left
+use foo/right.{double}
+
+fn bar(n: Option<Int>) -> Int {
+  when n is {
+    Some(double) -> double
+    None -> left.double(1)
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn prefix_unused_variables() {
+        let document = ParsedDocument::new(
+            r#"fn foo(point: Point) {
+  let Point { x, y } = point
+  let unused = 1
+  x
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let span = |needle: &str| {
+            let start = document.source_code.find(needle).unwrap();
+            Span {
+                start,
+                end: start + needle.len(),
+            }
+        };
+
+        let edits = ["unused", "y"]
+            .into_iter()
+            .filter_map(|name| document.prefix_unused_variable(name, span(name)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(edits.len(), 2);
+        assert!(document
+            .prefix_unused_variable("foo", span("point"))
+            .is_none());
+
+        assert_eq!(
+            apply(&document, edits),
+            r#"fn foo(point: Point) {
+  let Point { x, y: _y } = point
+  let _unused = 1
+  x
+}
+"#
+        );
+    }
+
+    #[test]
+    fn import_module_with_alias() {
+        let document = ParsedDocument::new(
+            r#"use aiken/collection/list
+
+fn foo(xs) {
+  dict.size(xs)
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert!(document
+            .import_module("aiken/collection/list", None)
+            .is_none());
+
+        let (title, edit) = document
+            .import_module("acme/ordered_dict", Some("dict"))
+            .unwrap();
+
+        assert_eq!(title, "Import acme/ordered_dict");
+        assert_eq!(
+            apply(&document, vec![(title, edit)]),
+            r#"use aiken/collection/list
+use acme/ordered_dict as dict
+
+fn foo(xs) {
+  dict.size(xs)
+}
+"#
+        );
+    }
+
+    #[test]
+    fn qualify_with_existing_alias() {
+        let source_code = r#"use acme/colors as c
+
+fn foo() {
+  Red
+}
+"#;
+        let at = source_code.find("Red").unwrap();
+
+        let document = ParsedDocument::new(source_code.to_string()).unwrap();
+        let (title, edit) = document.qualify("acme/colors", "Red", at).unwrap();
+
+        assert_eq!(title, "Use c.Red");
+        assert_eq!(
+            apply(&document, vec![(title, edit)]),
+            r#"use acme/colors as c
+
+fn foo() {
+  c.Red
+}
+"#
+        );
+
+        let unaliased = ParsedDocument::new(source_code.replace(" as c", "")).unwrap();
+        assert!(unaliased.qualify("acme/colors", "Red", at).is_none());
+
+        let unimported =
+            ParsedDocument::new(source_code.replace("use acme/colors as c\n", "")).unwrap();
+        assert!(unimported.qualify("acme/colors", "Red", 0).is_none());
+    }
+
+    #[test]
+    fn names_in_scope() {
+        let document = ParsedDocument::new(
+            r#"use aiken/collection/list.{length as size, map}
+
+const threshold = 42
+
+fn foo(xs, Pair(a, _)) {
+  let total = size(xs)
+  fn(y) { y + total }
+}
+
+fn bar(z) {
+  z
+}
+
+fn baz(opt) {
+  let w = {
+    let inner = 1
+    inner
+  }
+  when opt is {
+    Some(v) -> v
+    None -> w
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = |needle: &str| document.source_code.find(needle).unwrap();
+
+        assert_eq!(
+            document
+                .names_in_scope(at("y + total"))
+                .into_iter()
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec![
+                "a",
+                "bar",
+                "baz",
+                "foo",
+                "map",
+                "size",
+                "threshold",
+                "total",
+                "xs",
+                "y"
+            ],
+        );
+
+        // Neither bindings of a block which ended, nor those of another clause, are in scope.
+        assert_eq!(
+            document
+                .names_in_scope(at("None -> w") + "None -> ".len())
+                .into_iter()
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec!["bar", "baz", "foo", "map", "opt", "size", "threshold", "w"],
+        );
+    }
+
+    #[test]
+    fn if_chain_to_when() {
+        let document = ParsedDocument::new(
+            r#"fn foo(x: Int, y: Int) {
+  let z =
+    if x == 1 {
+      "one"
+    } else if 2 == x {
+      "two"
+    } else {
+      "many"
+    }
+  if x == 1 {
+    z
+  } else if y == 2 {
+    z
+  } else {
+    z
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = |needle: &str| document.source_code.find(needle).unwrap();
+
+        assert!(document.if_to_when(at("y == 2")).is_none());
+
+        let (title, edit) = document.if_to_when(at("\"two\"")).unwrap();
+        assert_eq!(title, "Convert to when expression");
+
+        assert_eq!(
+            apply(&document, vec![(title, edit)]),
+            r#"fn foo(x: Int, y: Int) {
+  let z =
+    when x is {
+      1 -> "one"
+      2 -> "two"
+      _ -> "many"
+    }
+  if x == 1 {
+    z
+  } else if y == 2 {
+    z
+  } else {
+    z
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn missing_annotations() {
+        let document = ParsedDocument::new(
+            r#"fn foo(x: Int) {
+  let y = x + 1
+  let z: Int = y * 2
+  z
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = |needle: &str| document.source_code.find(needle).unwrap();
+
+        let binding = document.missing_annotation(at("x + 1")).unwrap();
+        assert!(matches!(binding, MissingAnnotation::Binding { .. }));
+
+        let return_type = document.missing_annotation(at("foo")).unwrap();
+        assert!(matches!(return_type, MissingAnnotation::ReturnType { .. }));
+
+        assert!(document.missing_annotation(at("y * 2")).is_none());
+
+        assert_eq!(
+            apply(
+                &document,
+                vec![
+                    document.add_annotation(&binding, "Int"),
+                    document.add_annotation(&return_type, "Int"),
+                ]
+            ),
+            r#"fn foo(x: Int) -> Int {
+  let y: Int = x + 1
+  let z: Int = y * 2
+  z
+}
+"#
+        );
+    }
+
+    #[test]
+    fn remove_single_trace() {
+        let document = ParsedDocument::new(
+            r#"fn foo(x) {
+  trace @"computing"
+  x + 1
+}
+
+fn bar() {
+  trace @"unfinished"
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let trace = |needle: &str| document.source_code.find(needle).unwrap();
+
+        let edits = [
+            trace("trace @\"unfinished\""),
+            trace("trace @\"computing\""),
+        ]
+        .into_iter()
+        .filter_map(|start| document.remove_trace(start))
+        .collect::<Vec<_>>();
+
+        assert_eq!(edits.len(), 2);
+        assert!(document.remove_trace(trace("x + 1")).is_none());
+
+        assert_eq!(
+            apply(&document, edits),
+            r#"fn foo(x) {
+  x + 1
+}
+
+fn bar() {}
+"#
+        );
+    }
+
+    #[test]
+    fn remove_unused_imports_on_one_line() {
+        let document = ParsedDocument::new(
+            r#"use foo.{a, b, c}
+use bar
+
+fn main() {
+  b
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let import = |needle: &str| document.source_code.find(needle).unwrap();
+
+        let edits = document.remove_imports(&[
+            (import("a,"), true),
+            (import("c}"), true),
+            (import("use bar"), false),
+            (import("c}"), true),
+        ]);
+
+        assert_eq!(edits.len(), 3);
+        assert_eq!(
+            apply(&document, edits),
+            r#"use foo.{b}
+
+fn main() {
+  b
+}
+"#
+        );
+    }
+
+    #[test]
+    fn keep_only_used_imports() {
+        let document = ParsedDocument::new(
+            r#"use foo.{
+  a,
+  b as bee,
+  c,
+}
+use bar.{d}
+use baz.{e, f}
+
+fn main() {
+  (a, c, e, f)
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let unused = [
+            document.source_code.find("b as").unwrap(),
+            document.source_code.find("d}").unwrap(),
+        ];
+
+        let edits = document.keep_used_imports(&unused);
+
+        assert_eq!(
+            edits.iter().map(|(title, _)| title.as_str()).collect_vec(),
+            vec!["Keep only used imports from foo"]
+        );
+        assert_eq!(
+            apply(&document, edits),
+            r#"use foo.{a, c}
+use bar.{d}
+use baz.{e, f}
+
+fn main() {
+  (a, c, e, f)
+}
+"#
+        );
+    }
+
+    #[test]
+    fn remove_parentheses() {
+        let document = ParsedDocument::new(
+            r#"fn foo(n: Int) -> Int {
+  (( n * 2 )) + f(n)
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let range = |needle: &str| {
+            let start = document.source_code.find(needle).unwrap();
+            span_to_lsp_range(
+                Span {
+                    start,
+                    end: start + needle.len(),
+                },
+                &document.line_numbers,
+            )
+        };
+
+        let edits = document
+            .remove_parentheses(range("(( n * 2 ))"))
+            .into_iter()
+            .collect();
+        assert_eq!(
+            apply(&document, edits),
+            r#"fn foo(n: Int) -> Int {
+  n * 2 + f(n)
+}
+"#
+        );
+
+        assert!(document.remove_parentheses(range("(n)")).is_none());
+        assert!(document.remove_parentheses(range("n: Int")).is_none());
+    }
+
+    #[test]
+    fn rename_shadowing_binding() {
+        let document = ParsedDocument::new(
+            r#"pub type Point {
+  x: Int,
+  y: Int,
+}
+
+pub fn foo(x: Int) -> Point {
+  let x = x + 1
+  Point { x, y: x }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = document.source_code.find("let x").unwrap();
+        let edits = document.rename_shadowing_binding(at);
+        assert_eq!(edits[0].0, "Rename shadowing 'x' to 'x_2'");
+
+        let renamed = apply(
+            &document,
+            edits
+                .into_iter()
+                .sorted_by_key(|(_, edit)| std::cmp::Reverse(edit.range.start))
+                .collect(),
+        );
+        assert_eq!(
+            renamed,
+            r#"pub type Point {
+  x: Int,
+  y: Int,
+}
+
+pub fn foo(x: Int) -> Point {
+  let x_2 = x + 1
+  Point { x: x_2, y: x_2 }
+}
+"#
+        );
+        assert_eq!(check(&renamed).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn rename_shadowing_binding_out_of_scope() {
+        let document = ParsedDocument::new(
+            r#"pub fn foo(opt: Option<Int>) -> Int {
+  when opt is {
+    Some(x) -> x
+    None -> {
+      let x = 1
+      x + 1
+    }
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = document.source_code.find("let x").unwrap();
+        assert!(document.rename_shadowing_binding(at).is_empty());
+    }
+
+    #[test]
+    fn fold_constant_arithmetic() {
+        let document = ParsedDocument::new(
+            r#"fn foo(x: Int) -> Int {
+  let a = (1 + 2) * (30 - 4)
+  x * ((2 + 3) * -(4)) + a
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = |needle: &str| document.source_code.find(needle).unwrap();
+
+        let edit = document.fold_constant_arithmetic(at("1 + 2")).unwrap();
+        assert_eq!(edit.0, "Fold into 78");
+        assert_eq!(
+            apply(&document, vec![edit]),
+            r#"fn foo(x: Int) -> Int {
+  let a = 78
+  x * ((2 + 3) * -(4)) + a
+}
+"#
+        );
+
+        let edit = document.fold_constant_arithmetic(at("2 + 3")).unwrap();
+        assert_eq!(
+            apply(&document, vec![edit]),
+            r#"fn foo(x: Int) -> Int {
+  let a = (1 + 2) * (30 - 4)
+  x * (-20) + a
+}
+"#
+        );
+
+        assert!(document.fold_constant_arithmetic(at("+ a")).is_none());
+    }
+
+    #[test]
+    fn annotate_with_qualified_types() {
+        let deps = [(
+            "foo/dict",
+            "pub opaque type Dict<k, v> {\n  inner: List<Pair<k, v>>,\n}\n\npub type Key =\n  ByteArray\n\npub fn new() -> Dict<Key, Int> {\n  Dict { inner: [] }\n}\n",
+        )];
+
+        let source_code = r#"use foo/dict as d
+
+pub fn foo() {
+  let entries = [d.new()]
+  entries
+}
+"#;
+
+        let (module, _) = infer_with_deps(source_code, &deps).unwrap();
+        let document = ParsedDocument::new(source_code.to_string()).unwrap();
+
+        let mut tipo = None;
+        for body in module.definitions().flat_map(|def| def.bodies()) {
+            body.walk(&mut |expr| {
+                if let TypedExpr::Assignment { value, .. } = expr {
+                    tipo = Some(value.tipo());
                 }
-            },
+            });
+        }
+
+        let mut printer = Printer::new();
+        printer.with_qualifiers(document.qualifiers("test/module"));
+        let annotation = printer.pretty_print(&tipo.unwrap(), 0);
+        assert_eq!(annotation, "List<d.Dict<ByteArray, Int>>");
+
+        let missing = document
+            .missing_annotation(source_code.find("entries").unwrap())
+            .unwrap();
+        let annotated = apply(
+            &document,
+            vec![document.add_annotation(&missing, &annotation)],
+        );
+        assert!(annotated.contains("let entries: List<d.Dict<ByteArray, Int>> = [d.new()]"));
+        assert_eq!(check_with_deps(&annotated, &deps).unwrap(), vec![]);
+
+        let document = ParsedDocument::new(
+            "use foo/dict.{Dict as D}\n\npub fn foo() {\n  todo\n}\n".to_string(),
+        )
+        .unwrap();
+        let mut printer = Printer::new();
+        printer.with_qualifiers(document.qualifiers("test/module"));
+        assert_eq!(
+            printer.pretty_print(
+                &Type::App {
+                    public: true,
+                    contains_opaque: true,
+                    module: "foo/dict".to_string(),
+                    name: "Dict".to_string(),
+                    args: vec![],
+                    alias: None,
+                },
+                0
+            ),
+            "D"
+        );
+    }
+
+    #[test]
+    fn organize_imports() {
+        let document = ParsedDocument::new(
+            r#"use aiken/collection/list.{map}
+use aiken/crypto
+
+// Needed for the ordering of keys.
+use aiken/collection/dict.{Dict}
+use aiken/collection/list.{filter, foldr}
+
+fn foo(xs) {
+  list.map(xs, filter)
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let unused = [
+            document.source_code.find("use aiken/crypto").unwrap(),
+            document.source_code.find("foldr").unwrap(),
+        ];
+
+        let edits = document.organize_imports(&unused).into_iter().collect();
+
+        assert_eq!(
+            apply(&document, edits),
+            r#"// Needed for the ordering of keys.
+use aiken/collection/dict.{Dict}
+use aiken/collection/list.{filter, map}
+
+fn foo(xs) {
+  list.map(xs, filter)
+}
+"#
+        );
+
+        let organized = ParsedDocument::new(
+            r#"use aiken/collection/dict
+use aiken/collection/list
+
+fn foo() {
+  todo
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert!(organized.organize_imports(&[]).is_none());
+    }
+
+    #[test]
+    fn organize_imports_drops_modules_left_empty() {
+        let deps = [
+            ("foo", "pub fn x() {\n  1\n}\n\npub fn y() {\n  2\n}\n"),
+            ("bar", "pub fn z() {\n  3\n}\n"),
+        ];
+
+        let document = ParsedDocument::new(
+            r#"use bar.{z}
+use foo.{x}
+
+pub fn main() {
+  foo.y()
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let unused = [
+            document.source_code.find("z}").unwrap(),
+            document.source_code.find("x}").unwrap(),
+        ];
+
+        let edits = document.organize_imports(&unused).into_iter().collect();
+        let organized = apply(&document, edits);
+
+        assert_eq!(
+            organized,
+            r#"use foo
+
+pub fn main() {
+  foo.y()
+}
+"#
+        );
+
+        assert_eq!(check_with_deps(&organized, &deps).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn remove_one_of_adjacent_functions() {
+        let document = ParsedDocument::new(
+            r#"fn first() {
+  1
+}
+
+/// Never used.
+/// Really.
+fn second() {
+  2
+}
+
+fn third() {
+  3
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let start = |needle: &str| document.source_code.find(needle).unwrap();
+
+        assert_eq!(
+            apply(
+                &document,
+                document.remove_definitions(&[start("fn second")])
+            ),
+            r#"fn first() {
+  1
+}
+
+fn third() {
+  3
+}
+"#
+        );
+
+        assert_eq!(
+            apply(&document, document.remove_definitions(&[start("fn first")])),
+            r#"/// Never used.
+/// Really.
+fn second() {
+  2
+}
+
+fn third() {
+  3
+}
+"#
+        );
+    }
+
+    #[test]
+    fn remove_interleaved_unused_definitions() {
+        let document = ParsedDocument::new(
+            r#"pub fn used() {
+  1
+}
+
+/// Not used.
+fn unused_fn() {
+  2
+}
+
+const unused_const = 3
+
+pub const used_const = 4
+
+type UnusedType {
+  A
+  B
+}
+
+pub type UsedType = Int
+
+fn another_unused_fn() {
+  5
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let starts = [
+            "fn unused_fn",
+            "const unused_const",
+            "type UnusedType",
+            "fn another",
+        ]
+        .iter()
+        .map(|needle| document.source_code.find(needle).unwrap())
+        .collect::<Vec<_>>();
+
+        let edits = document.remove_definitions(&starts);
+
+        assert_eq!(edits.len(), 4);
+
+        assert_eq!(
+            apply(&document, edits),
+            r#"pub fn used() {
+  1
+}
+
+pub const used_const = 4
+
+pub type UsedType = Int
+
+"#
+        );
+    }
+
+    #[test]
+    fn add_missing_bool_clauses() {
+        let document = ParsedDocument::new(
+            r#"pub fn foo(b: Bool) -> Int {
+  when b is {
+    True -> 1
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let when = document.source_code.find("when").unwrap();
+
+        let fixed = apply(
+            &document,
+            document
+                .add_missing_clauses(when, &["False"])
+                .into_iter()
+                .collect(),
+        );
+
+        assert_eq!(
+            fixed,
+            r#"pub fn foo(b: Bool) -> Int {
+  when b is {
+    True -> 1
+    False -> todo
+  }
+}
+"#
+        );
+        assert!(check(&fixed).is_ok());
+    }
+
+    #[test]
+    fn rename_moved_import() {
+        let deps = [("lib/dict", "pub fn empty() -> List<Int> {\n  []\n}\n")];
+
+        let document = ParsedDocument::new(
+            r#"use legacy/dict
+
+pub fn foo() -> List<Int> {
+  dict.empty()
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert!(document.rename_import("legacy/list", "lib/list").is_none());
+
+        let fixed = apply(
+            &document,
+            document
+                .rename_import("legacy/dict", "lib/dict")
+                .into_iter()
+                .collect(),
+        );
+
+        assert_eq!(
+            fixed,
+            r#"use lib/dict
+
+pub fn foo() -> List<Int> {
+  dict.empty()
+}
+"#
+        );
+        assert_eq!(check_with_deps(&fixed, &deps).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn remove_traces_of_function() {
+        let document = ParsedDocument::new(
+            r#"pub fn foo(x: Int) -> Int {
+  trace @"start"
+  let y = x + 1
+  trace @"computed"
+  y
+}
+
+pub fn bar(x: Int) -> Int {
+  trace @"elsewhere"
+  x
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = document.source_code.find("let y").unwrap();
+
+        assert_eq!(
+            apply(
+                &document,
+                document.remove_traces(at).into_iter().rev().collect()
+            ),
+            r#"pub fn foo(x: Int) -> Int {
+  let y = x + 1
+  y
+}
+
+pub fn bar(x: Int) -> Int {
+  trace @"elsewhere"
+  x
+}
+"#
+        );
+    }
+
+    #[test]
+    fn destructure_parameter() {
+        let document = ParsedDocument::new(
+            r#"pub type Point {
+  x: Int,
+  y: Int,
+}
+
+pub fn norm(point: Point) -> Int {
+  let Point { x, y } = point
+  x * x + y * y
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = document.source_code.find("x * x").unwrap();
+
+        let fixed = apply(&document, document.destructure_parameter(at));
+
+        assert_eq!(
+            fixed,
+            r#"pub type Point {
+  x: Int,
+  y: Int,
+}
+
+pub fn norm(Point { x, y }: Point) -> Int {
+  x * x + y * y
+}
+"#
+        );
+        assert_eq!(check(&fixed).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn when_to_boolean() {
+        let document = ParsedDocument::new(
+            r#"pub fn is_none(opt: Option<Int>) -> Bool {
+  when opt is {
+    None -> True
+    _ -> False
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = document.source_code.find("None ->").unwrap();
+
+        let fixed = apply(
+            &document,
+            document.when_to_boolean(at).into_iter().collect(),
+        );
+
+        assert_eq!(
+            fixed,
+            r#"pub fn is_none(opt: Option<Int>) -> Bool {
+  opt == None
+}
+"#
+        );
+        assert_eq!(check(&fixed).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn point_free() {
+        let document = ParsedDocument::new(
+            r#"fn succ(n: Int) -> Int {
+  n + 1
+}
+
+fn apply(n: Int, f: fn(Int) -> Int) -> Int {
+  f(n)
+}
+
+pub fn foo(n: Int) -> Int {
+  apply(n, fn(x) { succ(x) })
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = document.source_code.find("succ(x)").unwrap();
+
+        let fixed = apply(&document, document.point_free(at).into_iter().collect());
+
+        assert!(fixed.contains("  apply(n, succ)\n"));
+        assert_eq!(check(&fixed).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn point_free_declined() {
+        let document = ParsedDocument::new(
+            r#"use foo/math
+
+fn adder(n: Int) -> fn(Int) -> Int {
+  fn(m) { n + m }
+}
+
+pub fn foo(n: Int) -> Int {
+  let a = apply(n, fn(x) { math.succ(x) })
+  let b = apply(n, fn(x) { adder(1)(x) })
+  let c = apply(n, fn(x) { succ(n: x) })
+  let d = apply(n, fn(x) { record.succ(x) })
+  a + b + c + d
+}
+"#
+            .to_string(),
         )
+        .unwrap();
+
+        let at = |needle: &str| document.source_code.find(needle).unwrap();
+
+        let (title, _) = document.point_free(at("math.succ(x)")).unwrap();
+        assert_eq!(title, "Replace with 'math.succ'");
+
+        assert!(document.point_free(at("adder(1)(x)")).is_none());
+        assert!(document.point_free(at("succ(n: x)")).is_none());
+        assert!(document.point_free(at("record.succ(x)")).is_none());
+    }
+
+    #[test]
+    fn list_combinator() {
+        let deps = [(
+            LIST_MODULE,
+            "pub fn map(xs: List<a>, f: fn(a) -> b) -> List<b> {\n  todo\n}\n\npub fn filter(xs: List<a>, f: fn(a) -> Bool) -> List<a> {\n  todo\n}\n",
+        )];
+
+        let document = ParsedDocument::new(
+            r#"use aiken/collection/list
+
+pub fn double(xs: List<Int>) -> List<Int> {
+  when xs is {
+    [] -> []
+    [x, ..rest] -> [x * 2, ..double(rest)]
+  }
+}
+
+pub fn positives(xs: List<Int>) -> List<Int> {
+  when xs is {
+    [] -> []
+    [x, ..rest] ->
+      if x > 0 {
+        [x, ..positives(rest)]
+      } else {
+        positives(rest)
+      }
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let at = |needle: &str| document.source_code.find(needle).unwrap();
+
+        let fixed = apply(
+            &document,
+            [at("double(rest)"), at("positives(rest)")]
+                .into_iter()
+                .rev()
+                .filter_map(|at| document.list_combinator(at))
+                .collect(),
+        );
+
+        assert_eq!(
+            fixed,
+            r#"use aiken/collection/list
+
+pub fn double(xs: List<Int>) -> List<Int> {
+  list.map(xs, fn(x) { x * 2 })
+}
+
+pub fn positives(xs: List<Int>) -> List<Int> {
+  list.filter(xs, fn(x) { x > 0 })
+}
+"#
+        );
+        assert_eq!(check_with_deps(&fixed, &deps).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn make_private_definition_public() {
+        let source_code = r#"/// Some helper.
+fn helper() -> Int {
+  1
+}
+
+pub fn foo() -> Int {
+  helper()
+}
+"#;
+
+        let (module, _) = infer_with_deps(source_code, &[]).unwrap();
+        let document = ParsedDocument::new(source_code.to_string()).unwrap();
+
+        assert!(module.private_definition("foo").is_none());
+        let location = module.private_definition("helper").unwrap();
+
+        assert_eq!(
+            apply(&document, vec![document.make_public(location.start)]),
+            r#"/// Some helper.
+pub fn helper() -> Int {
+  1
+}
+
+pub fn foo() -> Int {
+  helper()
+}
+"#
+        );
     }
 }
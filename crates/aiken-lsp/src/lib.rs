@@ -3,6 +3,7 @@ use aiken_project::{config::Config, paths};
 use error::Error;
 use lsp_server::Connection;
 use std::env;
+use utils::REGENERATE_DOCS_COMMAND;
 
 mod cast;
 mod edits;
@@ -65,6 +66,10 @@ fn capabilities() -> lsp_types::ServerCapabilities {
         document_formatting_provider: Some(lsp_types::OneOf::Left(true)),
         definition_provider: Some(lsp_types::OneOf::Left(true)),
         hover_provider: Some(lsp_types::HoverProviderCapability::Simple(true)),
+        execute_command_provider: Some(lsp_types::ExecuteCommandOptions {
+            commands: vec![REGENERATE_DOCS_COMMAND.to_string()],
+            ..Default::default()
+        }),
         text_document_sync: Some(lsp_types::TextDocumentSyncCapability::Options(
             lsp_types::TextDocumentSyncOptions {
                 open_close: None,
@@ -1,16 +1,37 @@
 use crate::{
-    edits::{self, AnnotatedEdit, ParsedDocument},
+    edits::{self, AnnotatedEdit, MissingAnnotation, ParsedDocument},
     server::lsp_project::LspProject,
 };
+use aiken_lang::{
+    ast::{Definition, Located, Span},
+    expr::TypedExpr,
+    levenshtein,
+    tipo::pretty::Printer,
+};
+use aiken_project::module::CheckedModule;
+use itertools::Itertools;
 use std::{collections::HashMap, str::FromStr};
 
 const UNKNOWN_VARIABLE: &str = "aiken::check::unknown::variable";
 const UNKNOWN_TYPE: &str = "aiken::check::unknown::type";
 const UNKNOWN_CONSTRUCTOR: &str = "aiken::check::unknown::type_constructor";
 const UNKNOWN_MODULE: &str = "aiken::check::unknown::module";
+const UNKNOWN_RECORD_FIELD: &str = "aiken::check::unknown::record_field";
+const DUPLICATE_IMPORT: &str = "aiken::check::duplicate::import";
 const UNUSED_IMPORT_VALUE: &str = "aiken::check::unused:import::value";
 const UNUSED_IMPORT_MODULE: &str = "aiken::check::unused::import::module";
+const UNUSED_FUNCTION: &str = "aiken::check::unused::function";
+const UNUSED_CONSTANT: &str = "aiken::check::unused::constant";
+const UNUSED_TYPE: &str = "aiken::check::unused::type";
+const UNUSED_VARIABLE: &str = "aiken::check::unused::variable";
+const TODO: &str = "aiken::check::todo";
 const USE_LET: &str = "aiken::check::single_constructor_expect";
+const NON_EXHAUSTIVE_PATTERN_MATCH: &str = "aiken::check::non_exhaustive_pattern_match";
+const DISCARDED_LET_ASSIGNMENT: &str = "aiken::check::unused::discarded_let_assignment";
+const SINGLE_USE_TYPE_ALIAS: &str = "aiken::check::single_use_type_alias";
+const VERBOSE_EQUALITY: &str = "aiken::check::verbose_equality";
+const IMPLICIT_DISCARD: &str = "aiken::check::implicit_discard";
+const REDUNDANT_PARENTHESES: &str = "aiken::check::syntax::redundant_parentheses";
 const UTF8_BYTE_ARRAY_IS_VALID_HEX_STRING: &str =
     "aiken::check::syntax::bytearray_literal_is_hex_string";
 
@@ -20,9 +41,21 @@ pub enum Quickfix {
     UnknownIdentifier(lsp_types::Diagnostic),
     UnknownModule(lsp_types::Diagnostic),
     UnknownConstructor(lsp_types::Diagnostic),
+    UnknownField(lsp_types::Diagnostic),
+    DuplicateImport(lsp_types::Diagnostic),
     UnusedImports(Vec<lsp_types::Diagnostic>),
     Utf8ByteArrayIsValidHexString(lsp_types::Diagnostic),
     UseLet(lsp_types::Diagnostic),
+    NonExhaustivePatternMatch(lsp_types::Diagnostic),
+    RedundantParentheses(lsp_types::Diagnostic),
+    DiscardedLetAssignment(lsp_types::Diagnostic),
+    SingleUseTypeAlias(lsp_types::Diagnostic),
+    VerboseEquality(lsp_types::Diagnostic),
+    UnusedDefinitions(Vec<lsp_types::Diagnostic>),
+    UnusedVariable(lsp_types::Diagnostic),
+    UnusedFunction(lsp_types::Diagnostic),
+    RedundantTrace(lsp_types::Diagnostic),
+    UnreachableCode(lsp_types::Diagnostic),
 }
 
 fn match_code(
@@ -34,6 +67,24 @@ fn match_code(
         && diagnostic.severity == Some(severity)
 }
 
+/// Whether a diagnostic reports an unused private definition. Those aren't fixed one by one, but
+/// all together for an entire document.
+pub fn is_unused_definition(diagnostic: &lsp_types::Diagnostic) -> bool {
+    use lsp_types::DiagnosticSeverity as Severity;
+
+    match_code(diagnostic, Severity::WARNING, UNUSED_FUNCTION)
+        || match_code(diagnostic, Severity::WARNING, UNUSED_CONSTANT)
+        || match_code(diagnostic, Severity::WARNING, UNUSED_TYPE)
+}
+
+/// Whether a diagnostic reports an unused import; which organizing imports gets rid of.
+pub fn is_unused_import(diagnostic: &lsp_types::Diagnostic) -> bool {
+    use lsp_types::DiagnosticSeverity as Severity;
+
+    match_code(diagnostic, Severity::WARNING, UNUSED_IMPORT_VALUE)
+        || match_code(diagnostic, Severity::WARNING, UNUSED_IMPORT_MODULE)
+}
+
 /// Assert whether a diagnostic can be automatically fixed. Note that diagnostics often comes in
 /// two severities, an error and hint; so we must be careful only addressing errors.
 pub fn assert(diagnostic: lsp_types::Diagnostic) -> Option<Quickfix> {
@@ -53,6 +104,14 @@ pub fn assert(diagnostic: lsp_types::Diagnostic) -> Option<Quickfix> {
         return Some(Quickfix::UnknownModule(diagnostic));
     }
 
+    if match_code(&diagnostic, Severity::ERROR, UNKNOWN_RECORD_FIELD) {
+        return Some(Quickfix::UnknownField(diagnostic));
+    }
+
+    if match_code(&diagnostic, Severity::ERROR, DUPLICATE_IMPORT) {
+        return Some(Quickfix::DuplicateImport(diagnostic));
+    }
+
     if match_code(&diagnostic, Severity::WARNING, UNUSED_IMPORT_VALUE)
         || match_code(&diagnostic, Severity::WARNING, UNUSED_IMPORT_MODULE)
     {
@@ -71,6 +130,46 @@ pub fn assert(diagnostic: lsp_types::Diagnostic) -> Option<Quickfix> {
         return Some(Quickfix::UseLet(diagnostic));
     }
 
+    if match_code(&diagnostic, Severity::ERROR, NON_EXHAUSTIVE_PATTERN_MATCH) {
+        return Some(Quickfix::NonExhaustivePatternMatch(diagnostic));
+    }
+
+    if match_code(&diagnostic, Severity::WARNING, REDUNDANT_PARENTHESES) {
+        return Some(Quickfix::RedundantParentheses(diagnostic));
+    }
+
+    if match_code(&diagnostic, Severity::WARNING, DISCARDED_LET_ASSIGNMENT) {
+        return Some(Quickfix::DiscardedLetAssignment(diagnostic));
+    }
+
+    if match_code(&diagnostic, Severity::WARNING, SINGLE_USE_TYPE_ALIAS) {
+        return Some(Quickfix::SingleUseTypeAlias(diagnostic));
+    }
+
+    if match_code(&diagnostic, Severity::WARNING, VERBOSE_EQUALITY) {
+        return Some(Quickfix::VerboseEquality(diagnostic));
+    }
+
+    if match_code(&diagnostic, Severity::WARNING, UNUSED_VARIABLE) {
+        return Some(Quickfix::UnusedVariable(diagnostic));
+    }
+
+    if match_code(&diagnostic, Severity::WARNING, UNUSED_FUNCTION) {
+        return Some(Quickfix::UnusedFunction(diagnostic));
+    }
+
+    // NOTE: Only discarded failures (e.g. a 'fail' followed by more statements) are fixed, by
+    // removing what comes after them; other discarded expressions are left alone.
+    if match_code(&diagnostic, Severity::ERROR, IMPLICIT_DISCARD) {
+        return Some(Quickfix::UnreachableCode(diagnostic));
+    }
+
+    // NOTE: A trace with nothing after it is implicitly followed by a 'todo', which is how we get
+    // to know about it. Other todos are left alone by the quickfix.
+    if match_code(&diagnostic, Severity::WARNING, TODO) {
+        return Some(Quickfix::RedundantTrace(diagnostic));
+    }
+
     None
 }
 
@@ -88,8 +187,26 @@ pub fn quickfix(
                     &mut actions,
                     text_document,
                     diagnostic,
-                    unknown_identifier(compiler, parsed_document, diagnostic.data.as_ref()),
+                    unknown_identifier(compiler, parsed_document, diagnostic),
                 );
+                if match_code(
+                    diagnostic,
+                    lsp_types::DiagnosticSeverity::ERROR,
+                    UNKNOWN_VARIABLE,
+                ) {
+                    each_as_distinct_action(
+                        &mut actions,
+                        text_document,
+                        diagnostic,
+                        misspelled_variable(parsed_document, diagnostic),
+                    );
+                }
+                actions.extend(private_identifier(
+                    compiler,
+                    parsed_document,
+                    text_document,
+                    diagnostic,
+                ));
             }
             Quickfix::UnknownModule(diagnostic) => each_as_distinct_action(
                 &mut actions,
@@ -101,21 +218,59 @@ pub fn quickfix(
                 &mut actions,
                 text_document,
                 diagnostic,
-                unknown_constructor(compiler, parsed_document, diagnostic.data.as_ref()),
+                unknown_constructor(compiler, parsed_document, diagnostic),
             ),
-            Quickfix::UnusedImports(diagnostics) => as_single_action(
+            Quickfix::UnknownField(diagnostic) => each_as_distinct_action(
                 &mut actions,
                 text_document,
-                diagnostics.to_owned(),
-                "Remove redundant imports",
-                unused_imports(
-                    parsed_document,
+                diagnostic,
+                unknown_field(diagnostic),
+            ),
+            Quickfix::DuplicateImport(diagnostic) => {
+                for edits in duplicate_import(parsed_document, diagnostic) {
+                    if let Some((title, _)) = edits.first() {
+                        let title = title.clone();
+                        as_single_action(
+                            &mut actions,
+                            text_document,
+                            vec![diagnostic.clone()],
+                            &title,
+                            edits,
+                        );
+                    }
+                }
+            }
+            Quickfix::UnusedImports(diagnostics) => {
+                let imports = unused_imports(
                     diagnostics
                         .iter()
                         .map(|diagnostic| diagnostic.data.as_ref())
                         .collect(),
-                ),
-            ),
+                );
+
+                as_single_action(
+                    &mut actions,
+                    text_document,
+                    diagnostics.to_owned(),
+                    "Remove redundant imports",
+                    parsed_document.remove_imports(&imports),
+                );
+
+                let unqualified = imports
+                    .iter()
+                    .filter_map(|(start, is_qualified)| is_qualified.then_some(*start))
+                    .collect_vec();
+
+                for (title, edit) in parsed_document.keep_used_imports(&unqualified) {
+                    as_single_action(
+                        &mut actions,
+                        text_document,
+                        diagnostics.to_owned(),
+                        &title,
+                        vec![(title.clone(), edit)],
+                    );
+                }
+            }
             Quickfix::Utf8ByteArrayIsValidHexString(diagnostic) => each_as_distinct_action(
                 &mut actions,
                 text_document,
@@ -128,17 +283,399 @@ pub fn quickfix(
                 diagnostic,
                 use_let(diagnostic),
             ),
+            Quickfix::NonExhaustivePatternMatch(diagnostic) => each_as_distinct_action(
+                &mut actions,
+                text_document,
+                diagnostic,
+                non_exhaustive_pattern_match(parsed_document, diagnostic),
+            ),
+            Quickfix::RedundantParentheses(diagnostic) => each_as_distinct_action_of_kind(
+                &mut actions,
+                text_document,
+                diagnostic,
+                lsp_types::CodeActionKind::from("quickfix.style".to_string()),
+                parsed_document
+                    .remove_parentheses(diagnostic.range)
+                    .into_iter()
+                    .collect(),
+            ),
+            Quickfix::DiscardedLetAssignment(diagnostic) => each_as_distinct_action(
+                &mut actions,
+                text_document,
+                diagnostic,
+                parsed_document
+                    .remove_discarded_let(parsed_document.byte_index(diagnostic.range.start))
+                    .into_iter()
+                    .collect(),
+            ),
+            Quickfix::SingleUseTypeAlias(diagnostic) => {
+                let edits = single_use_type_alias(parsed_document, diagnostic);
+                if !edits.is_empty() {
+                    as_single_action_of_kind(
+                        &mut actions,
+                        text_document,
+                        vec![diagnostic.clone()],
+                        lsp_types::CodeActionKind::REFACTOR_INLINE,
+                        "Inline single-use type alias",
+                        edits,
+                    )
+                }
+            }
+            Quickfix::UnusedDefinitions(diagnostics) => as_single_action_of_kind(
+                &mut actions,
+                text_document,
+                diagnostics.to_owned(),
+                lsp_types::CodeActionKind::from("source.removeUnused".to_string()),
+                "Remove all unused definitions in file",
+                parsed_document.remove_definitions(
+                    &diagnostics
+                        .iter()
+                        .map(|diagnostic| parsed_document.byte_index(diagnostic.range.start))
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+            Quickfix::VerboseEquality(diagnostic) => each_as_distinct_action_of_kind(
+                &mut actions,
+                text_document,
+                diagnostic,
+                lsp_types::CodeActionKind::REFACTOR_REWRITE,
+                verbose_equality(diagnostic),
+            ),
+            Quickfix::UnusedVariable(diagnostic) => each_as_distinct_action(
+                &mut actions,
+                text_document,
+                diagnostic,
+                unused_variable(parsed_document, diagnostic),
+            ),
+            Quickfix::UnusedFunction(diagnostic) => as_single_action(
+                &mut actions,
+                text_document,
+                vec![diagnostic.clone()],
+                "Remove unused function",
+                parsed_document
+                    .remove_definitions(&[parsed_document.byte_index(diagnostic.range.start)]),
+            ),
+            Quickfix::RedundantTrace(diagnostic) => each_as_distinct_action(
+                &mut actions,
+                text_document,
+                diagnostic,
+                parsed_document
+                    .remove_trace(parsed_document.byte_index(diagnostic.range.start))
+                    .into_iter()
+                    .collect(),
+            ),
+            Quickfix::UnreachableCode(diagnostic) => each_as_distinct_action(
+                &mut actions,
+                text_document,
+                diagnostic,
+                unreachable_code(parsed_document, diagnostic),
+            ),
         };
     }
 
     actions
 }
 
+/// Sort, merge and strip unused imports of an entire document in a single action; which editors
+/// may also run on save.
+pub fn organize_imports(
+    text_document: &lsp_types::TextDocumentIdentifier,
+    unused_imports: Vec<lsp_types::Diagnostic>,
+) -> Vec<lsp_types::CodeAction> {
+    let mut actions = Vec::new();
+
+    if let Some(ref parsed_document) = edits::parse_document(text_document) {
+        let unused = unused_imports
+            .iter()
+            .filter_map(|diagnostic| match diagnostic.data.as_ref() {
+                Some(serde_json::Value::String(args)) => args.split_once(',')?.1.parse().ok(),
+                _ => None,
+            })
+            .collect::<Vec<usize>>();
+
+        if let Some((title, edit)) = parsed_document.organize_imports(&unused) {
+            as_single_action_of_kind(
+                &mut actions,
+                text_document,
+                unused_imports,
+                lsp_types::CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                &title,
+                vec![(title.clone(), edit)],
+            );
+        }
+    }
+
+    actions
+}
+
+/// Code actions which aren't tied to any diagnostic, but to where the cursor (or selection) is.
+pub fn refactor(
+    text_document: &lsp_types::TextDocumentIdentifier,
+    range: lsp_types::Range,
+) -> Vec<lsp_types::CodeAction> {
+    let mut actions = Vec::new();
+
+    if let Some(ref parsed_document) = edits::parse_document(text_document) {
+        let at = parsed_document.byte_index(range.start);
+
+        let traces = parsed_document.remove_traces(at);
+
+        // NOTE: Traces with nothing after them already come with a quickfix, through the 'todo'
+        // they imply. Others are only ever found from where the cursor is.
+        if let Some(trace) = traces
+            .iter()
+            .find(|(_, edit)| edit.range.start <= range.start && range.start < edit.range.end)
+        {
+            as_single_action_of_kind(
+                &mut actions,
+                text_document,
+                vec![],
+                lsp_types::CodeActionKind::REFACTOR_REWRITE,
+                "Remove trace",
+                vec![trace.clone()],
+            );
+        }
+
+        if traces.len() > 1 {
+            as_single_action_of_kind(
+                &mut actions,
+                text_document,
+                vec![],
+                lsp_types::CodeActionKind::REFACTOR_REWRITE,
+                "Remove all traces in function",
+                traces,
+            );
+        }
+
+        let destructuring = parsed_document.destructure_parameter(at);
+        if !destructuring.is_empty() {
+            as_single_action_of_kind(
+                &mut actions,
+                text_document,
+                vec![],
+                lsp_types::CodeActionKind::REFACTOR_REWRITE,
+                "Destructure parameter in signature",
+                destructuring,
+            );
+        }
+
+        let renaming = parsed_document.rename_shadowing_binding(at);
+        if let Some((title, _)) = renaming.first() {
+            as_single_action_of_kind(
+                &mut actions,
+                text_document,
+                vec![],
+                lsp_types::CodeActionKind::REFACTOR_REWRITE,
+                &title.clone(),
+                renaming,
+            );
+        }
+
+        for (title, edit) in [
+            parsed_document.when_to_boolean(at),
+            parsed_document.if_to_when(at),
+            parsed_document.point_free(at),
+            parsed_document.fold_constant_arithmetic(at),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            as_single_action_of_kind(
+                &mut actions,
+                text_document,
+                vec![],
+                lsp_types::CodeActionKind::REFACTOR_REWRITE,
+                &title,
+                vec![(title.clone(), edit)],
+            );
+        }
+    }
+
+    actions
+}
+
+/// Offer to annotate an empty list literal with the element type the checker settled on, when
+/// that type is fully known. Empty literals otherwise remain open to any element type, which
+/// inference can't always resolve later on.
+pub fn annotate_empty_list(
+    module: &CheckedModule,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    range: lsp_types::Range,
+) -> Vec<lsp_types::CodeAction> {
+    let mut actions = Vec::new();
+
+    let Some(ref parsed_document) = edits::parse_document(text_document) else {
+        return actions;
+    };
+
+    let at = parsed_document.byte_index(range.start);
+
+    let Some(Located::Expression(TypedExpr::List {
+        elements,
+        tail: None,
+        tipo,
+        ..
+    })) = module.find_node(at)
+    else {
+        return actions;
+    };
+
+    if !elements.is_empty() || !tipo.is_monomorphic() {
+        return actions;
+    }
+
+    let mut printer = Printer::new();
+    printer.with_qualifiers(parsed_document.qualifiers(&module.name));
+    let annotation = printer.pretty_print(tipo, 0);
+
+    if let Some((title, edit)) = parsed_document.annotate_empty_list(at, &annotation) {
+        as_single_action_of_kind(
+            &mut actions,
+            text_document,
+            vec![],
+            lsp_types::CodeActionKind::QUICKFIX,
+            &title,
+            vec![(title.clone(), edit)],
+        );
+    }
+
+    actions
+}
+
+/// Offer to annotate a 'let' binding, or the return of a function, with the type the checker
+/// inferred for it; provided that type is fully known.
+pub fn annotate_type(
+    module: &CheckedModule,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    range: lsp_types::Range,
+) -> Vec<lsp_types::CodeAction> {
+    let mut actions = Vec::new();
+
+    let Some(ref parsed_document) = edits::parse_document(text_document) else {
+        return actions;
+    };
+
+    let Some(missing) = parsed_document.missing_annotation(parsed_document.byte_index(range.start))
+    else {
+        return actions;
+    };
+
+    let tipo = match missing {
+        MissingAnnotation::Binding { location, .. } => {
+            let mut tipo = None;
+            for body in module.ast.definitions().flat_map(|def| def.bodies()) {
+                body.walk(&mut |expr| {
+                    if let TypedExpr::Assignment {
+                        location: assignment,
+                        value,
+                        ..
+                    } = expr
+                    {
+                        if *assignment == location {
+                            tipo = Some(value.tipo());
+                        }
+                    }
+                });
+            }
+            tipo
+        }
+        MissingAnnotation::ReturnType { location } => {
+            module.ast.definitions().find_map(|def| match def {
+                Definition::Fn(function) if function.location == location => {
+                    Some(function.return_type.clone())
+                }
+                _ => None,
+            })
+        }
+    };
+
+    let Some(tipo) = tipo.filter(|tipo| tipo.is_monomorphic()) else {
+        return actions;
+    };
+
+    let mut printer = Printer::new();
+    printer.with_qualifiers(parsed_document.qualifiers(&module.name));
+    let annotation = printer.pretty_print(&tipo, 0);
+
+    let (title, edit) = parsed_document.add_annotation(&missing, &annotation);
+
+    as_single_action_of_kind(
+        &mut actions,
+        text_document,
+        vec![],
+        lsp_types::CodeActionKind::REFACTOR_REWRITE,
+        &title,
+        vec![(title.clone(), edit)],
+    );
+
+    actions
+}
+
+/// Offer to replace a hand-rolled recursion with the equivalent list combinator from the
+/// standard library; importing it if necessary. This is only offered when the standard library
+/// is available to the project.
+pub fn list_combinator(
+    compiler: &LspProject,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    range: lsp_types::Range,
+) -> Vec<lsp_types::CodeAction> {
+    let mut actions = Vec::new();
+
+    let Some(ref parsed_document) = edits::parse_document(text_document) else {
+        return actions;
+    };
+
+    let Some((title, edit)) =
+        parsed_document.list_combinator(parsed_document.byte_index(range.start))
+    else {
+        return actions;
+    };
+
+    let Some(list) = compiler
+        .project
+        .modules()
+        .into_iter()
+        .find(|module| module.name == edits::LIST_MODULE)
+    else {
+        return actions;
+    };
+
+    let mut edits = vec![(title.clone(), edit)];
+    edits.extend(parsed_document.import(&list, None));
+
+    as_single_action_of_kind(
+        &mut actions,
+        text_document,
+        vec![],
+        lsp_types::CodeActionKind::REFACTOR_REWRITE,
+        &title,
+        edits,
+    );
+
+    actions
+}
+
 fn each_as_distinct_action(
     actions: &mut Vec<lsp_types::CodeAction>,
     text_document: &lsp_types::TextDocumentIdentifier,
     diagnostic: &lsp_types::Diagnostic,
     edits: Vec<AnnotatedEdit>,
+) {
+    each_as_distinct_action_of_kind(
+        actions,
+        text_document,
+        diagnostic,
+        lsp_types::CodeActionKind::QUICKFIX,
+        edits,
+    )
+}
+
+fn each_as_distinct_action_of_kind(
+    actions: &mut Vec<lsp_types::CodeAction>,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    diagnostic: &lsp_types::Diagnostic,
+    kind: lsp_types::CodeActionKind,
+    edits: Vec<AnnotatedEdit>,
 ) {
     for (title, edit) in edits.into_iter() {
         let mut changes = HashMap::new();
@@ -147,7 +684,7 @@ fn each_as_distinct_action(
 
         actions.push(lsp_types::CodeAction {
             title,
-            kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+            kind: Some(kind.clone()),
             diagnostics: Some(vec![diagnostic.clone()]),
             is_preferred: Some(true),
             disabled: None,
@@ -168,6 +705,28 @@ fn as_single_action(
     diagnostics: Vec<lsp_types::Diagnostic>,
     title: &str,
     edits: Vec<AnnotatedEdit>,
+) {
+    if edits.is_empty() {
+        return;
+    }
+
+    as_single_action_of_kind(
+        actions,
+        text_document,
+        diagnostics,
+        lsp_types::CodeActionKind::QUICKFIX,
+        title,
+        edits,
+    )
+}
+
+fn as_single_action_of_kind(
+    actions: &mut Vec<lsp_types::CodeAction>,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    diagnostics: Vec<lsp_types::Diagnostic>,
+    kind: lsp_types::CodeActionKind,
+    title: &str,
+    edits: Vec<AnnotatedEdit>,
 ) {
     let mut changes = HashMap::new();
 
@@ -178,7 +737,7 @@ fn as_single_action(
 
     actions.push(lsp_types::CodeAction {
         title: title.to_string(),
-        kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+        kind: Some(kind),
         diagnostics: Some(diagnostics),
         is_preferred: Some(true),
         disabled: None,
@@ -192,16 +751,55 @@ fn as_single_action(
     });
 }
 
+/// Modules one may import from, most relevant first: those of the project itself, then those of
+/// the standard library, then any other; alphabetically within each group.
+fn importable_modules(compiler: &LspProject) -> Vec<CheckedModule> {
+    let project = compiler.project.config().name.to_string();
+
+    let mut modules = compiler.project.modules();
+    modules.sort_by(|a, b| {
+        import_relevance(&project, &a.package, &a.name)
+            .cmp(&import_relevance(&project, &b.package, &b.name))
+    });
+    modules
+}
+
+fn import_relevance<'a>(project: &str, package: &str, module: &'a str) -> (u8, &'a str) {
+    let group = if package == project {
+        0
+    } else if module.starts_with("aiken/") {
+        1
+    } else {
+        2
+    };
+
+    (group, module)
+}
+
+/// Imports of modules which define the unknown identifier. An unknown type only ever leads to
+/// modules exporting a type of that name, rather than any definition.
 fn unknown_identifier(
     compiler: &LspProject,
     parsed_document: &ParsedDocument,
-    data: Option<&serde_json::Value>,
+    diagnostic: &lsp_types::Diagnostic,
 ) -> Vec<AnnotatedEdit> {
     let mut edits = Vec::new();
 
-    if let Some(serde_json::Value::String(ref var_name)) = data {
-        for module in compiler.project.modules() {
-            if module.ast.has_definition(var_name) {
+    let is_type = match_code(
+        diagnostic,
+        lsp_types::DiagnosticSeverity::ERROR,
+        UNKNOWN_TYPE,
+    );
+
+    if let Some(serde_json::Value::String(ref var_name)) = diagnostic.data {
+        for module in importable_modules(compiler) {
+            let defined = if is_type {
+                module.ast.has_type(var_name)
+            } else {
+                module.ast.has_definition(var_name)
+            };
+
+            if defined {
                 if let Some(edit) = parsed_document.import(&module, Some(var_name)) {
                     edits.push(edit)
                 }
@@ -212,17 +810,122 @@ fn unknown_identifier(
     edits
 }
 
+/// Names in scope which are close enough to an unknown variable to likely be what was meant; at
+/// most three of them, closest first.
+fn misspelled_variable(
+    parsed_document: &ParsedDocument,
+    diagnostic: &lsp_types::Diagnostic,
+) -> Vec<AnnotatedEdit> {
+    let Some(serde_json::Value::String(ref name)) = diagnostic.data else {
+        return vec![];
+    };
+
+    parsed_document
+        .names_in_scope(parsed_document.byte_index(diagnostic.range.start))
+        .into_iter()
+        .unique()
+        .map(|candidate| (levenshtein::distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= 2 && candidate != name)
+        .sorted()
+        .take(3)
+        .map(|(_, candidate)| {
+            (
+                format!("Did you mean '{candidate}'?"),
+                lsp_types::TextEdit {
+                    range: diagnostic.range,
+                    new_text: candidate.to_string(),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Definitions of the project which do exist, but are private to their module. Making them
+/// public means editing that other module, alongside importing it in the current document.
+fn private_identifier(
+    compiler: &LspProject,
+    parsed_document: &ParsedDocument,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    diagnostic: &lsp_types::Diagnostic,
+) -> Vec<lsp_types::CodeAction> {
+    let mut actions = Vec::new();
+
+    let Some(serde_json::Value::String(ref name)) = diagnostic.data else {
+        return actions;
+    };
+
+    let project = compiler.project.config().name.to_string();
+
+    for module in importable_modules(compiler) {
+        if module.package != project {
+            continue;
+        }
+
+        let Some(location) = module.ast.private_definition(name) else {
+            continue;
+        };
+
+        let Some(source) = compiler.sources.get(&module.name) else {
+            continue;
+        };
+
+        let Ok(uri) = lsp_types::Url::from_file_path(&source.path) else {
+            continue;
+        };
+
+        if uri == text_document.uri {
+            continue;
+        }
+
+        let Some(definition) = ParsedDocument::new(module.code.clone()) else {
+            continue;
+        };
+
+        let mut changes = HashMap::new();
+
+        changes.insert(uri, vec![definition.make_public(location.start).1]);
+
+        if let Some((_, edit)) = parsed_document.import(&module, Some(name)) {
+            changes.insert(text_document.uri.clone(), vec![edit]);
+        }
+
+        actions.push(lsp_types::CodeAction {
+            title: format!("Make {name} public in {}", module.name),
+            kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+            diagnostics: Some(vec![diagnostic.clone()]),
+            is_preferred: Some(false),
+            disabled: None,
+            data: None,
+            command: None,
+            edit: Some(lsp_types::WorkspaceEdit {
+                changes: Some(changes),
+                document_changes: None,
+                change_annotations: None,
+            }),
+        });
+    }
+
+    actions
+}
+
 fn unknown_constructor(
     compiler: &LspProject,
     parsed_document: &ParsedDocument,
-    data: Option<&serde_json::Value>,
+    diagnostic: &lsp_types::Diagnostic,
 ) -> Vec<AnnotatedEdit> {
     let mut edits = Vec::new();
 
-    if let Some(serde_json::Value::String(ref constructor_name)) = data {
-        for module in compiler.project.modules() {
+    if let Some(serde_json::Value::String(ref constructor_name)) = diagnostic.data {
+        let at = parsed_document.byte_index(diagnostic.range.start);
+
+        for module in importable_modules(compiler) {
             if module.ast.has_constructor(constructor_name) {
-                if let Some(edit) = parsed_document.import(&module, Some(constructor_name)) {
+                // Modules already imported under an alias are referred to through it.
+                let edit = parsed_document
+                    .qualify(&module.name, constructor_name, at)
+                    .or_else(|| parsed_document.import(&module, Some(constructor_name)));
+
+                if let Some(edit) = edit {
                     edits.push(edit)
                 }
             }
@@ -240,11 +943,34 @@ fn unknown_module(
     let mut edits = Vec::new();
 
     if let Some(serde_json::Value::String(ref module_name)) = data {
-        for module in compiler.project.modules() {
-            if module.name.ends_with(module_name) {
-                if let Some(edit) = parsed_document.import(&module, None) {
-                    edits.push(edit);
-                }
+        // Modules known to have moved take precedence over any other guess.
+        if let Some(new_module) = compiler.project.config().renamed_modules.get(module_name) {
+            if let Some(edit) = parsed_document.rename_import(module_name, new_module) {
+                return vec![edit];
+            }
+        }
+
+        // Modules named exactly as the missing one come first, followed by those whose name merely
+        // ends the same way. The latter are imported under an alias, for the code to refer to them
+        // as written without clashing with whatever their own name may already be bound to.
+        let candidates = importable_modules(compiler)
+            .into_iter()
+            .filter(|module| module.name.ends_with(module_name.as_str()))
+            .map(|module| {
+                let is_exact = module.name.rsplit('/').next() == Some(module_name.as_str());
+                (!is_exact, module.name)
+            })
+            .sorted_by_key(|(is_suffix_only, _)| *is_suffix_only);
+
+        for (is_suffix_only, module) in candidates {
+            let alias = if is_suffix_only {
+                Some(module_name.as_str())
+            } else {
+                None
+            };
+
+            if let Some(edit) = parsed_document.import_module(&module, alias) {
+                edits.push(edit);
             }
         }
     }
@@ -252,13 +978,72 @@ fn unknown_module(
     edits
 }
 
-fn unused_imports(
-    parsed_document: &ParsedDocument,
-    datas: Vec<Option<&serde_json::Value>>,
-) -> Vec<AnnotatedEdit> {
+/// Field labels of the record which are close enough to the unknown one to likely be what was
+/// meant; closest first.
+fn unknown_field(diagnostic: &lsp_types::Diagnostic) -> Vec<AnnotatedEdit> {
     let mut edits = Vec::new();
 
-    for data in datas.iter().rev().flatten() {
+    if let Some(serde_json::Value::String(ref args)) = diagnostic.data.as_ref() {
+        let mut args = args.lines();
+
+        if let Some(label) = args.next() {
+            // The diagnostic covers the whole access (e.g. 'foo.bar'), which ends with the label.
+            let end = diagnostic.range.end;
+            let range = lsp_types::Range {
+                start: lsp_types::Position {
+                    line: end.line,
+                    character: end.character.saturating_sub(label.len() as u32),
+                },
+                end,
+            };
+
+            for (_, field) in args
+                .map(|field| (levenshtein::distance(label, field), field))
+                .filter(|(distance, _)| *distance <= 2)
+                .sorted()
+            {
+                edits.push((
+                    format!("Replace with '{field}'"),
+                    lsp_types::TextEdit {
+                        range,
+                        new_text: field.to_string(),
+                    },
+                ));
+            }
+        }
+    }
+
+    edits
+}
+
+/// For a name imported from several modules, the edits referring to it through each of them;
+/// one set of edits per candidate module.
+fn duplicate_import(
+    parsed_document: &ParsedDocument,
+    diagnostic: &lsp_types::Diagnostic,
+) -> Vec<Vec<AnnotatedEdit>> {
+    let Some(serde_json::Value::String(ref args)) = diagnostic.data else {
+        return vec![];
+    };
+
+    let mut args = args.lines();
+
+    let Some(name) = args.next() else {
+        return vec![];
+    };
+
+    args.unique()
+        .map(|module| parsed_document.qualify_ambiguous(module, name))
+        .filter(|edits| !edits.is_empty())
+        .collect()
+}
+
+/// Locations of unused imports, and whether each is an unqualified value or type (as opposed to
+/// an entire module).
+fn unused_imports(datas: Vec<Option<&serde_json::Value>>) -> Vec<(usize, bool)> {
+    let mut imports = Vec::new();
+
+    for data in datas.iter().flatten() {
         if let serde_json::Value::String(ref args) = data {
             let args = args.split(',').collect::<Vec<&str>>();
             match args.as_slice() {
@@ -270,7 +1055,7 @@ fn unused_imports(
                     let is_qualified = FromStr::from_str(is_qualified)
                         .expect("malformed unused_imports argument: not a bool");
 
-                    edits.push(parsed_document.remove_import(start, is_qualified));
+                    imports.push((start, is_qualified));
                 }
                 _ => {
                     panic!("malformed unused_imports arguments: not a 2-tuple");
@@ -279,7 +1064,7 @@ fn unused_imports(
         }
     }
 
-    edits
+    imports
 }
 
 fn utf8_byte_array_is_hex_string(diagnostic: &lsp_types::Diagnostic) -> Vec<AnnotatedEdit> {
@@ -307,3 +1092,218 @@ fn use_let(diagnostic: &lsp_types::Diagnostic) -> Vec<AnnotatedEdit> {
         },
     )]
 }
+
+fn non_exhaustive_pattern_match(
+    parsed_document: &ParsedDocument,
+    diagnostic: &lsp_types::Diagnostic,
+) -> Vec<AnnotatedEdit> {
+    let mut edits = Vec::new();
+
+    if let Some(serde_json::Value::String(ref unmatched)) = diagnostic.data.as_ref() {
+        let unmatched = unmatched.lines().collect::<Vec<&str>>();
+
+        // Missing patterns on a 'Bool' are necessarily constructors without arguments, so we can
+        // add them as-is without having to look at the subject's type.
+        let is_bool = unmatched
+            .iter()
+            .all(|pattern| *pattern == "True" || *pattern == "False");
+
+        if is_bool {
+            let start = parsed_document.byte_index(diagnostic.range.start);
+            if let Some(edit) = parsed_document.add_missing_clauses(start, &unmatched) {
+                edits.push(edit);
+            }
+        }
+    }
+
+    edits
+}
+
+fn verbose_equality(diagnostic: &lsp_types::Diagnostic) -> Vec<AnnotatedEdit> {
+    let mut edits = Vec::new();
+
+    if let Some(serde_json::Value::String(ref comparison)) = diagnostic.data.as_ref() {
+        edits.push((
+            format!("Simplify to '{comparison}'"),
+            lsp_types::TextEdit {
+                range: diagnostic.range,
+                new_text: comparison.clone(),
+            },
+        ))
+    }
+
+    edits
+}
+
+fn single_use_type_alias(
+    parsed_document: &ParsedDocument,
+    diagnostic: &lsp_types::Diagnostic,
+) -> Vec<AnnotatedEdit> {
+    let mut edits = Vec::new();
+
+    if let Some(serde_json::Value::String(ref args)) = diagnostic.data.as_ref() {
+        if let Some((start, end)) = args.split_once(',') {
+            let use_location = Span {
+                start: start
+                    .parse()
+                    .expect("malformed single_use_type_alias argument: not a usize"),
+                end: end
+                    .parse()
+                    .expect("malformed single_use_type_alias argument: not a usize"),
+            };
+
+            edits.extend(parsed_document.inline_type_alias(
+                parsed_document.byte_index(diagnostic.range.start),
+                use_location,
+            ));
+        }
+    }
+
+    edits
+}
+
+fn unused_variable(
+    parsed_document: &ParsedDocument,
+    diagnostic: &lsp_types::Diagnostic,
+) -> Vec<AnnotatedEdit> {
+    let mut edits = Vec::new();
+
+    if let Some(serde_json::Value::String(ref args)) = diagnostic.data.as_ref() {
+        if let [name, start, end] = args.split(',').collect::<Vec<&str>>().as_slice() {
+            let location = Span {
+                start: start
+                    .parse()
+                    .expect("malformed unused_variable argument: not a usize"),
+                end: end
+                    .parse()
+                    .expect("malformed unused_variable argument: not a usize"),
+            };
+
+            edits.extend(parsed_document.prefix_unused_variable(name, location));
+        }
+    }
+
+    edits
+}
+
+fn unreachable_code(
+    parsed_document: &ParsedDocument,
+    diagnostic: &lsp_types::Diagnostic,
+) -> Vec<AnnotatedEdit> {
+    parsed_document
+        .remove_unreachable_code(parsed_document.byte_index(diagnostic.range.end))
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_suggestions_order() {
+        let modules = [
+            ("acme/utils", "acme/collection/list"),
+            ("aiken-lang/stdlib", "aiken/collection/list"),
+            ("my/project", "project/list"),
+            ("acme/utils", "acme/list"),
+            ("my/project", "project/collection/list"),
+        ];
+
+        let ordered = modules
+            .into_iter()
+            .sorted_by_key(|(package, module)| import_relevance("my/project", package, module))
+            .map(|(_, module)| module)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ordered,
+            vec![
+                "project/collection/list",
+                "project/list",
+                "aiken/collection/list",
+                "acme/collection/list",
+                "acme/list",
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_field_suggestions() {
+        let diagnostic = |data: &str| lsp_types::Diagnostic {
+            range: lsp_types::Range {
+                start: lsp_types::Position::new(3, 2),
+                end: lsp_types::Position::new(3, 14),
+            },
+            data: Some(serde_json::Value::String(data.to_string())),
+            ..lsp_types::Diagnostic::default()
+        };
+
+        let suggestions = unknown_field(&diagnostic("amout\nowner\namounts\namount\nasset"))
+            .into_iter()
+            .map(|(title, edit)| (title, edit.range.start.character, edit.new_text))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            suggestions,
+            vec![
+                ("Replace with 'amount'".to_string(), 9, "amount".to_string()),
+                (
+                    "Replace with 'amounts'".to_string(),
+                    9,
+                    "amounts".to_string()
+                ),
+            ]
+        );
+
+        assert!(unknown_field(&diagnostic("foo\nowner\namount")).is_empty());
+    }
+
+    #[test]
+    fn misspelled_variable_suggestions() {
+        let parsed_document = ParsedDocument::new(
+            r#"fn foo(subject: Option<Int>, total: Int, totals: Int) -> Int {
+  when subject is {
+    Some(count) -> count
+    None -> totl + cont
+  }
+}
+"#
+            .to_string(),
+        )
+        .unwrap();
+
+        let diagnostic = |name: &str, column: u32| lsp_types::Diagnostic {
+            range: lsp_types::Range {
+                start: lsp_types::Position::new(3, column),
+                end: lsp_types::Position::new(3, column + name.len() as u32),
+            },
+            data: Some(serde_json::Value::String(name.to_string())),
+            ..lsp_types::Diagnostic::default()
+        };
+
+        let suggestions = misspelled_variable(&parsed_document, &diagnostic("totl", 12))
+            .into_iter()
+            .map(|(title, edit)| (title, edit.range.start, edit.new_text))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            suggestions,
+            vec![
+                (
+                    "Did you mean 'total'?".to_string(),
+                    lsp_types::Position::new(3, 12),
+                    "total".to_string()
+                ),
+                (
+                    "Did you mean 'totals'?".to_string(),
+                    lsp_types::Position::new(3, 12),
+                    "totals".to_string()
+                ),
+            ]
+        );
+
+        // Bindings of another clause are out of scope.
+        assert!(misspelled_variable(&parsed_document, &diagnostic("cont", 19)).is_empty());
+    }
+}
@@ -6,15 +6,16 @@ use crate::{
     quickfix::Quickfix,
     utils::{
         path_to_uri, span_to_lsp_range, text_edit_replace, uri_to_module_name,
-        COMPILING_PROGRESS_TOKEN, CREATE_COMPILING_PROGRESS_TOKEN,
+        COMPILING_PROGRESS_TOKEN, CREATE_COMPILING_PROGRESS_TOKEN, REGENERATE_DOCS_COMMAND,
     },
 };
 use aiken_lang::{
-    ast::{Definition, Located, ModuleKind, Span, Use},
+    ast::{Definition, DefinitionLocation, Located, ModuleKind, Span, Use},
     error::ExtraData,
+    format::Formatter,
     line_numbers::LineNumbers,
     parser,
-    tipo::pretty::Printer,
+    tipo::{pretty::Printer, Type},
 };
 use aiken_project::{
     config::{self, Config},
@@ -30,16 +31,17 @@ use lsp_types::{
         Notification, Progress, PublishDiagnostics, ShowMessage,
     },
     request::{
-        CodeActionRequest, Completion, Formatting, GotoDefinition, HoverRequest, Request,
-        WorkDoneProgressCreate,
+        CodeActionRequest, Completion, ExecuteCommand, Formatting, GotoDefinition, HoverRequest,
+        Request, WorkDoneProgressCreate,
     },
     DocumentFormattingParams, InitializeParams, TextEdit,
 };
 use miette::Diagnostic;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashMap,
     fs,
     path::{Path, PathBuf},
+    rc::Rc,
 };
 
 pub mod lsp_project;
@@ -57,8 +59,8 @@ pub struct Server {
 
     initialize_params: InitializeParams,
 
-    /// Files for which there are active diagnostics
-    published_diagnostics: HashSet<lsp_types::Url>,
+    /// Files for which there are active diagnostics, alongside those diagnostics
+    published_diagnostics: HashMap<lsp_types::Url, Vec<lsp_types::Diagnostic>>,
 
     /// Diagnostics that have been emitted by the compiler but not yet published
     /// to the client
@@ -76,7 +78,7 @@ pub struct Server {
 impl Server {
     /// Clear all diagnostics that have been previously published to the client
     fn clear_all_diagnostics(&mut self, connection: &Connection) -> Result<(), ServerError> {
-        for file in self.published_diagnostics.drain() {
+        for (file, _) in self.published_diagnostics.drain() {
             let params = lsp_types::PublishDiagnosticsParams {
                 uri: file,
                 diagnostics: vec![],
@@ -362,6 +364,68 @@ impl Server {
                         );
                         actions.extend(quickfixes);
                     }
+
+                    // NOTE: Unused definitions are removed all at once, for the whole document;
+                    // regardless of the diagnostics in the requested range.
+                    let unused_definitions = self
+                        .published_diagnostics
+                        .get(&params.text_document.uri)
+                        .into_iter()
+                        .flatten()
+                        .filter(|diagnostic| quickfix::is_unused_definition(diagnostic))
+                        .cloned()
+                        .collect_vec();
+
+                    if !unused_definitions.is_empty() {
+                        actions.extend(quickfix::quickfix(
+                            compiler,
+                            &params.text_document,
+                            &Quickfix::UnusedDefinitions(unused_definitions),
+                        ));
+                    }
+
+                    // NOTE: Likewise, organizing imports is about the whole document and therefore
+                    // relies on all its unused imports.
+                    let unused_imports = self
+                        .published_diagnostics
+                        .get(&params.text_document.uri)
+                        .into_iter()
+                        .flatten()
+                        .filter(|diagnostic| quickfix::is_unused_import(diagnostic))
+                        .cloned()
+                        .collect_vec();
+
+                    actions.extend(quickfix::organize_imports(
+                        &params.text_document,
+                        unused_imports,
+                    ));
+
+                    if let Some(module) = self.module_for_uri(&params.text_document.uri) {
+                        let empty_list = quickfix::annotate_empty_list(
+                            module,
+                            &params.text_document,
+                            params.range,
+                        );
+
+                        // NOTE: Both would otherwise offer the very same annotation.
+                        if empty_list.is_empty() {
+                            actions.extend(quickfix::annotate_type(
+                                module,
+                                &params.text_document,
+                                params.range,
+                            ));
+                        } else {
+                            actions.extend(empty_list);
+                        }
+                    }
+
+                    actions.extend(quickfix::list_combinator(
+                        compiler,
+                        &params.text_document,
+                        params.range,
+                    ));
+
+                    actions.extend(quickfix::refactor(&params.text_document, params.range));
                 }
 
                 Ok(lsp_server::Response {
@@ -371,12 +435,61 @@ impl Server {
                 })
             }
 
+            ExecuteCommand::METHOD => {
+                let params = cast_request::<ExecuteCommand>(request)?;
+
+                if params.command == REGENERATE_DOCS_COMMAND {
+                    self.regenerate_docs(connection)?;
+                }
+
+                Ok(lsp_server::Response {
+                    id,
+                    error: None,
+                    result: Some(serde_json::json!(null)),
+                })
+            }
+
             unsupported => Err(ServerError::UnsupportedLspRequest {
                 request: unsupported.to_string(),
             }),
         }
     }
 
+    /// Regenerate the documentation of the modules which changed since it was last generated.
+    /// Problems are shown as messages, leaving the published diagnostics untouched.
+    fn regenerate_docs(&mut self, connection: &Connection) -> Result<(), ServerError> {
+        let Some(compiler) = self.compiler.as_mut() else {
+            return Ok(());
+        };
+
+        let messages = match compiler.regenerate_docs() {
+            Ok(warnings) => warnings
+                .into_iter()
+                .map(|warning| lsp_types::ShowMessageParams {
+                    typ: lsp_types::MessageType::WARNING,
+                    message: warning.to_string(),
+                })
+                .collect_vec(),
+            Err(error) => vec![lsp_types::ShowMessageParams {
+                typ: lsp_types::MessageType::ERROR,
+                message: error.to_string(),
+            }],
+        };
+
+        for message in messages {
+            let notification = lsp_server::Notification {
+                method: ShowMessage::METHOD.to_string(),
+                params: serde_json::to_value(message)?,
+            };
+
+            connection
+                .sender
+                .send(lsp_server::Message::Notification(notification))?;
+        }
+
+        Ok(())
+    }
+
     fn completion(
         &self,
         params: lsp_types::CompletionParams,
@@ -511,27 +624,55 @@ impl Server {
             None => return Ok(None),
         };
 
-        let (location, definition_location, tipo) = match found {
+        let (location, signature, definition_location) = match found {
             Located::Expression(expression) => (
                 expression.location(),
+                pretty_type(expression.tipo()),
                 expression.definition_location(),
-                Some(expression.tipo()),
             ),
-            Located::Pattern(pattern, tipo) => (pattern.location(), None, Some(tipo)),
-            Located::Argument(arg_name, tipo) => (arg_name.location(), None, Some(tipo)),
+            Located::Pattern(pattern, tipo) => (pattern.location(), pretty_type(tipo), None),
+            Located::Argument(arg_name, tipo) => (arg_name.location(), pretty_type(tipo), None),
+            Located::Definition(Definition::Fn(function)) => (
+                function.location,
+                Formatter::new()
+                    .docs_fn_signature(
+                        &function.name,
+                        &function.arguments,
+                        &function.return_annotation,
+                        function.return_type.clone(),
+                        MAX_COLUMNS,
+                    )
+                    .to_pretty_string(MAX_COLUMNS),
+                Some(DefinitionLocation {
+                    module: None,
+                    span: function.location,
+                }),
+            ),
+            Located::Definition(Definition::ModuleConstant(constant)) => (
+                constant.location,
+                Formatter::new()
+                    .docs_const_expr(&constant.name, &constant.value)
+                    .to_pretty_string(MAX_COLUMNS),
+                Some(DefinitionLocation {
+                    module: None,
+                    span: constant.location,
+                }),
+            ),
             Located::Definition(_) => return Ok(None),
             Located::Annotation(_) => return Ok(None),
         };
 
         let doc = definition_location
-            .and_then(|loc| loc.module.map(|m| (m, loc.span)))
-            .and_then(|(m, span)| {
-                self.compiler
-                    .as_ref()
-                    .and_then(|compiler| compiler.modules.get(m))
-                    .map(|checked_module| (checked_module, span))
+            .and_then(|loc| {
+                let module = match loc.module {
+                    Some(name) => self
+                        .compiler
+                        .as_ref()
+                        .and_then(|compiler| compiler.modules.get(name)),
+                    None => self.module_for_uri(&params.text_document.uri),
+                }?;
+                module.ast.find_node(loc.span.start)
             })
-            .and_then(|(checked_module, span)| checked_module.ast.find_node(span.start))
             .and_then(|node| match node {
                 Located::Expression(_) => None,
                 Located::Pattern(_, _) => None,
@@ -539,23 +680,21 @@ impl Server {
                 Located::Annotation(_) => None,
                 Located::Definition(def) => def.doc(),
             })
+            .map(|doc| hover_documentation(&doc))
             .unwrap_or_default();
 
-        // Show the type of the hovered node to the user
-        let type_ = match tipo {
-            Some(t) => Printer::new().pretty_print(t.as_ref(), 0),
-            None => "?".to_string(),
-        };
-
         let contents = formatdoc! {r#"
             ```aiken
-            {type_}
+            {signature}
             ```
             {doc}
         "#};
 
         Ok(Some(lsp_types::Hover {
-            contents: lsp_types::HoverContents::Scalar(lsp_types::MarkedString::String(contents)),
+            contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: contents,
+            }),
             range: Some(span_to_lsp_range(location, &line_numbers)),
         }))
     }
@@ -603,7 +742,7 @@ impl Server {
             config,
             edited: HashMap::new(),
             initialize_params,
-            published_diagnostics: HashSet::new(),
+            published_diagnostics: HashMap::new(),
             stored_diagnostics: HashMap::new(),
             stored_messages: Vec::new(),
             compiler: None,
@@ -738,7 +877,8 @@ impl Server {
 
             // Record that we have published diagnostics to this file so we can
             // clear it later when they are outdated.
-            self.published_diagnostics.insert(uri.clone());
+            self.published_diagnostics
+                .insert(uri.clone(), diagnostics.clone());
 
             // Publish the diagnostics
             let params = lsp_types::PublishDiagnosticsParams {
@@ -850,3 +990,40 @@ impl Server {
         Ok(())
     }
 }
+
+/// Maximum width of signatures shown on hover.
+const MAX_COLUMNS: isize = 80;
+
+/// Maximum number of documentation lines shown on hover.
+const MAX_HOVER_DOC_LINES: usize = 30;
+
+/// Show the type of a hovered node to the user.
+fn pretty_type(tipo: Rc<Type>) -> String {
+    Printer::new().pretty_print(tipo.as_ref(), 0)
+}
+
+/// Documentation of a definition, cut after a reasonable length so that hover popups remain
+/// usable; closing any code block left open.
+fn hover_documentation(doc: &str) -> String {
+    let lines = doc.lines().collect_vec();
+
+    if lines.len() <= MAX_HOVER_DOC_LINES {
+        return doc.to_string();
+    }
+
+    let mut truncated = lines[..MAX_HOVER_DOC_LINES].join("\n");
+
+    if lines[..MAX_HOVER_DOC_LINES]
+        .iter()
+        .filter(|line| line.trim_start().starts_with("```"))
+        .count()
+        % 2
+        == 1
+    {
+        truncated.push_str("\n```");
+    }
+
+    truncated.push_str("\n\n*…see the full documentation for more.*");
+
+    truncated
+}
@@ -1,6 +1,12 @@
 use aiken_lang::{ast::Tracing, line_numbers::LineNumbers, test_framework::PropertyTest};
-use aiken_project::{config::Config, error::Error as ProjectError, module::CheckedModule, Project};
-use std::{collections::HashMap, path::PathBuf};
+use aiken_project::{
+    config::Config,
+    docs::{self, incremental::DocCache},
+    error::{Error as ProjectError, Warning},
+    module::CheckedModule,
+    Project,
+};
+use std::{collections::HashMap, fs, path::PathBuf};
 
 #[derive(Debug)]
 pub struct SourceInfo {
@@ -15,14 +21,20 @@ pub struct LspProject {
     pub project: Project<super::telemetry::Lsp>,
     pub modules: HashMap<String, CheckedModule>,
     pub sources: HashMap<String, SourceInfo>,
+    /// Project root directory
+    pub root: PathBuf,
+    /// Search entries of the modules documented so far, to only regenerate what changed.
+    pub docs: DocCache,
 }
 
 impl LspProject {
     pub fn new(config: Config, root: PathBuf, telemetry: super::telemetry::Lsp) -> Self {
         Self {
-            project: Project::new_with_config(config, root, telemetry),
+            project: Project::new_with_config(config, root.clone(), telemetry),
             modules: HashMap::new(),
             sources: HashMap::new(),
+            root,
+            docs: DocCache::default(),
         }
     }
 
@@ -53,7 +65,7 @@ impl LspProject {
                 .to_string_lossy()
                 .to_string();
 
-            let line_numbers = LineNumbers::new(&module.code);
+            let line_numbers = module.ast.lines.clone();
 
             let source = SourceInfo { path, line_numbers };
 
@@ -67,4 +79,48 @@ impl LspProject {
 
         Ok(())
     }
+
+    /// Regenerate the documentation of the project's modules which changed since the last
+    /// compilation, into the default 'docs' folder; as 'aiken docs' would, images included.
+    pub fn regenerate_docs(&mut self) -> Result<Vec<Warning>, ProjectError> {
+        let mut config = self.project.config().clone();
+
+        let doc_fs = docs::fs::DefaultFileSystem::default();
+
+        let (images, mut warnings) = docs::check_assets(&doc_fs, &self.root, &mut config);
+
+        let modules = self
+            .modules
+            .values()
+            .filter(|module| module.package == config.name.to_string())
+            .collect();
+
+        let (doc_files, failures) = self.docs.regenerate(&doc_fs, &self.root, &config, modules);
+
+        let destination = self.root.join("docs");
+
+        for file in doc_files {
+            let path = destination.join(file.path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, file.content)?;
+        }
+
+        for image in images {
+            fs::write(destination.join(image.path), image.content)?;
+        }
+
+        warnings.extend(failures.into_iter().map(
+            |docs::DocFailure {
+                 path,
+                 module,
+                 reason,
+             }| Warning::DocRenderingFailed {
+                path,
+                module,
+                reason,
+            },
+        ));
+
+        Ok(warnings)
+    }
 }
@@ -9,6 +9,7 @@ use crate::error::Error;
 
 pub const COMPILING_PROGRESS_TOKEN: &str = "compiling-aiken";
 pub const CREATE_COMPILING_PROGRESS_TOKEN: &str = "create-compiling-progress-token";
+pub const REGENERATE_DOCS_COMMAND: &str = "aiken.regenerateDocs";
 
 pub fn text_edit_replace(new_text: String) -> TextEdit {
     TextEdit {
@@ -5,14 +5,21 @@ use aiken_lang::{
     parser::token::Base,
 };
 pub use aiken_lang::{plutus_version::PlutusVersion, version::compiler_version};
+use globset::Glob;
+use itertools::Itertools;
 use miette::NamedSource;
-use semver::Version;
+use semver::{Version, VersionReq};
 use serde::{
     de,
     ser::{self, SerializeSeq, SerializeStruct},
     Deserialize, Serialize,
 };
-use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Config {
@@ -34,6 +41,12 @@ pub struct Config {
     pub dependencies: Vec<Dependency>,
     #[serde(default)]
     pub config: BTreeMap<String, BTreeMap<String, SimpleExpr>>,
+    #[serde(default, skip_serializing_if = "Docs::is_default")]
+    pub docs: Docs,
+    /// Modules that have been moved, from their old path to their new one. Imports of an old
+    /// path can then be migrated automatically.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub renamed_modules: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug)]
@@ -276,12 +289,481 @@ pub struct Repository {
     pub platform: Platform,
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum Platform {
     Github,
     Gitlab,
     Bitbucket,
+    /// Any other (e.g. self-hosted) forge. The blob template describes links to source files,
+    /// and may refer to '{base_url}', '{user}', '{project}', '{version}', '{path}' as well as
+    /// '{start}' and '{end}' for the first and last lines.
+    Custom {
+        base_url: String,
+        blob_template: String,
+    },
+}
+
+impl Platform {
+    /// Home page of a repository hosted on this platform.
+    pub fn repository_url(&self, user: &str, project: &str) -> String {
+        match self {
+            Platform::Github => format!("https://github.com/{user}/{project}"),
+            Platform::Gitlab => format!("https://gitlab.com/{user}/{project}"),
+            Platform::Bitbucket => format!("https://bitbucket.org/{user}/{project}"),
+            Platform::Custom { base_url, .. } => {
+                format!("{}/{user}/{project}", base_url.trim_end_matches('/'))
+            }
+        }
+    }
+
+    /// Link to the given lines of a file of a repository hosted on this platform.
+    pub fn blob_url(
+        &self,
+        repository: &Repository,
+        version: &str,
+        path: &str,
+        (start, end): (usize, usize),
+    ) -> String {
+        let base = self.repository_url(&repository.user, &repository.project);
+
+        let template = match self {
+            Platform::Github => format!("{base}/blob/{{version}}/{{path}}#L{{start}}-L{{end}}"),
+            Platform::Gitlab => format!("{base}/-/blob/{{version}}/{{path}}#L{{start}}-{{end}}"),
+            Platform::Bitbucket => {
+                format!("{base}/src/{{version}}/{{path}}#lines-{{start}}:{{end}}")
+            }
+            Platform::Custom { blob_template, .. } => blob_template.clone(),
+        };
+
+        let base_url = match self {
+            Platform::Custom { base_url, .. } => base_url.trim_end_matches('/'),
+            _ => "",
+        };
+
+        template
+            .replace("{base_url}", base_url)
+            .replace("{user}", &repository.user)
+            .replace("{project}", &repository.project)
+            .replace("{version}", version)
+            .replace("{path}", path)
+            .replace("{start}", &start.to_string())
+            .replace("{end}", &end.to_string())
+    }
+}
+
+/// Options controlling the output of 'aiken docs'.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Debug, Default)]
+pub struct Docs {
+    #[serde(default)]
+    pub format: DocFormat,
+    /// Split the sidebar into 'stable' and 'unstable' modules, the latter being those whose
+    /// documentation carries an '@unstable' marker.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub group_by_stability: bool,
+    /// List, for each function, other public functions often used alongside it or operating on
+    /// the same types. This requires analysing all function bodies of the project.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub related: bool,
+    /// Also document private functions, types and constants, marked as such. Meant for internal
+    /// references of a library, rather than for its users.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub include_private: bool,
+    /// Modules left out of the documentation altogether, as globs matched against their name
+    /// (e.g. 'tests/**').
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<Glob>,
+    /// Show, under each module of the sidebar, the first sentence of its documentation.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sidebar_summaries: bool,
+    #[serde(default, skip_serializing_if = "DocAnchors::is_default")]
+    pub anchors: DocAnchors,
+    #[serde(default, skip_serializing_if = "DocLayout::is_default")]
+    pub layout: DocLayout,
+    #[serde(default, skip_serializing_if = "DocSearchData::is_default")]
+    pub search_data: DocSearchData,
+    #[serde(default, skip_serializing_if = "DocHighlighting::is_default")]
+    pub highlighting: DocHighlighting,
+    /// Produce bare, semantic HTML pages, without any script (hence without search bar nor
+    /// client-side highlighting) and with minimal styling. Meant for embedding the
+    /// documentation into other websites.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub plain: bool,
+    /// Width, in columns, beyond which signatures and definitions wrap. Defaults to that of the
+    /// formatter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_columns: Option<u16>,
+    /// Range of compiler versions the documented library is known to work with (e.g.
+    /// '>=1.1.0, <2.0.0'), shown in the footer next to the version that generated the docs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compatible_compiler: Option<VersionReq>,
+    /// Where the documentation is published (e.g. 'https://example.com/docs'), used to produce
+    /// absolute URLs. Links between pages stay relative regardless.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Embed structured data (JSON-LD) describing the package in every page, for search engines.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub json_ld: bool,
+    /// Also generate a single 'API.md' markdown file with the reference of every module, e.g. to
+    /// commit it alongside the code or to convert it into a printable document.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub api_reference: bool,
+    /// Also generate a 'manifest.json' listing every generated file with its size and hash.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub manifest: bool,
+    /// Also generate an 'llms.txt' file (see https://llmstxt.org) summarising the package and
+    /// linking to the page of each module, for language models to consume. Links are absolute
+    /// when a base URL is configured.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub llms_txt: bool,
+    /// Order in which the kinds of members are listed on module pages, both in the sidebar and
+    /// in the content. Kinds left out keep their default relative order, after the listed ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<DocMembers>,
+    #[serde(default, skip_serializing_if = "DocSort::is_default")]
+    pub sort: DocSort,
+    /// Image used as favicon in place of the default one, as a path relative to the project's
+    /// root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<PathBuf>,
+    /// Image shown next to the project's name in the header of every page, as a path relative
+    /// to the project's root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<PathBuf>,
+    /// Template of 'edit this page' links, either absolute or relative to the configured
+    /// repository (e.g. 'edit/main/{path}' on GitHub). It may refer to '{path}', the source file
+    /// relative to the project's root ('README.md' for the index page), to '{module}', the name of
+    /// the documented module, and to '{version}'. Links are omitted without a repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit_url: Option<String>,
+    /// Privacy-friendly (i.e. cookie-less) analytics, added to every page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analytics: Option<DocAnalytics>,
+    /// Colours of the pages and of their code: either the name of a built-in theme (see
+    /// 'DocTheme'), or the path of a stylesheet relative to the project's root, loaded after the
+    /// default one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+}
+
+impl Docs {
+    pub fn is_default(&self) -> bool {
+        self == &Docs::default()
+    }
+
+    pub fn max_columns(&self) -> isize {
+        self.max_columns
+            .map(|columns| columns as isize)
+            .unwrap_or(aiken_lang::format::MAX_COLUMNS)
+    }
+
+    /// Absolute URL of a file of the generated documentation, given by its path relative to
+    /// the documentation's root. None unless a base URL is configured.
+    pub fn absolute_url(&self, path: &str) -> Option<String> {
+        self.base_url
+            .as_deref()
+            .map(|base_url| base_url.trim().trim_end_matches('/'))
+            .filter(|base_url| !base_url.is_empty())
+            .map(|base_url| format!("{base_url}/{}", path.trim_start_matches('/')))
+    }
+
+    /// Where the favicon lands in the generated documentation, be it the configured one or the
+    /// default.
+    pub fn favicon_output(&self) -> PathBuf {
+        self.favicon
+            .as_deref()
+            .map(|source| image_output(source, "favicon"))
+            .unwrap_or_else(|| PathBuf::from("favicon.svg"))
+    }
+
+    /// Where the logo, if any, lands in the generated documentation.
+    pub fn logo_output(&self) -> Option<PathBuf> {
+        self.logo
+            .as_deref()
+            .map(|source| image_output(source, "logo"))
+    }
+
+    /// Configured images, as paths relative to the project's root, along with where they land
+    /// in the generated documentation.
+    pub fn images(&self) -> Vec<(&Path, PathBuf)> {
+        self.favicon
+            .as_deref()
+            .map(|source| (source, self.favicon_output()))
+            .into_iter()
+            .chain(self.logo.as_deref().zip(self.logo_output()))
+            .collect()
+    }
+
+    /// The configured theme, or the default one when none (or an unknown one) is configured.
+    pub fn theme(&self) -> DocTheme {
+        self.theme
+            .as_deref()
+            .and_then(DocTheme::from_name)
+            .unwrap_or_default()
+    }
+
+    /// Every kind of members exactly once, following the configured order.
+    pub fn members_order(&self) -> Vec<DocMembers> {
+        self.order
+            .iter()
+            .chain(DocMembers::DEFAULT_ORDER.iter())
+            .copied()
+            .unique()
+            .collect()
+    }
+}
+
+/// Name of an image in the generated documentation, keeping the extension of its source.
+fn image_output(source: &Path, name: &str) -> PathBuf {
+    match source.extension() {
+        Some(extension) => PathBuf::from(name).with_extension(extension),
+        None => PathBuf::from(name),
+    }
+}
+
+/// A cookie-less analytics service, loaded through a single script tag.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Debug)]
+pub struct DocAnalytics {
+    pub provider: AnalyticsProvider,
+    /// The site's domain with Plausible, or the website id with Umami.
+    pub id: String,
+    /// Location of the tracking script, when self-hosted. It must be served over HTTPS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum AnalyticsProvider {
+    #[serde(rename = "plausible")]
+    Plausible,
+    #[serde(rename = "umami")]
+    Umami,
+}
+
+impl DocAnalytics {
+    /// Source of the tracking script; none when it wouldn't be served over HTTPS.
+    pub fn script_src(&self) -> Option<&str> {
+        let src = match (&self.script, self.provider) {
+            (Some(script), _) => script.as_str(),
+            (None, AnalyticsProvider::Plausible) => "https://plausible.io/js/script.js",
+            (None, AnalyticsProvider::Umami) => "https://cloud.umami.is/script.js",
+        };
+
+        Some(src).filter(|src| src.starts_with("https://"))
+    }
+
+    /// Name of the attribute identifying the website to the provider.
+    pub fn id_attribute(&self) -> &'static str {
+        match self.provider {
+            AnalyticsProvider::Plausible => "data-domain",
+            AnalyticsProvider::Umami => "data-website-id",
+        }
+    }
+}
+
+/// Colour themes of the documentation. Built-in ones come in a light and a dark variant, between
+/// which readers can toggle.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub enum DocTheme {
+    #[default]
+    AtomOne,
+    GitHub,
+    Solarized,
+    /// A stylesheet of the project, as a path relative to its root. Its rules override those of
+    /// the default theme.
+    Custom(PathBuf),
+}
+
+impl DocTheme {
+    pub const BUILT_IN: [&'static str; 3] = ["atom-one", "github", "solarized"];
+
+    /// The built-in theme of the given name, or a custom one when given a '.css' file.
+    pub fn from_name(name: &str) -> Option<DocTheme> {
+        match name {
+            "atom-one" => Some(DocTheme::AtomOne),
+            "github" => Some(DocTheme::GitHub),
+            "solarized" => Some(DocTheme::Solarized),
+            _ if name.ends_with(".css") => Some(DocTheme::Custom(PathBuf::from(name))),
+            _ => None,
+        }
+    }
+
+    /// Name of the stylesheet highlighting code in light mode.
+    pub fn light_highlighting(&self) -> &'static str {
+        match self {
+            DocTheme::AtomOne | DocTheme::Custom(..) => "atom-one-light",
+            DocTheme::GitHub => "github-light",
+            DocTheme::Solarized => "solarized-light",
+        }
+    }
+
+    /// Name of the stylesheet highlighting code in dark mode.
+    pub fn dark_highlighting(&self) -> &'static str {
+        match self {
+            DocTheme::AtomOne | DocTheme::Custom(..) => "atom-one-dark",
+            DocTheme::GitHub => "github-dark",
+            DocTheme::Solarized => "solarized-dark",
+        }
+    }
+
+    /// Whether pages load a 'css/theme.css' stylesheet over the default one, which sets the
+    /// palette of any theme but the default.
+    pub fn has_stylesheet(&self) -> bool {
+        self != &DocTheme::AtomOne
+    }
+}
+
+/// Kinds of members documented on module pages.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum DocMembers {
+    #[serde(rename = "validators")]
+    Validators,
+    #[serde(rename = "types")]
+    Types,
+    #[serde(rename = "constants")]
+    Constants,
+    #[serde(rename = "functions")]
+    Functions,
+}
+
+impl DocMembers {
+    pub const DEFAULT_ORDER: [DocMembers; 4] = [
+        DocMembers::Validators,
+        DocMembers::Types,
+        DocMembers::Constants,
+        DocMembers::Functions,
+    ];
+}
+
+/// How anchors (i.e. URL fragments) of module items and section headings are computed.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DocAnchors {
+    /// Items' names as-is, and url-encoded section headings.
+    #[default]
+    #[serde(rename = "names")]
+    Names,
+    /// Slugs following common markdown heading rules: lowercased, stripped of any character
+    /// but alphanumerics, '-' and '_', and with spaces replaced by '-'. Those are what most
+    /// external link-checkers expect.
+    #[serde(rename = "slugs")]
+    Slugs,
+}
+
+impl DocAnchors {
+    pub fn is_default(&self) -> bool {
+        self == &DocAnchors::default()
+    }
+}
+
+/// Where the pages of modules land in the generated documentation.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DocLayout {
+    /// One page per module, named after it (e.g. 'aiken/collection/list.html').
+    #[default]
+    #[serde(rename = "flat")]
+    Flat,
+    /// One folder per module, holding an 'index.html' page (e.g.
+    /// 'aiken/collection/list/index.html'), for URLs without extension such as
+    /// 'aiken/collection/list/' once served.
+    #[serde(rename = "nested")]
+    Nested,
+}
+
+impl DocLayout {
+    pub fn is_default(&self) -> bool {
+        self == &DocLayout::default()
+    }
+
+    /// Path of the page of a module, relative to the documentation's root.
+    pub fn module_page(&self, module: &str) -> String {
+        match self {
+            DocLayout::Flat => format!("{module}.html"),
+            DocLayout::Nested => format!("{module}/index.html"),
+        }
+    }
+
+    /// Link to the page of a module, relative to the documentation's root.
+    pub fn module_link(&self, module: &str) -> String {
+        match self {
+            DocLayout::Flat => format!("{module}.html"),
+            DocLayout::Nested => format!("{module}/"),
+        }
+    }
+}
+
+/// How pages load the index behind the search bar.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DocSearchData {
+    /// A 'search-data.js' script, which registers the index once loaded.
+    #[default]
+    #[serde(rename = "script")]
+    Script,
+    /// A 'search-data.json' file, fetched at runtime. Unlike the script, this is compatible
+    /// with a strict Content-Security-Policy.
+    #[serde(rename = "fetch")]
+    Fetch,
+}
+
+impl DocSearchData {
+    pub fn is_default(&self) -> bool {
+        self == &DocSearchData::default()
+    }
+}
+
+/// Where Aiken code blocks get their syntax highlighting.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DocHighlighting {
+    /// In the browser, with highlight.js, once pages are loaded.
+    #[default]
+    #[serde(rename = "client")]
+    Client,
+    /// When generating the documentation, so that pages need no highlighter script. Only code
+    /// blocks of documentation are highlighted; definitions, signatures and other languages
+    /// stay plain.
+    #[serde(rename = "static")]
+    Static,
+}
+
+impl DocHighlighting {
+    pub fn is_default(&self) -> bool {
+        self == &DocHighlighting::default()
+    }
+}
+
+/// Order of functions within each kind of members (or section thereof) on module pages and in
+/// search results. Types, constants and validators are always listed alphabetically.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DocSort {
+    /// As defined in the source code.
+    #[default]
+    #[serde(rename = "source")]
+    SourceOrder,
+    #[serde(rename = "alphabetical")]
+    Alphabetical,
+}
+
+impl DocSort {
+    pub fn is_default(&self) -> bool {
+        self == &DocSort::default()
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DocFormat {
+    /// Only generate the HTML website.
+    #[default]
+    #[serde(rename = "html")]
+    Html,
+    /// Generate the HTML website, plus machine-readable JSON files next to it: one per module,
+    /// and a 'docs.json' gathering them all.
+    #[serde(rename = "html+json")]
+    HtmlAndJson,
+}
+
+impl DocFormat {
+    pub fn includes_json(&self) -> bool {
+        matches!(self, DocFormat::HtmlAndJson)
+    }
 }
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Debug)]
@@ -293,10 +775,11 @@ pub struct Dependency {
 
 impl Display for Platform {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
-        match *self {
+        match self {
             Platform::Github => f.write_str("github"),
             Platform::Gitlab => f.write_str("gitlab"),
             Platform::Bitbucket => f.write_str("bitbucket"),
+            Platform::Custom { base_url, .. } => f.write_str(base_url),
         }
     }
 }
@@ -327,9 +810,37 @@ impl Config {
                 source: Platform::Github,
             }],
             config: BTreeMap::new(),
+            docs: Docs::default(),
+            renamed_modules: BTreeMap::new(),
         }
     }
 
+    /// Link to edit a source file of the project, given by its path relative to the project's
+    /// root and the module it defines (empty when none). None unless both a repository and an
+    /// edit URL template are configured.
+    pub fn edit_url(&self, module: &str, path: &str) -> Option<String> {
+        let repository = self.repository.as_ref()?;
+        let template = self.docs.edit_url.as_deref()?;
+
+        let url = if template.contains("://") {
+            template.to_string()
+        } else {
+            format!(
+                "{}/{}",
+                repository
+                    .platform
+                    .repository_url(&repository.user, &repository.project),
+                template.trim_start_matches('/')
+            )
+        };
+
+        Some(
+            url.replace("{module}", module)
+                .replace("{path}", path)
+                .replace("{version}", &self.version),
+        )
+    }
+
     pub fn save(&self, dir: &Path) -> Result<(), io::Error> {
         let aiken_toml_path = dir.join(paths::project_config());
         let aiken_toml = toml::to_string_pretty(self).unwrap();
@@ -422,6 +933,41 @@ mod tests {
         expr: SimpleExpr,
     }
 
+    #[test]
+    fn docs_theme() {
+        let docs = |theme: &str| Docs {
+            theme: Some(theme.to_string()),
+            ..Docs::default()
+        };
+
+        assert_eq!(Docs::default().theme(), DocTheme::AtomOne);
+        assert_eq!(docs("github").theme(), DocTheme::GitHub);
+        assert_eq!(docs("unknown").theme(), DocTheme::AtomOne);
+        assert_eq!(
+            docs("assets/docs.css").theme(),
+            DocTheme::Custom(PathBuf::from("assets/docs.css"))
+        );
+    }
+
+    #[test]
+    fn docs_absolute_url() {
+        let docs = |base_url: Option<&str>| Docs {
+            base_url: base_url.map(str::to_string),
+            ..Docs::default()
+        };
+
+        assert_eq!(docs(None).absolute_url("index.html"), None);
+        assert_eq!(docs(Some("")).absolute_url("index.html"), None);
+        assert_eq!(
+            docs(Some("https://example.com/docs/")).absolute_url("aiken/list.html"),
+            Some("https://example.com/docs/aiken/list.html".to_string())
+        );
+        assert_eq!(
+            docs(Some("https://example.com")).absolute_url("/index.html"),
+            Some("https://example.com/index.html".to_string())
+        );
+    }
+
     proptest! {
         #[test]
         fn round_trip_simple_expr(expr in arbitrary_simple_expr()) {
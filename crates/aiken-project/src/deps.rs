@@ -125,7 +125,7 @@ impl From<&Manifest> for LocalPackages {
                 .map(|p| Dependency {
                     name: p.name.clone(),
                     version: p.version.clone(),
-                    source: p.source,
+                    source: p.source.clone(),
                 })
                 .collect(),
         }
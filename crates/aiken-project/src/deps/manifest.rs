@@ -134,7 +134,7 @@ where
                 name: dep.name.clone(),
                 version: dep.version.clone(),
                 requirements: vec![],
-                source: dep.source,
+                source: dep.source.clone(),
             })
             .collect(),
         requirements: config.dependencies.clone(),
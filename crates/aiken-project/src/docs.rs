@@ -1,32 +1,50 @@
 use crate::{
-    config::{Config, Repository},
+    config::{
+        Config, DocAnalytics, DocAnchors, DocHighlighting, DocLayout, DocMembers, DocSearchData,
+        DocSort, DocTheme, Repository,
+    },
+    error::Warning,
     module::CheckedModule,
 };
 use aiken_lang::{
     ast::{
         DataType, Definition, Function, ModuleConstant, RecordConstructor, Span, TypeAlias,
-        TypedDefinition,
+        TypedArg, TypedDefinition, TypedFunction, Use, Validator,
     },
+    expr::TypedExpr,
     format,
     parser::extra::Comment,
     tipo::Type,
 };
 use askama::Template;
+use globset::Glob;
 use itertools::Itertools;
+use pallas_crypto::hash::Hasher;
 use pulldown_cmark as markdown;
 use serde::Serialize;
 use serde_json as json;
 use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
     path::{Path, PathBuf},
     rc::Rc,
     time::{Duration, SystemTime},
 };
 
+/// Types of arguments, listed in a table, never wrap.
 const MAX_COLUMNS: isize = 999;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub mod api_reference;
+pub mod diff;
+pub mod fs;
+pub mod highlight;
+pub mod incremental;
 pub mod link_tree;
+pub mod related;
 pub mod source_links;
+pub mod symbols;
+pub mod type_links;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DocFile {
@@ -34,30 +52,68 @@ pub struct DocFile {
     pub content: String,
 }
 
+/// An image of the project (i.e. its favicon or logo), copied as is next to the generated files.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DocImage {
+    pub path: PathBuf,
+    pub content: Vec<u8>,
+}
+
+/// A module, or page, which couldn't be rendered and is left out of the documentation. Pages
+/// which aren't those of a module are named after their path.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DocFailure {
+    pub path: PathBuf,
+    pub module: String,
+    pub reason: String,
+}
+
 #[derive(Template)]
 #[template(path = "module.html")]
 struct ModuleTemplate<'a> {
     aiken_version: &'a str,
+    compatible_compiler: String,
+    json_ld: String,
+    description: String,
+    url: Option<String>,
+    analytics: Option<&'a DocAnalytics>,
+    search_data: DocSearchData,
+    highlighting: DocHighlighting,
+    theme: DocTheme,
+    plain: bool,
+    favicon: String,
+    logo: Option<String>,
     breadcrumbs: String,
     page_title: &'a str,
     module_name: String,
     project_name: &'a str,
     project_version: &'a str,
     modules: &'a [DocLink],
+    unstable_modules: &'a [DocLink],
     functions: Vec<Interspersed>,
     types: Vec<DocType>,
     constants: Vec<DocConstant>,
+    validators: Vec<DocValidator>,
+    members_order: Vec<DocMembers>,
+    toc: Vec<DocTocSection>,
     documentation: String,
     source: &'a DocLink,
+    edit_url: Option<String>,
     timestamp: String,
+    last_modified: String,
 }
 
 impl<'a> ModuleTemplate<'a> {
     pub fn is_current_module(&self, module: &DocLink) -> bool {
-        match module.path.split(".html").next() {
-            None => false,
-            Some(name) => self.module_name == name,
-        }
+        let name = module
+            .path
+            .strip_suffix(".html")
+            .or_else(|| module.path.strip_suffix('/'));
+        name == Some(self.module_name.as_str())
+    }
+
+    pub fn build_date(&self) -> String {
+        build_date(&self.timestamp)
     }
 }
 
@@ -65,20 +121,78 @@ impl<'a> ModuleTemplate<'a> {
 #[template(path = "page.html")]
 struct PageTemplate<'a> {
     aiken_version: &'a str,
+    compatible_compiler: String,
+    json_ld: String,
+    description: String,
+    url: Option<String>,
+    analytics: Option<&'a DocAnalytics>,
+    search_data: DocSearchData,
+    highlighting: DocHighlighting,
+    theme: DocTheme,
+    plain: bool,
+    favicon: String,
+    logo: Option<String>,
     breadcrumbs: &'a str,
-    page_title: &'a str,
-    project_name: &'a str,
-    project_version: &'a str,
+    page_title: String,
+    project_name: String,
+    project_version: String,
     modules: &'a [DocLink],
+    unstable_modules: &'a [DocLink],
     content: String,
     source: &'a DocLink,
-    timestamp: &'a str,
+    edit_url: Option<String>,
+    timestamp: String,
 }
 
 impl<'a> PageTemplate<'a> {
+    /// A page at the root of the documentation, titled after its content and the project.
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        config: &'a Config,
+        modules: &'a [DocLink],
+        unstable_modules: &'a [DocLink],
+        source: &'a DocLink,
+        timestamp: &Duration,
+        path: &str,
+        title: &str,
+        content: String,
+    ) -> Self {
+        PageTemplate {
+            aiken_version: VERSION,
+            compatible_compiler: compatible_compiler(config),
+            json_ld: json_ld(config, title, path, source),
+            description: page_description(config, ""),
+            url: config.docs.absolute_url(path),
+            edit_url: None,
+            analytics: config.docs.analytics.as_ref(),
+            search_data: config.docs.search_data,
+            highlighting: config.docs.highlighting,
+            plain: config.docs.plain,
+            theme: config.docs.theme(),
+            favicon: config.docs.favicon_output().to_string_lossy().to_string(),
+            logo: config
+                .docs
+                .logo_output()
+                .map(|logo| logo.to_string_lossy().to_string()),
+            breadcrumbs: ".",
+            modules,
+            unstable_modules,
+            project_name: config.name.repo.to_string(),
+            page_title: format!("{title} - {}", config.name),
+            project_version: config.version.to_string(),
+            content,
+            source,
+            timestamp: timestamp.as_secs().to_string(),
+        }
+    }
+
     pub fn is_current_module(&self, _module: &DocLink) -> bool {
         false
     }
+
+    pub fn build_date(&self) -> String {
+        build_date(&self.timestamp)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -86,6 +200,7 @@ pub struct DocLink {
     indent: usize,
     name: String,
     path: String,
+    summary: String,
 }
 
 impl DocLink {
@@ -103,15 +218,313 @@ impl DocLink {
 /// The documentation is built using template files located at the root of this crate.
 /// With the documentation, we also build a client-side search index to ease navigation
 /// across multiple modules.
-pub fn generate_all(root: &Path, config: &Config, modules: Vec<&CheckedModule>) -> Vec<DocFile> {
+///
+/// Files of the project (e.g. the README) are read through the given filesystem, which may hold
+/// some of them in memory (see 'fs::OverlayFileSystem'). The generated files are returned
+/// rather than written, so that it is up to the caller to store them wherever is relevant.
+///
+/// Modules whose page fails to render are left out, as are other pages failing to render; the
+/// failures are returned alongside, so that the rest of the documentation is still generated.
+pub fn generate_all(
+    fs: &dyn fs::FileSystem,
+    root: &Path,
+    config: &Config,
+    modules: Vec<&CheckedModule>,
+) -> (Vec<DocFile>, Vec<DocFailure>) {
+    let timestamp = new_timestamp();
+
+    let mut modules = without_excluded(config, modules);
+    let mut failures: Vec<DocFailure> = vec![];
+
+    // Modules which fail to render are left out altogether, so that no page links to them. The
+    // remaining ones are then rendered again, as links differ; which only happens on failures.
+    let (context, generated) = loop {
+        let context = Context::new(config, &modules);
+        let mut generated = vec![];
+        let mut failed = vec![];
+
+        for module in modules
+            .iter()
+            .filter(|module| !module.skip_doc_generation())
+        {
+            match generate_module(
+                root,
+                config,
+                module,
+                &context.modules_links,
+                &context.unstable_modules_links,
+                &context.related,
+                &context.types,
+                &context.symbols,
+                &context.source,
+                &timestamp,
+                &last_modified(fs, module, &timestamp),
+            ) {
+                Ok(module_docs) => generated.push((*module, module_docs)),
+                Err(error) => failed.push(DocFailure {
+                    path: module.input_path.clone(),
+                    module: module.name.clone(),
+                    reason: error.to_string(),
+                }),
+            }
+        }
+
+        if failed.is_empty() {
+            break (context, generated);
+        }
+
+        modules.retain(|module| !failed.iter().any(|failure| failure.module == module.name));
+        failures.extend(failed);
+    };
+
+    let Context {
+        modules_links,
+        unstable_modules_links,
+        related: _,
+        types: _,
+        symbols,
+        source,
+    } = context;
+
+    let mut output_files: Vec<DocFile> = vec![];
+    let mut search_indexes: Vec<SearchIndex> = vec![];
+    let mut api_references: Vec<(String, String)> = vec![];
+    let mut modules_json: Vec<json::Value> = vec![];
+    let mut documented: Vec<&CheckedModule> = vec![];
+
+    for (module, (indexes, files, api_reference, module_json)) in generated {
+        if !indexes.is_empty() {
+            search_indexes.extend(indexes);
+            output_files.extend(files);
+            api_references.extend(api_reference.map(|reference| (module.name.clone(), reference)));
+            modules_json.extend(module_json);
+            documented.push(module);
+        }
+    }
+
+    let search_indexes = dedup_search_indexes(search_indexes, config.docs.layout);
+
+    let pages = [
+        generate_index_of_symbols(
+            config,
+            &modules_links,
+            &unstable_modules_links,
+            &source,
+            &timestamp,
+            &search_indexes,
+            &documented,
+        )
+        .map(Some),
+        generate_search_page(
+            config,
+            &modules_links,
+            &unstable_modules_links,
+            &source,
+            &timestamp,
+            &search_indexes,
+        )
+        .map(Some),
+        generate_changelog(
+            config,
+            &modules_links,
+            &unstable_modules_links,
+            &source,
+            &timestamp,
+            &search_indexes,
+        ),
+        generate_dependencies_page(
+            config,
+            &modules_links,
+            &unstable_modules_links,
+            &source,
+            &timestamp,
+            &documented,
+        ),
+    ];
+
+    for page in pages {
+        match page {
+            Ok(file) => output_files.extend(file),
+            Err(failure) => failures.push(failure),
+        }
+    }
+
+    output_files.extend(generate_static_assets(fs, root, config, search_indexes));
+
+    match generate_readme(
+        fs,
+        root,
+        config,
+        &modules_links,
+        &unstable_modules_links,
+        &symbols,
+        &source,
+        &timestamp,
+    ) {
+        Ok(file) => output_files.push(file),
+        Err(failure) => failures.push(failure),
+    }
+
+    if config.docs.api_reference {
+        output_files.push(api_reference::generate(config, &api_references));
+    }
+
+    if config.docs.format.includes_json() {
+        output_files.push(generate_docs_json(config, modules_json));
+    }
+
+    output_files.extend(generate_sitemap(config, &output_files, &timestamp));
+
+    if config.docs.llms_txt {
+        output_files.push(generate_llms_txt(config, &documented));
+    }
+
+    if config.docs.manifest {
+        output_files.push(generate_manifest(&output_files));
+    }
+
+    (output_files, failures)
+}
+
+/// Modules to document, i.e. those which aren't excluded by the configuration.
+fn without_excluded<'a>(
+    config: &Config,
+    modules: Vec<&'a CheckedModule>,
+) -> Vec<&'a CheckedModule> {
+    let excluded = config
+        .docs
+        .exclude
+        .iter()
+        .map(Glob::compile_matcher)
+        .collect_vec();
+
+    modules
+        .into_iter()
+        .filter(|module| !excluded.iter().any(|glob| glob.is_match(&module.name)))
+        .collect_vec()
+}
+
+/// What the page of each module depends on, besides the module itself.
+#[derive(Debug)]
+struct Context {
+    modules_links: Vec<DocLink>,
+    unstable_modules_links: Vec<DocLink>,
+    related: related::RelatedIndex,
+    types: type_links::TypeIndex,
+    symbols: symbols::SymbolIndex,
+    source: DocLink,
+}
+
+impl Context {
+    fn new(config: &Config, modules: &[&CheckedModule]) -> Self {
+        let (stable_modules, unstable_modules): (Vec<_>, Vec<_>) = if config.docs.group_by_stability
+        {
+            modules.iter().partition(|module| !module.is_unstable())
+        } else {
+            (modules.to_vec(), vec![])
+        };
+
+        Context {
+            modules_links: generate_modules_links(
+                &stable_modules,
+                config.docs.sidebar_summaries,
+                config.docs.include_private,
+                config.docs.layout,
+            ),
+            unstable_modules_links: generate_modules_links(
+                &unstable_modules,
+                config.docs.sidebar_summaries,
+                config.docs.include_private,
+                config.docs.layout,
+            ),
+            related: if config.docs.related {
+                related::RelatedIndex::new(modules)
+            } else {
+                related::RelatedIndex::default()
+            },
+            types: type_links::TypeIndex::new(
+                modules,
+                config.docs.include_private,
+                config.docs.anchors,
+                config.docs.layout,
+            ),
+            symbols: symbols::SymbolIndex::new(
+                modules,
+                config.docs.include_private,
+                config.docs.anchors,
+                config.docs.layout,
+            ),
+            source: source_link(config),
+        }
+    }
+}
+
+/// Generate the page of a single module, e.g. to refresh it alone. Unlike with 'generate_all',
+/// links from the module only resolve to its own definitions; the given links to modules and to
+/// the source repository (see 'modules_links' and 'source_link') make up the navigation.
+pub fn generate_module_page(
+    root: &Path,
+    config: &Config,
+    module: &CheckedModule,
+    modules: &[DocLink],
+    source: &DocLink,
+) -> Result<DocFile, DocFailure> {
     let timestamp = new_timestamp();
-    let modules_links = generate_modules_links(&modules);
+    let failure = |reason: String| DocFailure {
+        path: module.input_path.clone(),
+        module: module.name.clone(),
+        reason,
+    };
+
+    let (_, files, _, _) = generate_module(
+        root,
+        config,
+        module,
+        modules,
+        &[],
+        &related::RelatedIndex::default(),
+        &type_links::TypeIndex::new(
+            &[module],
+            config.docs.include_private,
+            config.docs.anchors,
+            config.docs.layout,
+        ),
+        &symbols::SymbolIndex::new(
+            &[module],
+            config.docs.include_private,
+            config.docs.anchors,
+            config.docs.layout,
+        ),
+        source,
+        &timestamp,
+        &timestamp,
+    )
+    .map_err(|error| failure(error.to_string()))?;
+
+    files
+        .into_iter()
+        .find(|file| file.path.extension() == Some("html".as_ref()))
+        .ok_or_else(|| failure("nothing to document".to_string()))
+}
+
+/// Links to the given modules, as listed in the sidebar of documentation pages.
+pub fn modules_links(config: &Config, modules: &[&CheckedModule]) -> Vec<DocLink> {
+    generate_modules_links(
+        modules,
+        config.docs.sidebar_summaries,
+        config.docs.include_private,
+        config.docs.layout,
+    )
+}
 
-    let source = match &config.repository {
+/// Link to the source repository of a project, if any.
+pub fn source_link(config: &Config) -> DocLink {
+    match &config.repository {
         None => DocLink {
             indent: 0,
             name: String::new(),
             path: String::new(),
+            summary: String::new(),
         },
         Some(Repository {
             user,
@@ -120,50 +533,118 @@ pub fn generate_all(root: &Path, config: &Config, modules: Vec<&CheckedModule>)
         }) => DocLink {
             indent: 0,
             name: format!("{user}/{project}"),
-            path: format!("https://{platform}.com/{user}/{project}"),
+            path: platform.repository_url(user, project),
+            summary: String::new(),
         },
-    };
+    }
+}
 
-    let mut output_files: Vec<DocFile> = vec![];
-    let mut search_indexes: Vec<SearchIndex> = vec![];
+/// Leave out of the configuration the favicon, logo and theme of the documentation which can't
+/// be found (or aren't known), with a warning for each; so that no page links to them. The
+/// configured images are read meanwhile, for the caller to copy them next to the generated files.
+pub fn check_assets(
+    fs: &dyn fs::FileSystem,
+    root: &Path,
+    config: &mut Config,
+) -> (Vec<DocImage>, Vec<Warning>) {
+    let mut warnings = vec![];
 
-    for module in &modules {
-        if module.skip_doc_generation() {
-            continue;
+    for image in [&mut config.docs.favicon, &mut config.docs.logo] {
+        if let Some(path) = image.take() {
+            if fs.read(&root.join(&path)).is_some() {
+                *image = Some(path);
+            } else {
+                warnings.push(Warning::MissingDocAsset { path });
+            }
         }
+    }
 
-        let (indexes, file) =
-            generate_module(root, config, module, &modules_links, &source, &timestamp);
-        if !indexes.is_empty() {
-            search_indexes.extend(indexes);
-            output_files.push(file);
+    if let Some(name) = config.docs.theme.take() {
+        match DocTheme::from_name(&name) {
+            None => warnings.push(Warning::UnknownDocTheme { name }),
+            Some(DocTheme::Custom(path)) if fs.read_to_string(&root.join(&path)).is_none() => {
+                warnings.push(Warning::MissingDocTheme { path })
+            }
+            Some(_) => config.docs.theme = Some(name),
         }
     }
 
-    output_files.extend(generate_static_assets(search_indexes));
-    output_files.push(generate_readme(
-        root,
-        config,
-        &modules_links,
-        &source,
-        &timestamp,
-    ));
+    let images = config
+        .docs
+        .images()
+        .into_iter()
+        .filter_map(|(source, path)| {
+            fs.read(&root.join(source))
+                .map(|content| DocImage { path, content })
+        })
+        .collect();
 
-    output_files
+    (images, warnings)
 }
 
+/// Definitions whose '@since' marker doesn't hold a version, as the path of their module, their
+/// name and the marker's value. Such markers are left out of the documentation.
+pub fn invalid_since_markers(modules: &[&CheckedModule]) -> Vec<(PathBuf, String, String)> {
+    modules
+        .iter()
+        .filter(|module| !module.skip_doc_generation())
+        .flat_map(|module| {
+            module.ast.definitions().filter_map(move |def| {
+                let (name, doc) = match def {
+                    Definition::Fn(Function { name, doc, .. })
+                    | Definition::ModuleConstant(ModuleConstant { name, doc, .. })
+                    | Definition::DataType(DataType { name, doc, .. })
+                    | Definition::TypeAlias(TypeAlias {
+                        alias: name, doc, ..
+                    })
+                    | Definition::Validator(Validator { name, doc, .. }) => (name, doc),
+                    _ => return None,
+                };
+
+                split_marker(doc.as_deref(), "@since")
+                    .1
+                    .filter(|since| !is_version(since))
+                    .map(|since| (module.input_path.clone(), name.clone(), since))
+            })
+        })
+        .collect()
+}
+
+/// Search indexes, files, API reference and JSON documentation of a module.
+type GeneratedModule = (
+    Vec<SearchIndex>,
+    Vec<DocFile>,
+    Option<String>,
+    Option<json::Value>,
+);
+
+#[allow(clippy::too_many_arguments)]
 fn generate_module(
     root: &Path,
     config: &Config,
     module: &CheckedModule,
     modules: &[DocLink],
+    unstable_modules: &[DocLink],
+    related: &related::RelatedIndex,
+    types: &type_links::TypeIndex,
+    symbols: &symbols::SymbolIndex,
     source: &DocLink,
     timestamp: &Duration,
-) -> (Vec<SearchIndex>, DocFile) {
+    last_modified: &Duration,
+) -> Result<GeneratedModule, askama::Error> {
     let mut search_indexes = vec![];
 
     let source_linker = source_links::SourceLinker::new(root, config, module);
 
+    // Link to the page of the module, relative to the documentation's root.
+    let page = config.docs.layout.module_link(&module.name);
+
+    let renderer = Renderer {
+        links: symbols::Resolver::new(symbols, &module.name),
+        highlighting: config.docs.highlighting,
+        plain: config.docs.plain,
+    };
+
     // Section headers
     let mut section_headers = module
         .extra
@@ -176,11 +657,13 @@ fn generate_module(
             if comment.starts_with("#") {
                 let trimmed = comment.trim_start_matches("#");
                 let heading = comment.len() - trimmed.len();
+                let title = trimmed.trim_start().to_string();
                 Some((
                     span,
                     DocSection {
                         heading,
-                        title: trimmed.trim_start().to_string(),
+                        anchor: to_anchor(config.docs.anchors, &title),
+                        title,
                     },
                 ))
             } else {
@@ -194,13 +677,41 @@ fn generate_module(
         .ast
         .definitions
         .iter()
-        .flat_map(|def| DocFunction::from_definition(def, &source_linker))
+        .filter(|def| is_documented(def, config.docs.include_private))
+        .flat_map(|def| {
+            DocFunction::from_definition(
+                def,
+                &source_linker,
+                types,
+                &renderer,
+                &module.name,
+                config.docs.anchors,
+                config.docs.max_columns(),
+            )
+        })
+        .map(|(span, mut function)| {
+            function.related = related
+                .related(&module.name, &function.name)
+                .into_iter()
+                .map(|(other_module, other_name)| DocLink {
+                    indent: 0,
+                    name: if other_module == &module.name {
+                        other_name.clone()
+                    } else {
+                        format!("{other_module}.{other_name}")
+                    },
+                    path: format!(
+                        "{}#{}",
+                        config.docs.layout.module_link(other_module),
+                        to_anchor(config.docs.anchors, other_name)
+                    ),
+                    summary: String::new(),
+                })
+                .collect();
+            (span, function)
+        })
         .collect();
 
-    functions.iter().for_each(|(_, function)| {
-        search_indexes.push(SearchIndex::from_function(module, function))
-    });
-
     let no_functions = functions.is_empty();
 
     let mut functions_and_headers = Vec::new();
@@ -218,99 +729,310 @@ fn generate_module(
             section_headers.remove(*ix);
         }
 
-        functions_and_headers.push(Interspersed::Function(function))
+        functions_and_headers.push(Interspersed::Function(Box::new(function)))
+    }
+
+    // Functions marked with a '@section' come after the others, grouped under a heading per
+    // section in order of first appearance.
+    let (in_sections, mut functions_and_headers): (Vec<_>, Vec<_>) =
+        functions_and_headers.into_iter().partition(
+            |item| matches!(item, Interspersed::Function(function) if function.section.is_some()),
+        );
+
+    let mut sections: Vec<(String, Vec<Interspersed>)> = vec![];
+    for item in in_sections {
+        let title = match &item {
+            Interspersed::Function(function) => function.section.clone().unwrap_or_default(),
+            Interspersed::Section(section) => section.title.clone(),
+        };
+        match sections.iter_mut().find(|(other, _)| other == &title) {
+            Some((_, functions)) => functions.push(item),
+            None => sections.push((title, vec![item])),
+        }
+    }
+
+    for (title, functions) in sections {
+        functions_and_headers.push(Interspersed::Section(DocSection {
+            heading: 1,
+            anchor: to_anchor(config.docs.anchors, &title),
+            title,
+        }));
+        functions_and_headers.extend(functions);
+    }
+
+    // Functions may be sorted alphabetically within their section, deprecated ones coming last.
+    for functions in
+        functions_and_headers.split_mut(|item| matches!(item, Interspersed::Section(..)))
+    {
+        if config.docs.sort == DocSort::Alphabetical {
+            functions.sort_by(|a, b| match (a, b) {
+                (Interspersed::Function(a), Interspersed::Function(b)) => a.name.cmp(&b.name),
+                _ => Ordering::Equal,
+            });
+        }
+        functions.sort_by_key(|item| {
+            matches!(item, Interspersed::Function(function) if function.deprecated.is_some())
+        });
     }
 
+    functions_and_headers.iter().for_each(|item| {
+        if let Interspersed::Function(function) = item {
+            search_indexes.push(SearchIndex::from_function(module, &page, function))
+        }
+    });
+
     // Types
     let types: Vec<DocType> = module
         .ast
         .definitions
         .iter()
-        .flat_map(|def| DocType::from_definition(def, &source_linker))
+        .filter(|def| is_documented(def, config.docs.include_private))
+        .flat_map(|def| {
+            DocType::from_definition(
+                def,
+                module,
+                &source_linker,
+                &renderer,
+                config.docs.anchors,
+                config.docs.max_columns(),
+            )
+        })
         .sorted()
+        .sorted_by_key(|type_info| type_info.deprecated.is_some())
         .collect();
-    types
-        .iter()
-        .for_each(|type_info| search_indexes.push(SearchIndex::from_type(module, type_info)));
+    types.iter().for_each(|type_info| {
+        search_indexes.extend(SearchIndex::from_type(module, &page, type_info))
+    });
 
     // Constants
     let constants: Vec<DocConstant> = module
         .ast
         .definitions
         .iter()
-        .flat_map(|def| DocConstant::from_definition(def, &source_linker))
+        .filter(|def| is_documented(def, config.docs.include_private))
+        .flat_map(|def| {
+            DocConstant::from_definition(
+                def,
+                &source_linker,
+                &renderer,
+                config.docs.anchors,
+                config.docs.max_columns(),
+            )
+        })
         .sorted()
+        .sorted_by_key(|constant| constant.deprecated.is_some())
         .collect();
-    constants
+    constants.iter().for_each(|constant| {
+        search_indexes.push(SearchIndex::from_constant(module, &page, constant))
+    });
+
+    // Validators
+    let validators: Vec<DocValidator> = module
+        .ast
+        .definitions
         .iter()
-        .for_each(|constant| search_indexes.push(SearchIndex::from_constant(module, constant)));
+        .filter(|def| is_documented(def, config.docs.include_private))
+        .flat_map(|def| {
+            DocValidator::from_definition(
+                def,
+                &source_linker,
+                &renderer,
+                config.docs.anchors,
+                config.docs.max_columns(),
+            )
+        })
+        .sorted()
+        .collect();
+    validators.iter().for_each(|validator| {
+        search_indexes.extend(SearchIndex::from_validator(module, &page, validator))
+    });
 
-    let is_empty = no_functions && types.is_empty() && constants.is_empty();
+    let is_empty =
+        no_functions && types.is_empty() && constants.is_empty() && validators.is_empty();
 
     // Module
     if !is_empty {
-        search_indexes.push(SearchIndex::from_module(module));
+        search_indexes.push(SearchIndex::from_module(module, &page));
     }
 
+    let mut files = vec![];
+
+    let doc_module = DocModule {
+        name: &module.name,
+        documentation: module_documentation(module),
+        functions: functions_and_headers
+            .iter()
+            .filter_map(|item| match item {
+                Interspersed::Function(function) => Some(function.as_ref()),
+                Interspersed::Section(..) => None,
+            })
+            .collect(),
+        types: &types,
+        constants: &constants,
+        validators: &validators,
+    };
+
+    let module_json = if config.docs.format.includes_json() {
+        let module_json = json::to_value(&doc_module).expect("module documentation serialization");
+        files.push(DocFile {
+            path: PathBuf::from(config.docs.layout.module_page(&module.name))
+                .with_extension("json"),
+            content: json::to_string_pretty(&module_json)
+                .expect("module documentation serialization"),
+        });
+        Some(module_json)
+    } else {
+        None
+    };
+
+    let api_reference = if config.docs.api_reference {
+        Some(api_reference::module(
+            &doc_module,
+            &config.docs.members_order(),
+        ))
+    } else {
+        None
+    };
+
+    let page_title = format!("{} - {}", module.name, config.name);
+
+    let toc = table_of_contents(
+        &config.docs.members_order(),
+        &functions_and_headers,
+        &types,
+        &constants,
+        &validators,
+    );
+
     let module = ModuleTemplate {
         aiken_version: VERSION,
-        breadcrumbs: to_breadcrumbs(&module.name),
-        documentation: render_markdown(&module.ast.docs.iter().join("\n")),
+        compatible_compiler: compatible_compiler(config),
+        analytics: config.docs.analytics.as_ref(),
+        search_data: config.docs.search_data,
+        highlighting: config.docs.highlighting,
+        plain: config.docs.plain,
+        theme: config.docs.theme(),
+        favicon: config.docs.favicon_output().to_string_lossy().to_string(),
+        logo: config
+            .docs
+            .logo_output()
+            .map(|logo| logo.to_string_lossy().to_string()),
+        json_ld: json_ld(config, &page_title, &page, source),
+        description: page_description(config, &module_documentation(module)),
+        url: config.docs.absolute_url(&page),
+        edit_url: config.edit_url(&module.name, source_linker.path()),
+        breadcrumbs: to_breadcrumbs(&config.docs.layout.module_page(&module.name)),
+        documentation: render_markdown(&module_documentation(module), "", &renderer),
         modules,
+        unstable_modules,
         project_name: &config.name.repo.to_string(),
-        page_title: &format!("{} - {}", module.name, config.name),
+        page_title: &page_title,
         module_name: module.name.clone(),
         project_version: &config.version.to_string(),
         functions: functions_and_headers,
         types,
         constants,
+        validators,
+        members_order: config.docs.members_order(),
+        toc,
         source,
         timestamp: timestamp.as_secs().to_string(),
+        last_modified: to_iso_date(last_modified),
     };
 
-    (
-        search_indexes,
-        DocFile {
-            path: PathBuf::from(format!("{}.html", module.module_name)),
-            content: module
-                .render()
-                .expect("Module documentation template rendering"),
-        },
-    )
+    files.push(DocFile {
+        path: PathBuf::from(config.docs.layout.module_page(&module.module_name)),
+        content: module.render()?,
+    });
+
+    Ok((search_indexes, files, api_reference, module_json))
 }
 
-fn generate_static_assets(search_indexes: Vec<SearchIndex>) -> Vec<DocFile> {
+fn generate_static_assets(
+    fs: &dyn fs::FileSystem,
+    root: &Path,
+    config: &Config,
+    search_indexes: Vec<SearchIndex>,
+) -> Vec<DocFile> {
     let mut assets: Vec<DocFile> = vec![];
 
-    assets.push(DocFile {
-        path: PathBuf::from("favicon.svg"),
-        content: std::include_str!("../templates/favicon.svg").to_string(),
-    });
+    // Configured images are copied over from the project, next to the generated files.
+    if config.docs.favicon.is_none() {
+        assets.push(DocFile {
+            path: PathBuf::from("favicon.svg"),
+            content: std::include_str!("../templates/favicon.svg").to_string(),
+        });
+    }
+
+    // Plain pages only come with some bare styling: no scripts, and hence no search data.
+    if config.docs.plain {
+        assets.push(DocFile {
+            path: PathBuf::from("css/plain.css"),
+            content: std::include_str!("../templates/css/plain.css").to_string(),
+        });
+
+        return assets;
+    }
+
+    let theme = config.docs.theme();
+
+    let (light, dark, palette) = match theme {
+        DocTheme::AtomOne | DocTheme::Custom(..) => (
+            std::include_str!("../templates/css/atom-one-light.min.css"),
+            std::include_str!("../templates/css/atom-one-dark.min.css"),
+            None,
+        ),
+        DocTheme::GitHub => (
+            std::include_str!("../templates/css/github-light.min.css"),
+            std::include_str!("../templates/css/github-dark.min.css"),
+            Some(std::include_str!("../templates/css/themes/github.css")),
+        ),
+        DocTheme::Solarized => (
+            std::include_str!("../templates/css/solarized-light.min.css"),
+            std::include_str!("../templates/css/solarized-dark.min.css"),
+            Some(std::include_str!("../templates/css/themes/solarized.css")),
+        ),
+    };
 
     assets.push(DocFile {
-        path: PathBuf::from("css/atom-one-light.min.css"),
-        content: std::include_str!("../templates/css/atom-one-light.min.css").to_string(),
+        path: PathBuf::from(format!("css/{}.min.css", theme.light_highlighting())),
+        content: light.to_string(),
     });
 
     assets.push(DocFile {
-        path: PathBuf::from("css/atom-one-dark.min.css"),
-        content: std::include_str!("../templates/css/atom-one-dark.min.css").to_string(),
+        path: PathBuf::from(format!("css/{}.min.css", theme.dark_highlighting())),
+        content: dark.to_string(),
     });
 
+    // Custom themes are read from the project instead.
+    let palette = match theme {
+        DocTheme::Custom(path) => fs.read_to_string(&root.join(path)),
+        _ => palette.map(str::to_string),
+    };
+
+    if let Some(palette) = palette {
+        assets.push(DocFile {
+            path: PathBuf::from("css/theme.css"),
+            content: palette,
+        });
+    }
+
     assets.push(DocFile {
         path: PathBuf::from("css/index.css"),
         content: std::include_str!("../templates/css/index.css").to_string(),
     });
 
-    assets.push(DocFile {
-        path: PathBuf::from("js/highlight.min.js"),
-        content: std::include_str!("../templates/js/highlight.min.js").to_string(),
-    });
+    if config.docs.highlighting == DocHighlighting::Client {
+        assets.push(DocFile {
+            path: PathBuf::from("js/highlight.min.js"),
+            content: std::include_str!("../templates/js/highlight.min.js").to_string(),
+        });
 
-    assets.push(DocFile {
-        path: PathBuf::from("js/highlightjs-aiken.js"),
-        content: std::include_str!("../templates/js/highlightjs-aiken.js").to_string(),
-    });
+        assets.push(DocFile {
+            path: PathBuf::from("js/highlightjs-aiken.js"),
+            content: std::include_str!("../templates/js/highlightjs-aiken.js").to_string(),
+        });
+    }
 
     assets.push(DocFile {
         path: PathBuf::from("js/lunr.min.js"),
@@ -322,389 +1044,4373 @@ fn generate_static_assets(search_indexes: Vec<SearchIndex>) -> Vec<DocFile> {
         content: std::include_str!("../templates/js/index.js").to_string(),
     });
 
-    assets.push(DocFile {
-        path: PathBuf::from("search-data.js"),
-        content: format!(
-            "window.Aiken.initSearch({});",
-            json::to_string(&escape_html_contents(search_indexes))
-                .expect("search index serialization")
-        ),
-    });
+    assets.extend(generate_search_data(config, search_indexes));
 
     assets
 }
 
-fn generate_readme(
-    root: &Path,
-    config: &Config,
-    modules: &[DocLink],
-    source: &DocLink,
-    timestamp: &Duration,
-) -> DocFile {
-    let path = PathBuf::from("index.html");
-
-    let content = std::fs::read_to_string(root.join("README.md")).unwrap_or_default();
+/// The data behind the client-side search, as loaded by the script of the pages.
+fn generate_search_data(config: &Config, search_indexes: Vec<SearchIndex>) -> Vec<DocFile> {
+    let mut files = vec![];
 
-    let template = PageTemplate {
-        aiken_version: VERSION,
-        breadcrumbs: ".",
-        modules,
-        project_name: &config.name.repo.to_string(),
-        page_title: &config.name.to_string(),
-        project_version: &config.version.to_string(),
-        content: render_markdown(&content),
-        source,
-        timestamp: &timestamp.as_secs().to_string(),
-    };
+    let search_data =
+        json::to_string(&escape_html_contents(search_indexes)).expect("search index serialization");
 
-    DocFile {
-        path,
-        content: template.render().expect("Page template rendering"),
+    if config.docs.search_data == DocSearchData::Script {
+        files.push(DocFile {
+            path: PathBuf::from("search-data.js"),
+            content: format!("window.Aiken.initSearch({search_data});"),
+        });
     }
+
+    files.push(DocFile {
+        path: PathBuf::from("search-data.json"),
+        content: search_data,
+    });
+
+    files
 }
 
-fn generate_modules_links(modules: &[&CheckedModule]) -> Vec<DocLink> {
-    let non_empty_modules = modules
-        .iter()
-        .filter(|module| {
-            !module.skip_doc_generation()
-                && module.ast.definitions.iter().any(|def| {
-                    matches!(
-                        def,
-                        Definition::Fn(Function { public: true, .. })
-                            | Definition::DataType(DataType { public: true, .. })
-                            | Definition::TypeAlias(TypeAlias { public: true, .. })
-                            | Definition::ModuleConstant(ModuleConstant { public: true, .. })
-                    )
-                })
-        })
-        .sorted_by(|a, b| a.name.cmp(&b.name))
-        .collect_vec();
+#[derive(Serialize)]
+struct ManifestEntry {
+    path: String,
+    size: usize,
+    blake2b_256: String,
+}
 
-    let mut links = link_tree::LinkTree::default();
+/// Version of the structure of 'docs.json', bumped whenever it changes in a way that isn't
+/// backward compatible.
+const DOCS_JSON_SCHEMA_VERSION: u32 = 1;
 
-    for module in non_empty_modules {
-        links.insert(module.name.as_str());
+/// The structured documentation of all modules, in a single file for tools to consume.
+fn generate_docs_json(config: &Config, modules: Vec<json::Value>) -> DocFile {
+    DocFile {
+        path: PathBuf::from("docs.json"),
+        content: json::to_string_pretty(&json::json!({
+            "schema_version": DOCS_JSON_SCHEMA_VERSION,
+            "name": config.name.to_string(),
+            "version": config.version.to_string(),
+            "modules": modules,
+        }))
+        .expect("docs serialization"),
     }
-
-    links.to_vec()
 }
 
-#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
-struct SearchIndex {
-    doc: String,
-    title: String,
-    content: String,
-    url: String,
-}
+/// List every generated page, for search engines to crawl. Only when the documentation's base
+/// URL is known, since a sitemap requires absolute URLs.
+fn generate_sitemap(config: &Config, files: &[DocFile], timestamp: &Duration) -> Option<DocFile> {
+    config.docs.absolute_url("")?;
 
-impl SearchIndex {
-    fn from_function(module: &CheckedModule, function: &DocFunction) -> Self {
-        SearchIndex {
-            doc: module.name.to_string(),
-            title: function.name.to_string(),
-            content: format!("{}\n{}", function.signature, function.raw_documentation),
-            url: format!("{}.html#{}", module.name, function.name),
-        }
-    }
+    let lastmod = to_iso_date(timestamp);
 
-    fn from_type(module: &CheckedModule, type_info: &DocType) -> Self {
-        let constructors = type_info
-            .constructors
-            .iter()
-            .map(|constructor| {
-                format!(
-                    "{}\n{}",
-                    constructor.definition, constructor.raw_documentation
-                )
-            })
-            .join("\n");
+    let mut content = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n"
+    ));
 
-        SearchIndex {
-            doc: module.name.to_string(),
-            title: type_info.name.to_string(),
-            content: format!(
-                "{}\n{}\n{}",
-                type_info.definition, type_info.raw_documentation, constructors,
-            ),
-            url: format!("{}.html#{}", module.name, type_info.name),
-        }
+    for path in files
+        .iter()
+        .filter(|file| {
+            file.path
+                .extension()
+                .map_or(false, |extension| extension == "html")
+        })
+        .map(|file| {
+            file.path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .join("/")
+        })
+        // Pages of modules are listed under the URL they're linked to.
+        .map(|path| match path.strip_suffix("/index.html") {
+            Some(module) if config.docs.layout == DocLayout::Nested => {
+                config.docs.layout.module_link(module)
+            }
+            _ => path,
+        })
+        .sorted_by_key(|path| (path != "index.html", path.clone()))
+    {
+        let loc = config.docs.absolute_url(&path)?;
+        content.push_str(&format!(
+            "  <url>\n    <loc>{}</loc>\n    <lastmod>{lastmod}</lastmod>\n  </url>\n",
+            escape_html_content(&loc)
+        ));
     }
 
-    fn from_constant(module: &CheckedModule, constant: &DocConstant) -> Self {
-        SearchIndex {
-            doc: module.name.to_string(),
-            title: constant.name.to_string(),
-            content: format!("{}\n{}", constant.definition, constant.raw_documentation),
-            url: format!("{}.html#{}", module.name, constant.name),
-        }
+    content.push_str("</urlset>\n");
+
+    Some(DocFile {
+        path: PathBuf::from("sitemap.xml"),
+        content,
+    })
+}
+
+/// Summary of the package following the llms.txt convention: its name, description and version,
+/// followed by a link to each documented module along with the first sentence of its
+/// documentation.
+fn generate_llms_txt(config: &Config, modules: &[&CheckedModule]) -> DocFile {
+    let url = |path: &str| {
+        config
+            .docs
+            .absolute_url(path)
+            .unwrap_or_else(|| path.to_string())
+    };
+
+    let mut content = format!("# {}\n\n", config.name);
+
+    let description = config.description.trim();
+    if !description.is_empty() {
+        content.push_str(&format!("> {}\n\n", description.lines().join(" ")));
     }
 
-    fn from_module(module: &CheckedModule) -> Self {
-        SearchIndex {
-            doc: module.name.to_string(),
-            title: module.name.to_string(),
-            content: module.ast.docs.iter().join("\n"),
-            url: format!("{}.html", module.name),
+    content.push_str(&format!("Version: {}\n\n## Modules\n\n", config.version));
+
+    for module in modules.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+        let link = url(&config.docs.layout.module_link(&module.name));
+        let summary = module_summary(module);
+        if summary.is_empty() {
+            content.push_str(&format!("- [{}]({link})\n", module.name));
+        } else {
+            content.push_str(&format!("- [{}]({link}): {summary}\n", module.name));
         }
     }
-}
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum Interspersed {
-    Section(DocSection),
-    Function(DocFunction),
-}
+    content.push_str(&format!(
+        "\n## Optional\n\n- [All symbols]({}): every definition of the package, by module\n",
+        url("all.html")
+    ));
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
-struct DocSection {
-    heading: usize,
-    title: String,
+    DocFile {
+        path: PathBuf::from("llms.txt"),
+        content,
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct DocFunction {
-    name: String,
-    signature: String,
-    documentation: String,
-    raw_documentation: String,
-    source_url: String,
-}
+/// List every generated file alongside its size (in bytes) and content hash, to ease auditing
+/// and verifying deployments.
+fn generate_manifest(files: &[DocFile]) -> DocFile {
+    let entries = files
+        .iter()
+        .map(|file| ManifestEntry {
+            path: file
+                .path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .join("/"),
+            size: file.content.len(),
+            blake2b_256: Hasher::<256>::hash(file.content.as_bytes()).to_string(),
+        })
+        .sorted_by(|a, b| a.path.cmp(&b.path))
+        .collect_vec();
 
-impl DocFunction {
-    fn from_definition(
-        def: &TypedDefinition,
-        source_linker: &source_links::SourceLinker,
-    ) -> Option<(Span, Self)> {
-        match def {
-            Definition::Fn(func_def) if func_def.public => Some((
-                func_def.location,
-                DocFunction {
-                    name: func_def.name.clone(),
-                    documentation: func_def
-                        .doc
-                        .as_deref()
-                        .map(render_markdown)
-                        .unwrap_or_default(),
-                    raw_documentation: func_def.doc.as_deref().unwrap_or_default().to_string(),
-                    signature: format::Formatter::new()
-                        .docs_fn_signature(
-                            &func_def.name,
-                            &func_def.arguments,
-                            &func_def.return_annotation,
-                            func_def.return_type.clone(),
-                        )
-                        .to_pretty_string(MAX_COLUMNS),
-                    source_url: source_linker
-                        .url(func_def.location.map_end(|_| func_def.end_position)),
-                },
-            )),
-            _ => None,
-        }
+    DocFile {
+        path: PathBuf::from("manifest.json"),
+        content: json::to_string_pretty(&json::json!({ "files": entries }))
+            .expect("manifest serialization"),
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
-struct DocConstant {
-    name: String,
-    definition: String,
-    documentation: String,
-    raw_documentation: String,
-    source_url: String,
+#[allow(clippy::too_many_arguments)]
+fn generate_readme(
+    fs: &dyn fs::FileSystem,
+    root: &Path,
+    config: &Config,
+    modules: &[DocLink],
+    unstable_modules: &[DocLink],
+    symbols: &symbols::SymbolIndex,
+    source: &DocLink,
+    timestamp: &Duration,
+) -> Result<DocFile, DocFailure> {
+    let path = PathBuf::from("index.html");
+
+    let content = fs
+        .read_to_string(&root.join("README.md"))
+        .unwrap_or_default();
+
+    let title = config.name.to_string();
+
+    let mut template = PageTemplate::new(
+        config,
+        modules,
+        unstable_modules,
+        source,
+        timestamp,
+        "index.html",
+        &title,
+        render_markdown(
+            &content,
+            "",
+            &Renderer {
+                links: symbols::Resolver::new(symbols, ""),
+                highlighting: config.docs.highlighting,
+                plain: config.docs.plain,
+            },
+        ),
+    );
+    template.page_title = title;
+    template.description = page_description(config, &content);
+    template.edit_url = config.edit_url("", "README.md");
+
+    render_page(path, &template)
 }
 
-impl DocConstant {
-    fn from_definition(
-        def: &TypedDefinition,
-        source_linker: &source_links::SourceLinker,
-    ) -> Option<Self> {
-        match def {
-            Definition::ModuleConstant(const_def) if const_def.public => Some(DocConstant {
-                name: const_def.name.clone(),
-                documentation: const_def
-                    .doc
-                    .as_deref()
-                    .map(render_markdown)
-                    .unwrap_or_default(),
-                raw_documentation: const_def.doc.as_deref().unwrap_or_default().to_string(),
-                definition: format::Formatter::new()
-                    .docs_const_expr(&const_def.name, &const_def.value)
-                    .to_pretty_string(MAX_COLUMNS),
-                source_url: source_linker.url(const_def.location),
-            }),
-            _ => None,
+/// A single page listing, alphabetically, the functions, types, constants and validators of all
+/// modules, from their search indexes.
+fn generate_index_of_symbols(
+    config: &Config,
+    modules: &[DocLink],
+    unstable_modules: &[DocLink],
+    source: &DocLink,
+    timestamp: &Duration,
+    search_indexes: &[SearchIndex],
+    documented: &[&CheckedModule],
+) -> Result<DocFile, DocFailure> {
+    let path = PathBuf::from("all.html");
+
+    // Modules link to their page, while constructors and handlers are qualified by their
+    // type or validator.
+    let symbols = search_indexes
+        .iter()
+        .filter(|entry| entry.url.contains('#') && !entry.title.contains('.'))
+        .sorted_by(|a, b| (a.title.to_lowercase(), &a.doc).cmp(&(b.title.to_lowercase(), &b.doc)))
+        .group_by(|entry| {
+            entry
+                .title
+                .chars()
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .unwrap_or_default()
+        });
+
+    let mut letters = String::new();
+    let mut content = String::new();
+
+    let changelog = if has_changelog(search_indexes) {
+        "<p><a href=\"changelog.html\">Changelog</a></p>\n"
+    } else {
+        ""
+    };
+
+    let dependencies = if has_dependencies(documented) {
+        "<p><a href=\"dependencies.html\">Dependencies</a></p>\n"
+    } else {
+        ""
+    };
+
+    for (letter, entries) in &symbols {
+        letters.push_str(&format!("<a href=\"#symbols-{letter}\">{letter}</a>\n"));
+        content.push_str(&format!(
+            "<h2 id=\"symbols-{letter}\">{letter}</h2>\n<ul class=\"symbols-list\">\n"
+        ));
+        for entry in entries {
+            content.push_str(&format!(
+                "<li><a href=\"{}\"><code>{}</code></a> <span class=\"symbols-module\">{}</span></li>\n",
+                entry.url,
+                escape_html_content(&entry.title),
+                escape_html_content(&entry.doc),
+            ));
         }
+        content.push_str("</ul>\n");
     }
+
+    let template = PageTemplate::new(
+        config,
+        modules,
+        unstable_modules,
+        source,
+        timestamp,
+        "all.html",
+        "All symbols",
+        format!(
+            "<h1>All symbols</h1>\n{changelog}{dependencies}<nav class=\"symbols-letters\">\n{letters}</nav>\n{content}"
+        ),
+    );
+
+    render_page(path, &template)
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-struct DocType {
-    name: String,
-    definition: String,
-    documentation: String,
-    raw_documentation: String,
-    constructors: Vec<DocTypeConstructor>,
-    parameters: Vec<String>,
-    opaque: bool,
-    source_url: String,
+/// Whether the documentation comes with a changelog, i.e. whether some definition has a
+/// '@since' marker.
+fn has_changelog(search_indexes: &[SearchIndex]) -> bool {
+    search_indexes.iter().any(|entry| entry.since.is_some())
 }
 
-impl DocType {
-    fn from_definition(
-        def: &TypedDefinition,
-        source_linker: &source_links::SourceLinker,
-    ) -> Option<Self> {
-        match def {
-            Definition::TypeAlias(info) if info.public => Some(DocType {
-                name: info.alias.clone(),
-                definition: format::Formatter::new()
-                    .docs_type_alias(&info.alias, &info.parameters, &info.annotation)
-                    .to_pretty_string(MAX_COLUMNS),
-                documentation: info.doc.as_deref().map(render_markdown).unwrap_or_default(),
-                raw_documentation: info.doc.as_deref().unwrap_or_default().to_string(),
-                constructors: vec![],
-                parameters: info.parameters.clone(),
-                opaque: false,
-                source_url: source_linker.url(info.location),
-            }),
+/// A page listing, for each version, the definitions it introduced or deprecated; as told by
+/// '@since' markers and deprecation messages starting with a version. Only generated when some
+/// definition has a '@since' marker.
+fn generate_changelog(
+    config: &Config,
+    modules: &[DocLink],
+    unstable_modules: &[DocLink],
+    source: &DocLink,
+    timestamp: &Duration,
+    search_indexes: &[SearchIndex],
+) -> Result<Option<DocFile>, DocFailure> {
+    if !has_changelog(search_indexes) {
+        return Ok(None);
+    }
 
-            Definition::DataType(info) if info.public && !info.opaque => Some(DocType {
-                name: info.name.clone(),
-                definition: format::Formatter::new()
-                    .docs_data_type(
-                        &info.name,
-                        &info.parameters,
-                        &info.constructors,
-                        &info.location,
-                    )
-                    .to_pretty_string(MAX_COLUMNS),
-                documentation: info.doc.as_deref().map(render_markdown).unwrap_or_default(),
-                raw_documentation: info.doc.as_deref().unwrap_or_default().to_string(),
-                constructors: info
-                    .constructors
-                    .iter()
-                    .map(DocTypeConstructor::from_record_constructor)
-                    .collect(),
-                parameters: info.parameters.clone(),
-                opaque: info.opaque,
-                source_url: source_linker.url(info.location),
-            }),
+    let path = PathBuf::from("changelog.html");
 
-            Definition::DataType(info) if info.public && info.opaque => Some(DocType {
-                name: info.name.clone(),
-                definition: format::Formatter::new()
-                    .docs_opaque_data_type(&info.name, &info.parameters, &info.location)
-                    .to_pretty_string(MAX_COLUMNS),
-                documentation: info.doc.as_deref().map(render_markdown).unwrap_or_default(),
-                raw_documentation: info.doc.as_deref().unwrap_or_default().to_string(),
-                constructors: vec![],
-                parameters: info.parameters.clone(),
-                opaque: info.opaque,
-                source_url: source_linker.url(info.location),
-            }),
+    // Definitions introduced and deprecated by each version, along with the version as written.
+    let mut versions: BTreeMap<semver::Version, (&str, Vec<_>, Vec<_>)> = BTreeMap::new();
+    let mut unversioned = vec![];
 
-            _ => None,
+    for entry in search_indexes
+        .iter()
+        .filter(|entry| entry.url.contains('#') && !entry.title.contains('.'))
+        .sorted_by(|a, b| (&a.doc, &a.title).cmp(&(&b.doc, &b.title)))
+    {
+        if let Some(since) = entry.since.as_deref() {
+            if let Some(version) = parse_version(since) {
+                let (_, added, _) = versions.entry(version).or_insert((since, vec![], vec![]));
+                added.push(entry);
+            }
         }
-    }
-}
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-struct DocTypeConstructor {
-    definition: String,
-    documentation: String,
-    raw_documentation: String,
-}
+        match entry.deprecated_in.as_deref() {
+            Some(deprecated_in) => {
+                if let Some(version) = parse_version(deprecated_in) {
+                    let (_, _, deprecated) =
+                        versions
+                            .entry(version)
+                            .or_insert((deprecated_in, vec![], vec![]));
+                    deprecated.push(entry);
+                }
+            }
+            None if entry.deprecated => unversioned.push(entry),
+            None => (),
+        }
+    }
 
-impl DocTypeConstructor {
-    fn from_record_constructor(constructor: &RecordConstructor<Rc<Type>>) -> Self {
-        let doc_args = constructor
-            .arguments
+    let list = |entries: &[&SearchIndex]| {
+        let items = entries
             .iter()
-            .filter_map(|arg| match (arg.label.as_deref(), arg.doc.as_deref()) {
-                (Some(label), Some(doc)) => Some(format!("#### `.{label}`\n{doc}\n<hr/>\n",)),
-                _ => None,
+            .map(|entry| {
+                format!(
+                    "<li><a href=\"{}\"><code>{}</code></a> <span class=\"symbols-module\">{}</span></li>\n",
+                    entry.url,
+                    escape_html_content(&entry.title),
+                    escape_html_content(&entry.doc),
+                )
             })
-            .join("\n");
+            .join("");
+        format!("<ul class=\"symbols-list\">\n{items}</ul>\n")
+    };
 
-        DocTypeConstructor {
-            definition: format::Formatter::new()
-                .docs_record_constructor(constructor)
-                .to_pretty_string(format::MAX_COLUMNS),
-            documentation: constructor
-                .doc
-                .as_deref()
-                .map(|doc| render_markdown(&format!("{doc}\n{doc_args}")))
-                .or(if doc_args.is_empty() {
-                    None
-                } else {
-                    Some(render_markdown(&format!("\n{doc_args}")))
-                })
-                .unwrap_or_default(),
-            raw_documentation: constructor.doc.as_deref().unwrap_or_default().to_string(),
+    let mut content = String::from("<h1>Changelog</h1>\n");
+
+    for (label, added, deprecated) in versions.values().rev() {
+        let label = escape_html_content(label);
+        content.push_str(&format!("<h2 id=\"version-{label}\">{label}</h2>\n"));
+        if !added.is_empty() {
+            content.push_str(&format!("<h3>New</h3>\n{}", list(added)));
+        }
+        if !deprecated.is_empty() {
+            content.push_str(&format!("<h3>Deprecated</h3>\n{}", list(deprecated)));
         }
     }
-}
 
-// ------ Extra Helpers
+    if !unversioned.is_empty() {
+        content.push_str(&format!(
+            "<h2 id=\"deprecated\">Deprecated, in an unknown version</h2>\n{}",
+            list(&unversioned)
+        ));
+    }
 
-fn render_markdown(text: &str) -> String {
-    let mut s = String::with_capacity(text.len() * 3 / 2);
-    let p = markdown::Parser::new_ext(text, markdown::Options::all());
-    markdown::html::push_html(&mut s, p);
-    s
+    let template = PageTemplate::new(
+        config,
+        modules,
+        unstable_modules,
+        source,
+        timestamp,
+        "changelog.html",
+        "Changelog",
+        content,
+    );
+
+    render_page(path, &template).map(Some)
 }
 
-fn escape_html_contents(indexes: Vec<SearchIndex>) -> Vec<SearchIndex> {
-    fn escape_html_content(it: String) -> String {
-        it.replace('&', "&amp;")
-            .replace('<', "&lt;")
-            .replace('>', "&gt;")
-            .replace('\"', "&quot;")
-            .replace('\'', "&#39;")
-    }
+/// Modules imported by each of the given ones, as found in their import statements.
+fn module_imports<'a>(modules: &[&'a CheckedModule]) -> BTreeMap<&'a str, Vec<String>> {
+    modules
+        .iter()
+        .map(|module| {
+            let imports = module
+                .ast
+                .definitions()
+                .filter_map(|def| match def {
+                    Definition::Use(Use { module, .. }) => Some(module.join("/")),
+                    _ => None,
+                })
+                .filter(|import| import != &module.name)
+                .sorted()
+                .dedup()
+                .collect_vec();
 
-    indexes
-        .into_iter()
-        .map(|idx| SearchIndex {
-            doc: idx.doc,
-            title: idx.title,
-            content: escape_html_content(idx.content),
-            url: idx.url,
+            (module.name.as_str(), imports)
         })
-        .collect::<Vec<SearchIndex>>()
+        .collect()
 }
 
-fn new_timestamp() -> Duration {
-    SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .expect("get current timestamp")
+/// Whether the documentation comes with a dependencies page, i.e. whether some documented module
+/// imports another one.
+fn has_dependencies(documented: &[&CheckedModule]) -> bool {
+    module_imports(documented)
+        .values()
+        .any(|imported| !imported.is_empty())
 }
 
-fn to_breadcrumbs(path: &str) -> String {
-    let breadcrumbs = path
-        .strip_prefix('/')
-        .unwrap_or(path)
-        .split('/')
-        .skip(1)
-        .map(|_| "..")
-        .join("/");
-    if breadcrumbs.is_empty() {
-        ".".to_string()
-    } else {
-        breadcrumbs
+/// A page telling, for each documented module, which modules it imports and which import it;
+/// as an adjacency list. Modules outside of the documentation (e.g. those of dependencies) are
+/// listed without a link. Only generated when some module imports another one.
+fn generate_dependencies_page(
+    config: &Config,
+    modules: &[DocLink],
+    unstable_modules: &[DocLink],
+    source: &DocLink,
+    timestamp: &Duration,
+    documented: &[&CheckedModule],
+) -> Result<Option<DocFile>, DocFailure> {
+    if !has_dependencies(documented) {
+        return Ok(None);
     }
-}
 
-#[test]
-fn to_breadcrumbs_test() {
-    // Pages
-    assert_eq!(to_breadcrumbs("a.html"), ".");
-    assert_eq!(to_breadcrumbs("/a.html"), ".");
-    assert_eq!(to_breadcrumbs("/a/b.html"), "..");
-    assert_eq!(to_breadcrumbs("/a/b/c.html"), "../..");
+    let imports = module_imports(documented);
 
-    // Modules
-    assert_eq!(to_breadcrumbs("a"), ".");
-    assert_eq!(to_breadcrumbs("a/b"), "..");
-    assert_eq!(to_breadcrumbs("a/b/c"), "../..");
+    let path = PathBuf::from("dependencies.html");
+
+    let mut imported_by: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (module, imported) in imports.iter() {
+        for import in imported {
+            imported_by.entry(import).or_default().push(module);
+        }
+    }
+
+    let link = |module: &str| {
+        if imports.contains_key(module) {
+            format!(
+                "<a href=\"{}\"><code>{}</code></a>",
+                config.docs.layout.module_link(module),
+                escape_html_content(module)
+            )
+        } else {
+            format!("<code>{}</code>", escape_html_content(module))
+        }
+    };
+
+    let list = |title: &str, modules: &[&str]| {
+        if modules.is_empty() {
+            return String::new();
+        }
+        let items = modules
+            .iter()
+            .map(|module| format!("<li>{}</li>\n", link(module)))
+            .join("");
+        format!("<h3>{title}</h3>\n<ul class=\"symbols-list\">\n{items}</ul>\n")
+    };
+
+    let mut content = String::from("<h1>Dependencies</h1>\n");
+
+    for (module, imported) in imports.iter() {
+        content.push_str(&format!(
+            "<h2 id=\"{}\">{}</h2>\n",
+            escape_html_content(module),
+            link(module)
+        ));
+        content.push_str(&list(
+            "Imports",
+            &imported.iter().map(String::as_str).collect_vec(),
+        ));
+        content.push_str(&list(
+            "Imported by",
+            imported_by
+                .get(module)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        ));
+    }
+
+    let template = PageTemplate::new(
+        config,
+        modules,
+        unstable_modules,
+        source,
+        timestamp,
+        "dependencies.html",
+        "Dependencies",
+        content,
+    );
+
+    render_page(path, &template).map(Some)
+}
+
+/// A static listing of every entry of the search index, grouped by module. It stands in for the
+/// search bar when scripts are disabled, and gives crawlers a page linking to every definition.
+fn generate_search_page(
+    config: &Config,
+    modules: &[DocLink],
+    unstable_modules: &[DocLink],
+    source: &DocLink,
+    timestamp: &Duration,
+    search_indexes: &[SearchIndex],
+) -> Result<DocFile, DocFailure> {
+    let path = PathBuf::from("search.html");
+
+    let mut content = String::from("<h1>Search</h1>\n");
+
+    for (module, entries) in &search_indexes.iter().group_by(|entry| &entry.doc) {
+        content.push_str(&format!(
+            "<h2><a href=\"{}\">{}</a></h2>\n<table class=\"search-table\">\n",
+            config.docs.layout.module_link(module),
+            escape_html_content(module)
+        ));
+        for entry in entries.filter(|entry| entry.url.contains('#')) {
+            let notes = [(entry.private, "private"), (entry.deprecated, "deprecated")]
+                .into_iter()
+                .filter_map(|(is_set, note)| is_set.then_some(note))
+                .join(", ");
+            content.push_str(&format!(
+                "<tr><td><a href=\"{}\"><code>{}</code></a></td><td>{notes}</td></tr>\n",
+                entry.url,
+                escape_html_content(&entry.title),
+            ));
+        }
+        content.push_str("</table>\n");
+    }
+
+    let template = PageTemplate::new(
+        config,
+        modules,
+        unstable_modules,
+        source,
+        timestamp,
+        "search.html",
+        "Search",
+        content,
+    );
+
+    render_page(path, &template)
+}
+
+/// Render a page which isn't the page of a module, telling what went wrong otherwise.
+fn render_page(path: PathBuf, template: &PageTemplate) -> Result<DocFile, DocFailure> {
+    match template.render() {
+        Ok(content) => Ok(DocFile { path, content }),
+        Err(error) => Err(DocFailure {
+            module: path.to_string_lossy().to_string(),
+            path,
+            reason: error.to_string(),
+        }),
+    }
+}
+
+fn generate_modules_links(
+    modules: &[&CheckedModule],
+    with_summaries: bool,
+    include_private: bool,
+    layout: DocLayout,
+) -> Vec<DocLink> {
+    let non_empty_modules = modules
+        .iter()
+        .filter(|module| {
+            !module.skip_doc_generation()
+                && module
+                    .ast
+                    .definitions
+                    .iter()
+                    .any(|def| is_documented(def, include_private))
+        })
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .collect_vec();
+
+    let mut links = link_tree::LinkTree::default();
+
+    for module in non_empty_modules.iter() {
+        links.insert(module.name.as_str());
+    }
+
+    // The tree links to flat pages, named after their module.
+    let mut links = links.to_vec();
+    for link in links.iter_mut().filter(|link| !link.is_separator()) {
+        link.path = layout.module_link(link.path.trim_end_matches(".html"));
+    }
+
+    if with_summaries {
+        for link in links.iter_mut().filter(|link| !link.is_separator()) {
+            if let Some(module) = non_empty_modules
+                .iter()
+                .find(|module| link.path == layout.module_link(&module.name))
+            {
+                link.summary = module_summary(module);
+            }
+        }
+    }
+
+    links
+}
+
+/// Whether a definition shows in the documentation: validators always do, while other
+/// definitions must be public, unless private ones are explicitly included. Either way,
+/// definitions marked as '@hidden' never show.
+fn is_documented(def: &TypedDefinition, include_private: bool) -> bool {
+    match def {
+        Definition::Fn(Function { public, doc, .. })
+        | Definition::DataType(DataType { public, doc, .. })
+        | Definition::TypeAlias(TypeAlias { public, doc, .. })
+        | Definition::ModuleConstant(ModuleConstant { public, doc, .. }) => {
+            (*public || include_private) && !is_hidden(doc.as_deref())
+        }
+        Definition::Validator(Validator { doc, .. }) => !is_hidden(doc.as_deref()),
+        _ => false,
+    }
+}
+
+/// Whether some documentation holds a '@hidden' marker on a line of its own, for definitions
+/// which must be public (e.g. to be used by other modules of the project) but aren't meant for
+/// library consumers.
+fn is_hidden(doc: Option<&str>) -> bool {
+    doc.map_or(false, |doc| {
+        doc.lines().any(|line| line.trim() == "@hidden")
+    })
+}
+
+/// Declared range of compatible compiler versions, or an empty string when unspecified.
+fn compatible_compiler(config: &Config) -> String {
+    config
+        .docs
+        .compatible_compiler
+        .as_ref()
+        .map(|range| range.to_string())
+        .unwrap_or_default()
+}
+
+/// Structured data (JSON-LD) describing a documentation page and the documented package, for
+/// search engines. Empty unless enabled.
+fn json_ld(config: &Config, page_title: &str, path: &str, source: &DocLink) -> String {
+    if !config.docs.json_ld {
+        return String::new();
+    }
+
+    let mut package = json::json!({
+        "@type": "SoftwareSourceCode",
+        "name": config.name.to_string(),
+        "version": config.version,
+        "description": config.description,
+        "programmingLanguage": "Aiken",
+    });
+
+    if !source.is_empty() {
+        package["codeRepository"] = json::Value::String(source.path.clone());
+    }
+
+    if let Some(license) = &config.license {
+        package["license"] = json::Value::String(license.clone());
+    }
+
+    let mut page = json::json!({
+        "@context": "https://schema.org",
+        "@type": "TechArticle",
+        "headline": page_title,
+        "about": package,
+    });
+
+    if let Some(url) = config.docs.absolute_url(path) {
+        page["url"] = json::Value::String(url);
+    }
+
+    // Prevent any '</script>' in user-provided values from closing the enclosing tag early.
+    json::to_string(&page)
+        .expect("JSON-LD serialization")
+        .replace("</", "<\\/")
+}
+
+/// Maximum length, in characters, of module summaries shown in the sidebar.
+const MAX_SUMMARY_LENGTH: usize = 80;
+
+/// Maximum length, in characters, of page descriptions, as shown in link previews.
+const MAX_DESCRIPTION_LENGTH: usize = 160;
+
+/// A one-line, plain-text summary of a module: the first sentence of its documentation,
+/// truncated when too long. Empty when the module has no documentation.
+fn module_summary(module: &CheckedModule) -> String {
+    summary(&module_documentation(module))
+}
+
+/// The first sentence of some markdown documentation, as plain text and truncated when too long.
+fn summary(documentation: &str) -> String {
+    let paragraph = first_paragraph(documentation);
+
+    let sentence = match paragraph.find(". ") {
+        Some(end) => &paragraph[..=end],
+        None => paragraph.as_str(),
+    };
+
+    truncate(sentence.trim(), MAX_SUMMARY_LENGTH)
+}
+
+/// A plain-text description of a page, for search engines and link previews: the first
+/// paragraph of its markdown content, or else that of the project.
+fn page_description(config: &Config, content: &str) -> String {
+    let paragraph = first_paragraph(content);
+
+    let description = if paragraph.is_empty() {
+        config.description.trim()
+    } else {
+        paragraph.trim()
+    };
+
+    truncate(description, MAX_DESCRIPTION_LENGTH)
+}
+
+/// The text of the first paragraph of some markdown, stripped of any formatting.
+fn first_paragraph(text: &str) -> String {
+    let mut paragraph = String::new();
+    let mut in_paragraph = false;
+
+    for event in markdown::Parser::new(text) {
+        match event {
+            markdown::Event::Start(markdown::Tag::Paragraph) => in_paragraph = true,
+            markdown::Event::Text(text) | markdown::Event::Code(text) if in_paragraph => {
+                paragraph.push_str(&text)
+            }
+            markdown::Event::SoftBreak | markdown::Event::HardBreak if in_paragraph => {
+                paragraph.push(' ')
+            }
+            markdown::Event::End(markdown::TagEnd::Paragraph) if !paragraph.is_empty() => break,
+            markdown::Event::End(markdown::TagEnd::Paragraph) => in_paragraph = false,
+            _ => (),
+        }
+    }
+
+    paragraph
+}
+
+/// Some text, cut with an ellipsis when longer than the given number of characters.
+fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() > max_length {
+        let truncated: String = text.chars().take(max_length - 1).collect();
+        format!("{}…", truncated.trim_end())
+    } else {
+        text.to_string()
+    }
+}
+
+/// Compute the anchor of a module item or section heading. Module pages themselves are found
+/// after their name (see 'DocLayout'), module names being lowercase paths already.
+fn to_anchor(anchors: DocAnchors, name: &str) -> String {
+    match anchors {
+        DocAnchors::Names => name.to_string(),
+        DocAnchors::Slugs => name
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                '-' | '_' => Some(c),
+                _ if c.is_alphanumeric() => Some(c),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/// Anchor of a type of the given module. Types and values have separate namespaces, so their
+/// anchors may collide once turned into slugs (e.g. a type 'Outcome' and a function 'outcome');
+/// the type's anchor then gets a '-type' suffix, which no value's name can have.
+fn to_type_anchor(anchors: DocAnchors, module: &CheckedModule, name: &str) -> String {
+    let anchor = to_anchor(anchors, name);
+
+    let collides = module.ast.definitions().any(|def| match def {
+        Definition::Fn(Function { name, .. })
+        | Definition::ModuleConstant(ModuleConstant { name, .. })
+        | Definition::Validator(Validator { name, .. }) => to_anchor(anchors, name) == anchor,
+        _ => false,
+    });
+
+    if collides {
+        format!("{anchor}-type")
+    } else {
+        anchor
+    }
+}
+
+/// Module documentation, without the stability marker which is only meant for the sidebar.
+fn module_documentation(module: &CheckedModule) -> String {
+    module
+        .ast
+        .docs
+        .iter()
+        .filter(|line| line.trim() != "@unstable")
+        .join("\n")
+}
+
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+struct SearchIndex {
+    doc: String,
+    title: String,
+    content: String,
+    /// First sentence of the documentation, as plain text, previewed in search results.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    summary: String,
+    url: String,
+    /// Set on definitions which aren't exported by their module, so that they can be told apart.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    private: bool,
+    /// Set on deprecated definitions, so that they rank after the others.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    deprecated: bool,
+    /// Version which introduced the definition, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
+    /// Version which deprecated the definition, if its deprecation message starts with one. Only
+    /// the changelog makes use of it.
+    #[serde(skip)]
+    deprecated_in: Option<String>,
+}
+
+impl SearchIndex {
+    fn from_function(module: &CheckedModule, page: &str, function: &DocFunction) -> Self {
+        SearchIndex {
+            doc: module.name.to_string(),
+            title: function.name.to_string(),
+            content: format!("{}\n{}", function.signature, function.raw_documentation),
+            summary: summary(&function.raw_documentation),
+            url: format!(
+                "{page}#{}",
+                function.permalink.as_ref().unwrap_or(&function.anchor)
+            ),
+            private: function.private,
+            deprecated: function.deprecated.is_some(),
+            since: function.since.clone(),
+            deprecated_in: deprecation_version(function.deprecated.as_deref()),
+        }
+    }
+
+    /// Entries for a type and, unless it's a mere record, for each of its constructors so that
+    /// search can lead straight to them.
+    fn from_type(module: &CheckedModule, page: &str, type_info: &DocType) -> Vec<Self> {
+        let constructors = type_info
+            .constructors
+            .iter()
+            .map(|constructor| {
+                format!(
+                    "{}\n{}",
+                    constructor.definition, constructor.raw_documentation
+                )
+            })
+            .join("\n");
+
+        let mut entries = vec![SearchIndex {
+            doc: module.name.to_string(),
+            title: type_info.name.to_string(),
+            content: format!(
+                "{}\n{}\n{}",
+                type_info.definition, type_info.raw_documentation, constructors,
+            ),
+            summary: summary(&type_info.raw_documentation),
+            url: format!(
+                "{page}#{}",
+                type_info.permalink.as_ref().unwrap_or(&type_info.anchor)
+            ),
+            private: type_info.private,
+            deprecated: type_info.deprecated.is_some(),
+            since: type_info.since.clone(),
+            deprecated_in: deprecation_version(type_info.deprecated.as_deref()),
+        }];
+
+        if type_info.constructors.len() > 1 {
+            entries.extend(
+                type_info
+                    .constructors
+                    .iter()
+                    .map(|constructor| SearchIndex {
+                        doc: module.name.to_string(),
+                        title: format!("{}.{}", type_info.name, constructor.name),
+                        content: format!(
+                            "{}\n{}",
+                            constructor.definition, constructor.raw_documentation
+                        ),
+                        summary: summary(&constructor.raw_documentation),
+                        url: format!("{page}#{}", constructor.anchor),
+                        private: type_info.private,
+                        deprecated: type_info.deprecated.is_some(),
+                        since: type_info.since.clone(),
+                        deprecated_in: deprecation_version(type_info.deprecated.as_deref()),
+                    }),
+            );
+        }
+
+        entries
+    }
+
+    fn from_constant(module: &CheckedModule, page: &str, constant: &DocConstant) -> Self {
+        SearchIndex {
+            doc: module.name.to_string(),
+            title: constant.name.to_string(),
+            content: format!("{}\n{}", constant.definition, constant.raw_documentation),
+            summary: summary(&constant.raw_documentation),
+            url: format!(
+                "{page}#{}",
+                constant.permalink.as_ref().unwrap_or(&constant.anchor)
+            ),
+            private: constant.private,
+            deprecated: constant.deprecated.is_some(),
+            since: constant.since.clone(),
+            deprecated_in: deprecation_version(constant.deprecated.as_deref()),
+        }
+    }
+
+    /// Entries for a validator and each of its handlers.
+    fn from_validator(module: &CheckedModule, page: &str, validator: &DocValidator) -> Vec<Self> {
+        let mut entries = vec![SearchIndex {
+            doc: module.name.to_string(),
+            title: validator.name.to_string(),
+            content: format!("{}\n{}", validator.signature, validator.raw_documentation),
+            summary: summary(&validator.raw_documentation),
+            url: format!("{page}#{}", validator.anchor),
+            private: false,
+            deprecated: false,
+            since: validator.since.clone(),
+            deprecated_in: None,
+        }];
+
+        entries.extend(validator.handlers.iter().map(|handler| SearchIndex {
+            doc: module.name.to_string(),
+            title: format!("{}.{}", validator.name, handler.name),
+            content: format!("{}\n{}", handler.signature, handler.raw_documentation),
+            summary: summary(&handler.raw_documentation),
+            url: format!("{page}#{}", handler.anchor),
+            private: false,
+            deprecated: false,
+            since: validator.since.clone(),
+            deprecated_in: None,
+        }));
+
+        entries
+    }
+
+    fn from_module(module: &CheckedModule, page: &str) -> Self {
+        SearchIndex {
+            doc: module.name.to_string(),
+            title: module.name.to_string(),
+            content: module_documentation(module),
+            summary: module_summary(module),
+            url: page.to_string(),
+            private: false,
+            deprecated: false,
+            since: None,
+            deprecated_in: None,
+        }
+    }
+}
+
+/// Structured documentation of a single module, serialized next to its HTML page when a
+/// JSON output is requested.
+#[derive(Serialize)]
+struct DocModule<'a> {
+    name: &'a str,
+    documentation: String,
+    functions: Vec<&'a DocFunction>,
+    types: &'a [DocType],
+    constants: &'a [DocConstant],
+    validators: &'a [DocValidator],
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Interspersed {
+    Section(DocSection),
+    Function(Box<DocFunction>),
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+struct DocSection {
+    heading: usize,
+    title: String,
+    anchor: String,
+}
+
+/// A kind of members in the table of contents of a module page, along with the members
+/// themselves.
+#[derive(Debug)]
+struct DocTocSection {
+    title: &'static str,
+    anchor: &'static str,
+    entries: Vec<DocTocEntry>,
+}
+
+#[derive(Debug)]
+struct DocTocEntry {
+    name: String,
+    anchor: String,
+}
+
+impl DocTocEntry {
+    fn new(name: &str, anchor: &str) -> Self {
+        DocTocEntry {
+            name: name.to_string(),
+            anchor: anchor.to_string(),
+        }
+    }
+}
+
+/// Sections of the table of contents of a module page, in the same order as on the page; kinds
+/// of members the module has none of are left out.
+fn table_of_contents(
+    members_order: &[DocMembers],
+    functions: &[Interspersed],
+    types: &[DocType],
+    constants: &[DocConstant],
+    validators: &[DocValidator],
+) -> Vec<DocTocSection> {
+    members_order
+        .iter()
+        .map(|members| match members {
+            DocMembers::Validators => DocTocSection {
+                title: "Validators",
+                anchor: "module-validators",
+                entries: validators
+                    .iter()
+                    .map(|validator| DocTocEntry::new(&validator.name, &validator.anchor))
+                    .collect(),
+            },
+            DocMembers::Types => DocTocSection {
+                title: "Types",
+                anchor: "module-types",
+                entries: types
+                    .iter()
+                    .map(|type_info| DocTocEntry::new(&type_info.name, &type_info.anchor))
+                    .collect(),
+            },
+            DocMembers::Constants => DocTocSection {
+                title: "Constants",
+                anchor: "module-constants",
+                entries: constants
+                    .iter()
+                    .map(|constant| DocTocEntry::new(&constant.name, &constant.anchor))
+                    .collect(),
+            },
+            DocMembers::Functions => DocTocSection {
+                title: "Functions",
+                anchor: "module-functions",
+                entries: functions
+                    .iter()
+                    .filter_map(|item| match item {
+                        Interspersed::Function(function) => {
+                            Some(DocTocEntry::new(&function.name, &function.anchor))
+                        }
+                        Interspersed::Section(..) => None,
+                    })
+                    .collect(),
+            },
+        })
+        .filter(|section| !section.entries.is_empty())
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct DocFunction {
+    name: String,
+    anchor: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    private: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated: Option<String>,
+    /// Version which introduced the function, given by a '@since' marker.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
+    /// Secondary anchor, given by an '@anchor' marker, which remains as the definition gets
+    /// renamed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permalink: Option<String>,
+    /// Title of the section the function is grouped under, given by a '@section' marker.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    section: Option<String>,
+    signature: String,
+    /// The signature as HTML, with links to the documentation of the types it mentions.
+    #[serde(skip)]
+    signature_html: String,
+    #[serde(skip)]
+    documentation: String,
+    raw_documentation: String,
+    source_url: String,
+    arguments: Vec<DocArgument>,
+    #[serde(skip)]
+    related: Vec<DocLink>,
+}
+
+impl DocFunction {
+    fn from_definition(
+        def: &TypedDefinition,
+        source_linker: &source_links::SourceLinker,
+        types: &type_links::TypeIndex,
+        renderer: &Renderer,
+        module: &str,
+        anchors: DocAnchors,
+        max_columns: isize,
+    ) -> Option<(Span, Self)> {
+        match def {
+            Definition::Fn(func_def) => {
+                let signature = format::Formatter::new()
+                    .docs_fn_signature(
+                        &func_def.name,
+                        &func_def.arguments,
+                        &func_def.return_annotation,
+                        func_def.return_type.clone(),
+                        max_columns,
+                    )
+                    .to_pretty_string(max_columns);
+
+                let signature_html = types.link_signature(
+                    &signature,
+                    func_def
+                        .arguments
+                        .iter()
+                        .map(|arg| arg.tipo.as_ref())
+                        .chain([func_def.return_type.as_ref()]),
+                    module,
+                );
+
+                let (raw_documentation, section) =
+                    split_marker(func_def.doc.as_deref(), "@section");
+                let (raw_documentation, since) = split_since(Some(&raw_documentation));
+                let (raw_documentation, permalink) = split_permalink(Some(&raw_documentation));
+                let (raw_documentation, deprecated) = split_deprecation(Some(&raw_documentation));
+
+                let anchor = to_anchor(anchors, &func_def.name);
+
+                Some((
+                    func_def.location,
+                    DocFunction {
+                        name: func_def.name.clone(),
+                        anchor: anchor.clone(),
+                        private: !func_def.public,
+                        documentation: render_markdown(&raw_documentation, &anchor, renderer),
+                        raw_documentation,
+                        deprecated,
+                        since,
+                        permalink,
+                        section,
+                        signature,
+                        signature_html,
+                        source_url: source_linker
+                            .url(func_def.location.map_end(|_| func_def.end_position)),
+                        arguments: func_def
+                            .arguments
+                            .iter()
+                            .map(|arg| DocArgument::from_arg(arg, &anchor, renderer))
+                            .collect(),
+                        related: vec![],
+                    },
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct DocValidator {
+    name: String,
+    anchor: String,
+    signature: String,
+    #[serde(skip)]
+    documentation: String,
+    raw_documentation: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
+    source_url: String,
+    parameters: Vec<DocArgument>,
+    handlers: Vec<DocHandler>,
+}
+
+impl DocValidator {
+    fn from_definition(
+        def: &TypedDefinition,
+        source_linker: &source_links::SourceLinker,
+        renderer: &Renderer,
+        anchors: DocAnchors,
+        max_columns: isize,
+    ) -> Option<Self> {
+        let Definition::Validator(validator) = def else {
+            return None;
+        };
+
+        let anchor = to_anchor(anchors, &validator.name);
+
+        // The fallback is only worth documenting when explicitly defined; by default, it merely
+        // fails on any other purpose.
+        let fallback = Some(&validator.fallback)
+            .filter(|fallback| !matches!(fallback.body, TypedExpr::ErrorTerm { .. }));
+
+        let signature = if validator.params.is_empty() {
+            format!("validator {}", validator.name)
+        } else {
+            format!(
+                "validator {}{}",
+                validator.name,
+                format::Formatter::new()
+                    .docs_fn_args(&validator.params)
+                    .to_pretty_string(max_columns)
+            )
+        };
+
+        let (raw_documentation, since) = split_since(validator.doc.as_deref());
+
+        Some(DocValidator {
+            name: validator.name.clone(),
+            signature,
+            documentation: render_markdown(&raw_documentation, &anchor, renderer),
+            raw_documentation,
+            since,
+            source_url: source_linker.url(validator.location.map_end(|_| validator.end_position)),
+            parameters: validator
+                .params
+                .iter()
+                .map(|arg| DocArgument::from_arg(arg, &anchor, renderer))
+                .collect(),
+            handlers: validator
+                .handlers
+                .iter()
+                .chain(fallback)
+                .map(|handler| {
+                    DocHandler::from_function(
+                        handler,
+                        &anchor,
+                        source_linker,
+                        renderer,
+                        max_columns,
+                    )
+                })
+                .collect(),
+            anchor,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct DocHandler {
+    name: String,
+    anchor: String,
+    signature: String,
+    #[serde(skip)]
+    documentation: String,
+    raw_documentation: String,
+    source_url: String,
+}
+
+impl DocHandler {
+    fn from_function(
+        handler: &TypedFunction,
+        validator_anchor: &str,
+        source_linker: &source_links::SourceLinker,
+        renderer: &Renderer,
+        max_columns: isize,
+    ) -> Self {
+        let anchor = format!("{validator_anchor}.{}", handler.name);
+
+        DocHandler {
+            name: handler.name.clone(),
+            signature: format::Formatter::new()
+                .docs_fn_signature(
+                    &handler.name,
+                    &handler.arguments,
+                    &handler.return_annotation,
+                    handler.return_type.clone(),
+                    max_columns,
+                )
+                .to_pretty_string(max_columns),
+            documentation: handler
+                .doc
+                .as_deref()
+                .map(|doc| render_markdown(doc, &anchor, renderer))
+                .unwrap_or_default(),
+            raw_documentation: handler.doc.as_deref().unwrap_or_default().to_string(),
+            source_url: source_linker.url(handler.location.map_end(|_| handler.end_position)),
+            anchor,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct DocArgument {
+    label: String,
+    tipo: String,
+    #[serde(skip)]
+    documentation: String,
+    raw_documentation: String,
+}
+
+impl DocArgument {
+    fn from_arg(arg: &TypedArg, function_anchor: &str, renderer: &Renderer) -> Self {
+        let label = arg.arg_name.get_label();
+        let anchor = format!("{function_anchor}.{label}");
+
+        DocArgument {
+            documentation: arg
+                .doc
+                .as_deref()
+                .map(|doc| render_markdown(doc, &anchor, renderer))
+                .unwrap_or_default(),
+            label,
+            tipo: format::Formatter::new()
+                .docs_fn_arg_type(arg)
+                .to_pretty_string(MAX_COLUMNS),
+            raw_documentation: arg.doc.as_deref().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct DocConstant {
+    name: String,
+    anchor: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    private: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
+    /// Secondary anchor, given by an '@anchor' marker, which remains as the definition gets
+    /// renamed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permalink: Option<String>,
+    definition: String,
+    /// Type of the constant, as inferred from its value.
+    type_signature: String,
+    #[serde(skip)]
+    documentation: String,
+    raw_documentation: String,
+    source_url: String,
+}
+
+impl DocConstant {
+    fn from_definition(
+        def: &TypedDefinition,
+        source_linker: &source_links::SourceLinker,
+        renderer: &Renderer,
+        anchors: DocAnchors,
+        max_columns: isize,
+    ) -> Option<Self> {
+        match def {
+            Definition::ModuleConstant(const_def) => {
+                let (raw_documentation, since) = split_since(const_def.doc.as_deref());
+                let (raw_documentation, permalink) = split_permalink(Some(&raw_documentation));
+                let (raw_documentation, deprecated) = split_deprecation(Some(&raw_documentation));
+
+                let anchor = to_anchor(anchors, &const_def.name);
+
+                Some(DocConstant {
+                    name: const_def.name.clone(),
+                    private: !const_def.public,
+                    documentation: render_markdown(&raw_documentation, &anchor, renderer),
+                    anchor,
+                    raw_documentation,
+                    deprecated,
+                    since,
+                    permalink,
+                    definition: format::Formatter::new()
+                        .docs_const_expr(&const_def.name, &const_def.value)
+                        .to_pretty_string(max_columns),
+                    type_signature: const_def.value.tipo().to_pretty(0),
+                    source_url: source_linker.url(const_def.location),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+struct DocType {
+    name: String,
+    anchor: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    private: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
+    /// Secondary anchor, given by an '@anchor' marker, which remains as the definition gets
+    /// renamed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permalink: Option<String>,
+    definition: String,
+    #[serde(skip)]
+    documentation: String,
+    raw_documentation: String,
+    constructors: Vec<DocTypeConstructor>,
+    parameters: Vec<String>,
+    opaque: bool,
+    source_url: String,
+}
+
+impl DocType {
+    fn from_definition(
+        def: &TypedDefinition,
+        module: &CheckedModule,
+        source_linker: &source_links::SourceLinker,
+        renderer: &Renderer,
+        anchors: DocAnchors,
+        max_columns: isize,
+    ) -> Option<Self> {
+        let (raw_documentation, since) = match def {
+            Definition::TypeAlias(TypeAlias { doc, .. })
+            | Definition::DataType(DataType { doc, .. }) => split_since(doc.as_deref()),
+            _ => return None,
+        };
+        let (raw_documentation, permalink) = split_permalink(Some(&raw_documentation));
+        let (raw_documentation, deprecated) = split_deprecation(Some(&raw_documentation));
+
+        match def {
+            Definition::TypeAlias(info) => Some(DocType {
+                name: info.alias.clone(),
+                private: !info.public,
+                anchor: to_type_anchor(anchors, module, &info.alias),
+                definition: format::Formatter::new()
+                    .docs_type_alias(&info.alias, &info.parameters, &info.annotation)
+                    .to_pretty_string(max_columns),
+                documentation: render_markdown(
+                    &raw_documentation,
+                    &to_type_anchor(anchors, module, &info.alias),
+                    renderer,
+                ),
+                raw_documentation,
+                deprecated,
+                since,
+                permalink,
+                constructors: vec![],
+                parameters: info.parameters.clone(),
+                opaque: false,
+                source_url: source_linker.url(info.location),
+            }),
+
+            Definition::DataType(info) if !info.opaque => Some(DocType {
+                name: info.name.clone(),
+                private: !info.public,
+                anchor: to_type_anchor(anchors, module, &info.name),
+                definition: format::Formatter::new()
+                    .docs_data_type(
+                        &info.name,
+                        &info.parameters,
+                        &info.constructors,
+                        &info.location,
+                    )
+                    .to_pretty_string(max_columns),
+                documentation: render_markdown(
+                    &raw_documentation,
+                    &to_type_anchor(anchors, module, &info.name),
+                    renderer,
+                ),
+                raw_documentation,
+                deprecated,
+                since,
+                permalink,
+                constructors: info
+                    .constructors
+                    .iter()
+                    .map(|constructor| {
+                        DocTypeConstructor::from_record_constructor(
+                            constructor,
+                            &to_type_anchor(anchors, module, &info.name),
+                            renderer,
+                            anchors,
+                            max_columns,
+                        )
+                    })
+                    .collect(),
+                parameters: info.parameters.clone(),
+                opaque: info.opaque,
+                source_url: source_linker.url(info.location),
+            }),
+
+            Definition::DataType(info) if info.opaque => Some(DocType {
+                name: info.name.clone(),
+                private: !info.public,
+                anchor: to_type_anchor(anchors, module, &info.name),
+                definition: format::Formatter::new()
+                    .docs_opaque_data_type(&info.name, &info.parameters, &info.location)
+                    .to_pretty_string(max_columns),
+                documentation: render_markdown(
+                    &raw_documentation,
+                    &to_type_anchor(anchors, module, &info.name),
+                    renderer,
+                ),
+                raw_documentation,
+                deprecated,
+                since,
+                permalink,
+                constructors: vec![],
+                parameters: info.parameters.clone(),
+                opaque: info.opaque,
+                source_url: source_linker.url(info.location),
+            }),
+
+            _ => None,
+        }
+    }
+
+    /// Whether there are too many constructors to list them all upfront.
+    fn collapses_constructors(&self) -> bool {
+        self.constructors.len() > MAX_VISIBLE_CONSTRUCTORS
+    }
+}
+
+/// Constructors shown by default, when listing those of a type.
+const MAX_VISIBLE_CONSTRUCTORS: usize = 10;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+struct DocTypeConstructor {
+    name: String,
+    anchor: String,
+    definition: String,
+    #[serde(skip)]
+    documentation: String,
+    raw_documentation: String,
+    fields: Vec<DocField>,
+}
+
+impl DocTypeConstructor {
+    fn from_record_constructor(
+        constructor: &RecordConstructor<Rc<Type>>,
+        type_anchor: &str,
+        renderer: &Renderer,
+        anchors: DocAnchors,
+        max_columns: isize,
+    ) -> Self {
+        let anchor = format!("{type_anchor}.{}", to_anchor(anchors, &constructor.name));
+
+        DocTypeConstructor {
+            name: constructor.name.clone(),
+            definition: format::Formatter::new()
+                .docs_record_constructor(constructor)
+                .to_pretty_string(max_columns),
+            documentation: constructor
+                .doc
+                .as_deref()
+                .map(|doc| render_markdown(doc, &anchor, renderer))
+                .unwrap_or_default(),
+            raw_documentation: constructor.doc.as_deref().unwrap_or_default().to_string(),
+            fields: constructor
+                .arguments
+                .iter()
+                .filter_map(|arg| match (arg.label.as_deref(), arg.doc.as_deref()) {
+                    (Some(label), Some(doc)) => {
+                        let anchor = format!("{anchor}.{}", to_anchor(anchors, label));
+                        Some(DocField {
+                            label: label.to_string(),
+                            documentation: render_markdown(doc, &anchor, renderer),
+                            raw_documentation: doc.to_string(),
+                            anchor,
+                        })
+                    }
+                    _ => None,
+                })
+                .collect(),
+            anchor,
+        }
+    }
+}
+
+/// A documented, labeled, field of a record constructor.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct DocField {
+    label: String,
+    anchor: String,
+    #[serde(skip)]
+    documentation: String,
+    raw_documentation: String,
+}
+
+// ------ Extra Helpers
+
+/// Render some markdown as HTML. Shortcut references which aren't otherwise defined, such as
+/// '[Credential]' or '[list.map]', are resolved as intra-doc links and left as-is when they
+/// point at nothing known. Code blocks tagged with a language come with a button to copy them.
+/// Footnotes are identified after the anchor of what's documented, as a page holds the
+/// documentation of many definitions.
+fn render_markdown(text: &str, anchor: &str, renderer: &Renderer) -> String {
+    use markdown::{CodeBlockKind, Event, Tag, TagEnd};
+
+    let mut s = String::with_capacity(text.len() * 3 / 2);
+    let mut in_copyable_block = false;
+    // Source of the Aiken code block being read, when highlighting it statically.
+    let mut aiken_block: Option<String> = None;
+    // Footnotes, numbered in order of appearance alongside how many times they're referenced,
+    // and the one being defined if any.
+    let mut footnotes: Vec<(String, usize)> = vec![];
+    let mut footnote: Option<(String, usize)> = None;
+    let mut footnote_number = |label: &str, is_reference: bool| {
+        let ix = match footnotes.iter().position(|(other, _)| other == label) {
+            Some(ix) => ix,
+            None => {
+                footnotes.push((label.to_string(), 0));
+                footnotes.len() - 1
+            }
+        };
+        if is_reference {
+            footnotes[ix].1 += 1;
+        }
+        (ix + 1, footnotes[ix].1)
+    };
+    let footnote_id = |label: &str| {
+        let label = to_anchor(DocAnchors::Slugs, label);
+        if anchor.is_empty() {
+            label
+        } else {
+            format!("{anchor}-{label}")
+        }
+    };
+    let p = markdown::Parser::new_with_broken_link_callback(
+        text,
+        markdown::Options::all(),
+        Some(|link: markdown::BrokenLink<'_>| {
+            renderer
+                .links
+                .resolve(&link.reference)
+                .map(|url| (url.into(), "".into()))
+        }),
+    )
+    .flat_map(|event| match event {
+        Event::Start(Tag::CodeBlock(ref kind)) => {
+            let lang = match kind {
+                CodeBlockKind::Fenced(lang) => lang.split_whitespace().next().unwrap_or_default(),
+                CodeBlockKind::Indented => "",
+            };
+            let copyable = !lang.is_empty() && !renderer.plain;
+
+            // Code blocks without a language are taken as Aiken, as they are client-side.
+            let start = if renderer.highlighting == DocHighlighting::Static
+                && (lang.is_empty() || lang == "aiken")
+            {
+                aiken_block = Some(String::new());
+                Event::Html("<pre><code class=\"language-aiken hljs\">".into())
+            } else {
+                event
+            };
+
+            if copyable {
+                in_copyable_block = true;
+                vec![Event::Html(COPYABLE_CODE_BLOCK_START.into()), start]
+            } else {
+                vec![start]
+            }
+        }
+        Event::Text(ref code) if aiken_block.is_some() => {
+            if let Some(source) = aiken_block.as_mut() {
+                source.push_str(code);
+            }
+            vec![]
+        }
+        Event::End(TagEnd::CodeBlock) => {
+            let mut events = match aiken_block.take() {
+                Some(source) => vec![
+                    Event::Html(
+                        highlight::aiken(&source)
+                            .unwrap_or_else(|| escape_html_content(&source))
+                            .into(),
+                    ),
+                    Event::Html("</code></pre>\n".into()),
+                ],
+                None => vec![event],
+            };
+
+            if in_copyable_block {
+                in_copyable_block = false;
+                events.push(Event::Html("</div>".into()));
+            }
+
+            events
+        }
+        // Footnotes link back to where they're referenced from.
+        // Repeated references to a footnote are numbered, so that each has its own id; the
+        // footnote links back to the first one.
+        Event::FootnoteReference(ref label) => {
+            let id = footnote_id(label);
+            let (number, occurrence) = footnote_number(label, true);
+            let reference_id = match occurrence {
+                1 => format!("fnref-{id}"),
+                _ => format!("fnref-{id}-{occurrence}"),
+            };
+            vec![Event::Html(
+                format!(
+                    "<sup class=\"footnote-reference\" id=\"{reference_id}\"><a href=\"#fn-{id}\">{number}</a></sup>"
+                )
+                .into(),
+            )]
+        }
+        Event::Start(Tag::FootnoteDefinition(ref label)) => {
+            let id = footnote_id(label);
+            let (number, _) = footnote_number(label, false);
+            let html = format!(
+                "<div class=\"footnote-definition\" id=\"fn-{id}\"><sup class=\"footnote-definition-label\">{number}</sup>"
+            );
+            footnote = Some((id, number));
+            vec![Event::Html(html.into())]
+        }
+        Event::End(TagEnd::FootnoteDefinition) => match footnote.take() {
+            Some((id, number)) => vec![Event::Html(
+                format!(
+                    "<a class=\"footnote-backref\" href=\"#fnref-{id}\" title=\"Back to reference {number}\" aria-label=\"Back to reference {number}\">↩</a></div>\n"
+                )
+                .into(),
+            )],
+            None => vec![event],
+        },
+        Event::TaskListMarker(checked) => vec![Event::Html(
+            format!(
+                "<input class=\"task-list-item-checkbox\" type=\"checkbox\" disabled{}/>",
+                if checked { " checked" } else { "" }
+            )
+            .into(),
+        )],
+        _ => vec![event],
+    });
+    markdown::html::push_html(&mut s, p);
+    s
+}
+
+/// What rendering markdown depends on, besides the markdown itself.
+struct Renderer<'a> {
+    links: symbols::Resolver<'a>,
+    highlighting: DocHighlighting,
+    /// Leave out the button to copy code blocks, which requires scripts.
+    plain: bool,
+}
+
+const COPYABLE_CODE_BLOCK_START: &str = r##"<div class="code-block"><button class="copy-code" title="Copy to clipboard" aria-label="Copy to clipboard"><svg class="icon icon-copy"><use xlink:href="#icon-copy"></use></svg></button>"##;
+
+/// Split a deprecation notice off some documentation, given either as a leading '@deprecated'
+/// line or as a 'deprecated:' entry of a front-matter block. The notice carries an optional
+/// message, typically pointing at a replacement.
+fn split_deprecation(doc: Option<&str>) -> (String, Option<String>) {
+    let doc = doc.unwrap_or_default();
+    let mut lines = doc.lines().skip_while(|line| line.trim().is_empty());
+
+    match lines.next().map(str::trim) {
+        Some(line) if line.starts_with("@deprecated") => (
+            lines.join("\n"),
+            Some(line.trim_start_matches("@deprecated").trim().to_string()),
+        ),
+        Some("---") => {
+            let front_matter = lines
+                .by_ref()
+                .take_while(|line| line.trim() != "---")
+                .collect_vec();
+            let deprecated = front_matter.iter().find_map(|line| {
+                line.trim()
+                    .strip_prefix("deprecated:")
+                    .map(|message| message.trim().to_string())
+            });
+            if deprecated.is_some() {
+                (lines.join("\n"), deprecated)
+            } else {
+                (doc.to_string(), None)
+            }
+        }
+        _ => (doc.to_string(), None),
+    }
+}
+
+/// Split a marker (e.g. '@section' or '@since') off some documentation, on a line of its own
+/// and followed by a value.
+fn split_marker(doc: Option<&str>, marker: &str) -> (String, Option<String>) {
+    let doc = doc.unwrap_or_default();
+    let mut value = None;
+
+    let rest = doc
+        .lines()
+        .filter(|line| {
+            match line
+                .trim()
+                .strip_prefix(marker)
+                .filter(|rest| rest.starts_with(' '))
+            {
+                Some(rest) if value.is_none() => {
+                    value = Some(rest.trim().to_string());
+                    false
+                }
+                _ => true,
+            }
+        })
+        .join("\n");
+
+    match value {
+        Some(_) => (rest, value),
+        None => (doc.to_string(), None),
+    }
+}
+
+/// Split a '@since' marker off some documentation, giving the version which introduced the
+/// documented definition. Markers which don't hold a version are dropped.
+fn split_since(doc: Option<&str>) -> (String, Option<String>) {
+    let (rest, since) = split_marker(doc, "@since");
+    (rest, since.filter(|since| is_version(since)))
+}
+
+/// Split an '@anchor' marker off some documentation, giving an anchor which stays the same
+/// as the documented definition gets renamed. Markers which aren't made of alphanumerics, '-'
+/// and '_' only are dropped.
+fn split_permalink(doc: Option<&str>) -> (String, Option<String>) {
+    let (rest, anchor) = split_marker(doc, "@anchor");
+    (
+        rest,
+        anchor.filter(|anchor| {
+            anchor
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        }),
+    )
+}
+
+/// Whether some text looks like a version number, with at least a major version and possibly
+/// a 'v' prefix (e.g. '1.2.0', 'v2' or '1.0.0-beta').
+fn is_version(text: &str) -> bool {
+    parse_version(text).is_some()
+}
+
+/// Parse some text looking like a version number (see 'is_version'), completing any missing
+/// minor or patch version with zeros.
+fn parse_version(text: &str) -> Option<semver::Version> {
+    let version = text.strip_prefix('v').unwrap_or(text);
+    let (core, suffix) = version.split_at(version.find(['-', '+']).unwrap_or(version.len()));
+    let padding = match core.matches('.').count() {
+        0 => ".0.0",
+        1 => ".0",
+        _ => "",
+    };
+    semver::Version::parse(&format!("{core}{padding}{suffix}")).ok()
+}
+
+/// Version which deprecated a definition, when its deprecation message starts with one (e.g.
+/// '@deprecated 1.2.0: use `bar` instead', or '@deprecated since v2').
+fn deprecation_version(message: Option<&str>) -> Option<String> {
+    let message = message?;
+    let message = message.strip_prefix("since ").unwrap_or(message);
+    message
+        .split_whitespace()
+        .next()
+        .map(|word| word.trim_end_matches([':', ',', '.', ';']))
+        .filter(|word| is_version(word))
+        .map(str::to_string)
+}
+
+fn escape_html_content(it: &str) -> String {
+    it.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Search indexes with a single entry per definition, identified by its module and title. Should
+/// a definition be indexed more than once under different URLs, the entry pointing at the page
+/// of its own module is kept, at the position of the first one. Entries of modules themselves
+/// are never merged with those of their definitions.
+fn dedup_search_indexes(indexes: Vec<SearchIndex>, layout: DocLayout) -> Vec<SearchIndex> {
+    let is_canonical = |entry: &SearchIndex| {
+        let page = entry.url.split('#').next().unwrap_or_default();
+        page == layout.module_link(&entry.doc)
+    };
+
+    let mut deduped: Vec<SearchIndex> = Vec::with_capacity(indexes.len());
+    let mut positions: BTreeMap<(String, String, bool), usize> = BTreeMap::new();
+
+    for entry in indexes {
+        let key = (
+            entry.doc.clone(),
+            entry.title.clone(),
+            entry.url.contains('#'),
+        );
+        match positions.get(&key) {
+            Some(&ix) => {
+                if !is_canonical(&deduped[ix]) && is_canonical(&entry) {
+                    deduped[ix] = entry;
+                }
+            }
+            None => {
+                positions.insert(key, deduped.len());
+                deduped.push(entry);
+            }
+        }
+    }
+
+    deduped
+}
+
+/// Make search indexes safe to inject in the search results, which are rendered as HTML. Only
+/// the fragment of urls is encoded, since it may contain arbitrary characters (e.g. from
+/// section headings) while the path is derived from module names.
+fn escape_html_contents(indexes: Vec<SearchIndex>) -> Vec<SearchIndex> {
+    fn encode_url_fragment(url: String) -> String {
+        match url.split_once('#') {
+            Some((path, fragment)) => format!(
+                "{path}#{}",
+                askama::filters::urlencode(fragment).unwrap_or_else(|_| fragment.to_string())
+            ),
+            None => url,
+        }
+    }
+
+    indexes
+        .into_iter()
+        .map(|idx| SearchIndex {
+            doc: escape_html_content(&idx.doc),
+            title: escape_html_content(&idx.title),
+            content: escape_html_content(&idx.content),
+            summary: escape_html_content(&idx.summary),
+            url: encode_url_fragment(idx.url),
+            private: idx.private,
+            deprecated: idx.deprecated,
+            since: idx.since.as_deref().map(escape_html_content),
+            deprecated_in: idx.deprecated_in,
+        })
+        .collect::<Vec<SearchIndex>>()
+}
+
+/// Time of the build, as seconds since the UNIX epoch. It honors 'SOURCE_DATE_EPOCH' so that
+/// builds can be reproducible (see https://reproducible-builds.org/specs/source-date-epoch/).
+fn new_timestamp() -> Duration {
+    new_timestamp_from(std::env::var("SOURCE_DATE_EPOCH").ok().as_deref())
+}
+
+/// Time of the build, given a value of 'SOURCE_DATE_EPOCH' if any; falling back to the current
+/// time when it is missing or isn't a number of seconds.
+fn new_timestamp_from(source_date_epoch: Option<&str>) -> Duration {
+    source_date_epoch
+        .and_then(|epoch| epoch.trim().parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("get current timestamp")
+        })
+}
+
+/// Time of the last modification of a module's source file, as seconds since the UNIX epoch.
+/// It defaults to, and never goes past, the time of the build; which keeps builds reproducible.
+fn last_modified(
+    fs: &dyn fs::FileSystem,
+    module: &CheckedModule,
+    timestamp: &Duration,
+) -> Duration {
+    fs.modified(&module.input_path)
+        .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map_or(*timestamp, |modified| modified.min(*timestamp))
+}
+
+/// Calendar date (e.g. '2024-03-14', in UTC) of some time since the UNIX epoch.
+fn to_iso_date(time: &Duration) -> String {
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (time.as_secs() / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Date of the build, from its timestamp as given to templates. Pages show it as is, while
+/// scripts turn it into a time relative to now.
+fn build_date(timestamp: &str) -> String {
+    to_iso_date(&Duration::from_secs(timestamp.parse().unwrap_or_default()))
+}
+
+/// Relative path from a page (or the page of a module) back to the root of the documentation:
+/// one '..' per directory the page sits in, that is, per '/' separator in its path.
+fn to_breadcrumbs(path: &str) -> String {
+    let depth = path.trim_start_matches('/').matches('/').count();
+    if depth == 0 {
+        ".".to_string()
+    } else {
+        vec![".."; depth].join("/")
+    }
+}
+
+#[test]
+fn to_iso_date_test() {
+    assert_eq!(to_iso_date(&Duration::ZERO), "1970-01-01");
+    assert_eq!(to_iso_date(&Duration::from_secs(951_782_400)), "2000-02-29");
+    assert_eq!(
+        to_iso_date(&Duration::from_secs(1_700_000_000)),
+        "2023-11-14"
+    );
+    assert_eq!(
+        to_iso_date(&Duration::from_secs(1_735_689_599)),
+        "2024-12-31"
+    );
+}
+
+#[test]
+fn to_breadcrumbs_test() {
+    // Root
+    assert_eq!(to_breadcrumbs(""), ".");
+    assert_eq!(to_breadcrumbs("index.html"), ".");
+    assert_eq!(to_breadcrumbs("/index.html"), ".");
+
+    // Pages
+    assert_eq!(to_breadcrumbs("a.html"), ".");
+    assert_eq!(to_breadcrumbs("/a.html"), ".");
+    assert_eq!(to_breadcrumbs("/a/b.html"), "..");
+    assert_eq!(to_breadcrumbs("/a/b/c.html"), "../..");
+
+    // Modules
+    assert_eq!(to_breadcrumbs("a"), ".");
+    assert_eq!(to_breadcrumbs("a/b"), "..");
+    assert_eq!(to_breadcrumbs("a/b/c"), "../..");
+    assert_eq!(to_breadcrumbs("aiken/collection/list"), "../..");
+    assert_eq!(to_breadcrumbs("aiken/crypto/bls12_381/g1"), "../../..");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TestProject;
+
+    fn config(extra: &str) -> Config {
+        toml::from_str(&format!(
+            "name = \"test/project\"\nversion = \"1.0.0\"\n{extra}"
+        ))
+        .expect("invalid test config")
+    }
+
+    fn check(source_code: &str) -> CheckedModule {
+        let mut project = TestProject::new();
+        let module = project.parse(source_code);
+        project.check(module)
+    }
+
+    fn generate_files(config: &Config, modules: Vec<&CheckedModule>) -> Vec<DocFile> {
+        generate_all(&fs::NoFileSystem, Path::new(""), config, modules).0
+    }
+
+    fn file_content(files: &[DocFile], path: &str) -> String {
+        files
+            .iter()
+            .find(|file| file.path == Path::new(path))
+            .unwrap_or_else(|| panic!("missing {path}"))
+            .content
+            .clone()
+    }
+
+    fn generate(config: &Config, module: &CheckedModule) -> (Vec<SearchIndex>, Vec<DocFile>) {
+        let source = DocLink {
+            indent: 0,
+            name: String::new(),
+            path: String::new(),
+            summary: String::new(),
+        };
+        let (search_indexes, files, _, _) = generate_module(
+            Path::new(""),
+            config,
+            module,
+            &[],
+            &[],
+            &related::RelatedIndex::default(),
+            &type_links::TypeIndex::default(),
+            &symbols::SymbolIndex::default(),
+            &source,
+            &Duration::ZERO,
+            &Duration::ZERO,
+        )
+        .expect("module page rendering");
+        (search_indexes, files)
+    }
+
+    #[test]
+    fn single_module_page() {
+        let module = check(indoc::indoc! {r#"
+            /// Add one, see [pred].
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+
+            pub fn pred(n: Int) -> Int {
+              n - 1
+            }
+        "#});
+
+        let config = config("");
+        let page = generate_module_page(
+            Path::new(""),
+            &config,
+            &module,
+            &modules_links(&config, &[&module]),
+            &source_link(&config),
+        )
+        .unwrap();
+
+        assert_eq!(page.path, Path::new("test_module.html"));
+        assert!(page.content.contains("<a href=\"#pred\">pred</a>"));
+        assert!(page.content.contains("<a href=\"./test_module.html\">"));
+    }
+
+    #[test]
+    fn incremental_regeneration() {
+        let mut module = check(indoc::indoc! {r#"
+            /// Add one.
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+
+        let config = config("");
+        let mut cache = incremental::DocCache::default();
+        let paths = |files: Vec<DocFile>| files.into_iter().map(|file| file.path).collect_vec();
+
+        let (files, failures) =
+            cache.regenerate(&fs::NoFileSystem, Path::new(""), &config, vec![&module]);
+        assert!(failures.is_empty());
+        let files = paths(files);
+        assert!(files.contains(&PathBuf::from("test_module.html")));
+        assert!(files.contains(&PathBuf::from("css/index.css")));
+
+        let (files, _) = cache.regenerate(&fs::NoFileSystem, Path::new(""), &config, vec![&module]);
+        assert_eq!(
+            paths(files),
+            vec![
+                PathBuf::from("all.html"),
+                PathBuf::from("search.html"),
+                PathBuf::from("search-data.js"),
+                PathBuf::from("search-data.json"),
+            ]
+        );
+
+        module.code.push('\n');
+        let (files, _) = cache.regenerate(&fs::NoFileSystem, Path::new(""), &config, vec![&module]);
+        let files = paths(files);
+        assert!(files.contains(&PathBuf::from("test_module.html")));
+        assert!(!files.contains(&PathBuf::from("css/index.css")));
+    }
+
+    #[test]
+    fn incremental_regeneration_across_modules() {
+        let mut project = TestProject::new();
+
+        let parse_other = |project: &mut TestProject, source_code: &str| {
+            let mut other = project.parse(source_code);
+            other.name = "other".to_string();
+            other.ast.name = "other".to_string();
+            project.check(other)
+        };
+
+        let other = parse_other(&mut project, "pub fn foo() { 1 }");
+
+        let module = project.check(project.parse(indoc::indoc! {r#"
+            /// Like [other.bar].
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#}));
+
+        let config = config("");
+        let mut cache = incremental::DocCache::default();
+        let page = |files: Vec<DocFile>| {
+            files
+                .into_iter()
+                .find(|file| file.path == Path::new("test_module.html"))
+                .map(|file| file.content)
+        };
+
+        let (files, _) = cache.regenerate(
+            &fs::NoFileSystem,
+            Path::new(""),
+            &config,
+            vec![&other, &module],
+        );
+        assert!(page(files).unwrap().contains("Like [other.bar]."));
+
+        let other = parse_other(&mut project, "pub fn foo() { 1 }\n\npub fn bar() { 2 }");
+
+        let (files, _) = cache.regenerate(
+            &fs::NoFileSystem,
+            Path::new(""),
+            &config,
+            vec![&other, &module],
+        );
+        assert!(page(files)
+            .expect("page not regenerated")
+            .contains("<a href=\"./other.html#bar\">other.bar</a>"));
+
+        let (files, _) = cache.regenerate(
+            &fs::NoFileSystem,
+            Path::new(""),
+            &config,
+            vec![&other, &module],
+        );
+        assert!(page(files).is_none());
+    }
+
+    #[test]
+    fn module_json_is_opt_in() {
+        let module = check(indoc::indoc! {r#"
+            /// Add one.
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        assert_eq!(
+            files.iter().map(|file| &file.path).collect_vec(),
+            vec![&PathBuf::from("test_module.html")]
+        );
+
+        let (_, files) = generate(&config("[docs]\nformat = \"html+json\""), &module);
+        let json: json::Value = json::from_str(&file_content(&files, "test_module.json")).unwrap();
+        assert_eq!(json["name"], "test_module");
+        assert_eq!(json["functions"][0]["name"], "succ");
+        assert_eq!(json["functions"][0]["raw_documentation"], " Add one.");
+        assert!(json["functions"][0].get("documentation").is_none());
+
+        let (_, files) = generate(
+            &config("[docs]\nformat = \"html+json\"\nlayout = \"nested\""),
+            &module,
+        );
+        assert_eq!(
+            files.iter().map(|file| &file.path).collect_vec(),
+            vec![
+                &PathBuf::from("test_module/index.json"),
+                &PathBuf::from("test_module/index.html")
+            ]
+        );
+    }
+
+    #[test]
+    fn docs_json() {
+        let module = check(indoc::indoc! {r#"
+            /// Add one.
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+
+            pub type Point {
+              x: Int,
+              y: Int,
+            }
+        "#});
+
+        let files = generate_files(&config(""), vec![&module]);
+        assert!(!files.iter().any(|file| file.path == Path::new("docs.json")));
+
+        let files = generate_files(&config("[docs]\nformat = \"html+json\""), vec![&module]);
+        let json: json::Value = json::from_str(&file_content(&files, "docs.json")).unwrap();
+        assert_eq!(json["schema_version"], DOCS_JSON_SCHEMA_VERSION);
+        assert_eq!(json["modules"][0]["name"], "test_module");
+        assert_eq!(
+            json["modules"][0]["functions"][0]["signature"],
+            "succ(n: Int) -> Int"
+        );
+        assert_eq!(
+            json["modules"][0]["functions"][0]["raw_documentation"],
+            " Add one."
+        );
+        assert_eq!(json["modules"][0]["types"][0]["name"], "Point");
+    }
+
+    #[test]
+    fn excluded_modules() {
+        let mut project = TestProject::new();
+
+        let mut fixture = project.parse("pub fn fixture() { 1 }");
+        fixture.name = "tests/fixtures/sample".to_string();
+        fixture.ast.name = "tests/fixtures/sample".to_string();
+        let fixture = project.check(fixture);
+
+        let module = project.check(project.parse("pub fn foo() { 1 }"));
+
+        let files = generate_files(
+            &config("[docs]\nexclude = [\"tests/**\"]\nsearch_data = \"fetch\""),
+            vec![&fixture, &module],
+        );
+
+        assert!(files
+            .iter()
+            .any(|file| file.path == Path::new("test_module.html")));
+        assert!(!files
+            .iter()
+            .any(|file| file.path == Path::new("tests/fixtures/sample.html")));
+        assert!(files
+            .iter()
+            .all(|file| !file.content.contains("tests/fixtures/sample")));
+
+        let search_data = file_content(&files, "search-data.json");
+        assert!(search_data.contains("foo"));
+        assert!(!search_data.contains("fixture"));
+
+        assert!(toml::from_str::<Config>(
+            "name = \"test/project\"\nversion = \"1.0.0\"\n[docs]\nexclude = [\"tests/[\"]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn sidebar_groups_unstable_modules() {
+        let module = check(indoc::indoc! {r#"
+            //// @unstable
+
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+
+        let index =
+            |config: &Config| file_content(&generate_files(config, vec![&module]), "index.html");
+
+        assert!(!index(&config("")).contains("Unstable modules"));
+        assert!(index(&config("[docs]\ngroup_by_stability = true")).contains("Unstable modules"));
+    }
+
+    #[test]
+    fn function_section_markers() {
+        let module = check(indoc::indoc! {r#"
+            pub fn a() { 1 }
+
+            /// @section Helpers
+            /// Doubles things.
+            pub fn b() { 2 }
+
+            pub fn c() { 3 }
+
+            /// Quadruples things.
+            ///
+            /// @section Others
+            pub fn d() { 4 }
+
+            /// @section Helpers
+            pub fn e() { 5 }
+        "#});
+
+        let page = file_content(
+            &generate_files(&config(""), vec![&module]),
+            "test_module.html",
+        );
+
+        let position = |needle: &str| page.find(needle).unwrap_or_else(|| panic!("{needle}"));
+        let order = [
+            "<h2 id=\"a\"",
+            "<h2 id=\"c\"",
+            "class=\"module-heading\"><a href=\"#Helpers\">Helpers</a></h2>",
+            "<h2 id=\"b\"",
+            "<h2 id=\"e\"",
+            "class=\"module-heading\"><a href=\"#Others\">Others</a></h2>",
+            "<h2 id=\"d\"",
+        ]
+        .map(position);
+        assert!(order.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert!(!page.contains("@section"));
+        assert!(page.contains("Doubles things."));
+
+        assert_eq!(
+            split_marker(Some("Quadruples things.\n\n@section Others"), "@section"),
+            (
+                "Quadruples things.\n".to_string(),
+                Some("Others".to_string())
+            )
+        );
+        assert_eq!(
+            split_marker(Some("No @section here"), "@section"),
+            ("No @section here".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn sidebar_module_summaries() {
+        let module = check(indoc::indoc! {r#"
+            //// Arithmetic on **small** integers. Every function
+            //// here is `pure`.
+            ////
+            //// More details.
+
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+
+        assert_eq!(module_summary(&module), "Arithmetic on small integers.");
+
+        let links = generate_modules_links(&[&module], true, false, DocLayout::Flat);
+        assert_eq!(links[0].summary, "Arithmetic on small integers.");
+
+        let links = generate_modules_links(&[&module], false, false, DocLayout::Flat);
+        assert!(links[0].summary.is_empty());
+
+        let undocumented = check("pub fn succ(n: Int) -> Int { n + 1 }");
+        assert_eq!(module_summary(&undocumented), "");
+    }
+
+    #[test]
+    fn page_descriptions() {
+        let module = check(indoc::indoc! {r#"
+            //// # Arithmetic
+            ////
+            //// Operations on **small** [integers](https://example.com). Every function
+            //// here is `pure`.
+            ////
+            //// More details.
+
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+
+        let page = file_content(&generate_files(&config(
+                "description = \"Some project\"\n[docs]\nbase_url = \"https://example.com/docs/\"",
+            ), vec![&module]), "test_module.html");
+
+        let description = "Operations on small integers. Every function here is pure.";
+        assert!(page.contains(&format!(
+            "<meta name=\"description\" content=\"{description}\"/>"
+        )));
+        assert!(page.contains(&format!(
+            "<meta property=\"og:description\" content=\"{description}\"/>"
+        )));
+        assert!(page.contains("<meta property=\"og:title\""));
+        assert!(page.contains(
+            "<meta property=\"og:url\" content=\"https://example.com/docs/test_module.html\"/>"
+        ));
+
+        let config = config("description = \"Some project\"");
+        assert_eq!(page_description(&config, ""), "Some project");
+        let long = page_description(&config, &"word ".repeat(100));
+        assert_eq!(long.chars().count(), MAX_DESCRIPTION_LENGTH);
+        assert!(long.ends_with("word…"));
+    }
+
+    #[test]
+    fn footer_compatible_compiler() {
+        let module = check("pub fn succ(n: Int) -> Int { n + 1 }");
+
+        let page = |config: &Config| {
+            let (_, files) = generate(config, &module);
+            file_content(&files, "test_module.html")
+        };
+
+        let default = page(&config(""));
+        assert!(default.contains(&format!("Generated with Aiken v{VERSION}")));
+        assert!(!default.contains("Compatible with Aiken"));
+
+        let declared = page(&config("[docs]\ncompatible_compiler = \">=1.1.0, <2.0.0\""));
+        assert!(declared.contains("Compatible with Aiken <code>&gt;=1.1.0, &lt;2.0.0</code>"));
+    }
+
+    #[test]
+    fn json_ld_structured_data() {
+        let module = check("pub fn succ(n: Int) -> Int { n + 1 }");
+
+        let page = |config: &Config| {
+            let (_, files) = generate(config, &module);
+            file_content(&files, "test_module.html")
+        };
+
+        assert!(!page(&config("")).contains("application/ld+json"));
+
+        let html = page(&config(indoc::indoc! {r#"
+            [docs]
+            json_ld = true
+            base_url = "https://example.com/docs/"
+        "#}));
+
+        let start = html
+            .find(r#"<script type="application/ld+json">"#)
+            .expect("missing JSON-LD");
+        let content = &html[start..];
+        let content = &content[content.find('>').unwrap() + 1..content.find("</script>").unwrap()];
+
+        let data: json::Value = json::from_str(content).unwrap();
+        assert_eq!(data["@type"], "TechArticle");
+        assert_eq!(data["url"], "https://example.com/docs/test_module.html");
+        assert_eq!(data["about"]["@type"], "SoftwareSourceCode");
+        assert_eq!(data["about"]["programmingLanguage"], "Aiken");
+    }
+
+    #[test]
+    fn sitemap_lists_generated_pages() {
+        let module =
+            check("use aiken/builtin\n\npub fn succ(n: Int) -> Int { builtin.add_integer(n, 1) }");
+        let empty = check("fn pred(n: Int) -> Int { n - 1 }");
+
+        let files = generate_files(&config(""), vec![&module]);
+        assert!(!files
+            .iter()
+            .any(|file| file.path == Path::new("sitemap.xml")));
+
+        let sitemap = file_content(
+            &generate_files(
+                &config("[docs]\nbase_url = \"https://example.com/docs/\""),
+                vec![&module, &empty],
+            ),
+            "sitemap.xml",
+        );
+
+        let locs = sitemap
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("<loc>"))
+            .filter_map(|line| line.strip_suffix("</loc>"))
+            .collect_vec();
+
+        assert_eq!(
+            locs,
+            vec![
+                "https://example.com/docs/index.html",
+                "https://example.com/docs/all.html",
+                "https://example.com/docs/dependencies.html",
+                "https://example.com/docs/search.html",
+                "https://example.com/docs/test_module.html",
+            ]
+        );
+        assert_eq!(sitemap.matches("<lastmod>").count(), locs.len());
+    }
+
+    #[test]
+    fn manifest_lists_generated_files() {
+        let module = check("pub fn succ(n: Int) -> Int { n + 1 }");
+
+        let files = generate_files(&config(""), vec![&module]);
+        assert!(!files
+            .iter()
+            .any(|file| file.path == Path::new("manifest.json")));
+
+        let files = generate_files(&config("[docs]\nmanifest = true"), vec![&module]);
+
+        let manifest: json::Value = json::from_str(&file_content(&files, "manifest.json")).unwrap();
+        let entries = manifest["files"].as_array().unwrap();
+
+        assert_eq!(entries.len(), files.len() - 1);
+
+        let css = entries
+            .iter()
+            .find(|entry| entry["path"] == "css/index.css")
+            .expect("missing stylesheet in manifest");
+        assert_eq!(css["size"], file_content(&files, "css/index.css").len());
+        assert_eq!(css["blake2b_256"].as_str().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn members_order() {
+        let module = check(indoc::indoc! {r#"
+            pub type Foo {
+              Foo
+            }
+
+            pub const bar: Int = 42
+
+            pub fn baz() {
+              bar
+            }
+        "#});
+
+        let position = |html: &str, id: &str| {
+            html.find(&format!("id=\"{id}\""))
+                .unwrap_or_else(|| panic!("missing {id}"))
+        };
+
+        let (_, files) = generate(&config(""), &module);
+        let html = &files[0].content;
+        assert!(position(html, "module-types") < position(html, "module-constants"));
+        assert!(position(html, "module-constants") < position(html, "module-functions"));
+
+        let (_, files) = generate(&config("[docs]\norder = [\"functions\"]"), &module);
+        let html = &files[0].content;
+        assert!(position(html, "module-functions") < position(html, "module-types"));
+        assert!(position(html, "module-types") < position(html, "module-constants"));
+    }
+
+    #[test]
+    fn llms_txt() {
+        let mut project = TestProject::new();
+
+        let mut other = project.parse(indoc::indoc! {r#"
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+        other.name = "foo/other".to_string();
+        other.ast.name = "foo/other".to_string();
+        let other = project.check(other);
+
+        let mut module = project.parse(indoc::indoc! {r#"
+            //// Arithmetic helpers. And more.
+
+            pub fn pred(n: Int) -> Int {
+              n - 1
+            }
+        "#});
+        module.name = "foo/math".to_string();
+        module.ast.name = "foo/math".to_string();
+        let module = project.check(module);
+
+        let generate_llms_txt = |extra: &str| {
+            generate_files(&config(extra), vec![&other, &module])
+                .into_iter()
+                .find(|file| file.path == Path::new("llms.txt"))
+                .map(|file| file.content)
+        };
+
+        assert!(generate_llms_txt("").is_none());
+
+        let content = generate_llms_txt("[docs]\nllms_txt = true").expect("missing llms.txt");
+        assert!(content.starts_with("# test/project\n\nVersion: 1.0.0\n"));
+        assert!(content.contains("Version: 1.0.0\n\n## Modules\n\n"));
+        assert!(content.contains(
+            "- [foo/math](foo/math.html): Arithmetic helpers.\n- [foo/other](foo/other.html)\n"
+        ));
+        assert!(content.contains("- [All symbols](all.html)"));
+
+        let content = generate_llms_txt(indoc::indoc! {r#"
+            description = "Numbers, mostly"
+            [docs]
+            llms_txt = true
+            base_url = "https://example.com/docs"
+        "#})
+        .expect("missing llms.txt");
+        assert!(content.starts_with("# test/project\n\n> Numbers, mostly\n\nVersion: 1.0.0\n"));
+        assert!(content.contains(
+            "- [foo/math](https://example.com/docs/foo/math.html): Arithmetic helpers.\n"
+        ));
+    }
+
+    #[test]
+    fn source_links_to_repository() {
+        let mut module = check(indoc::indoc! {r#"
+            pub fn foo() {
+              1
+            }
+
+            pub fn bar() {
+              2
+            }
+        "#});
+        module.input_path = PathBuf::from("lib/test_module.ak");
+
+        let (_, files) = generate(&config(""), &module);
+        assert!(!files[0].content.contains("member-source"));
+
+        for (platform, expected) in [
+            (
+                r#""github""#,
+                "https://github.com/aiken-lang/test/blob/1.0.0/lib/test_module.ak#L5-L7",
+            ),
+            (
+                r#""gitlab""#,
+                "https://gitlab.com/aiken-lang/test/-/blob/1.0.0/lib/test_module.ak#L5-7",
+            ),
+            (
+                r#""bitbucket""#,
+                "https://bitbucket.org/aiken-lang/test/src/1.0.0/lib/test_module.ak#lines-5:7",
+            ),
+            (
+                r#"{ custom = { base_url = "https://git.example.com/", blob_template = "{base_url}/{user}/{project}/src/tag/{version}/{path}#L{start}-L{end}" } }"#,
+                "https://git.example.com/aiken-lang/test/src/tag/1.0.0/lib/test_module.ak#L5-L7",
+            ),
+        ] {
+            let config = config(&format!(
+                "[repository]\nuser = \"aiken-lang\"\nproject = \"test\"\nplatform = {platform}"
+            ));
+
+            let (_, files) = generate(&config, &module);
+            assert!(
+                files[0].content.contains(&format!("href=\"{expected}\"")),
+                "missing source link for {platform}"
+            );
+        }
+
+        let config = config(indoc::indoc! {r#"
+            [repository]
+            user = "aiken-lang"
+            project = "test"
+            platform = { custom = { base_url = "https://git.example.com", blob_template = "" } }
+        "#});
+        let files = generate_files(&config, vec![&module]);
+        assert!(file_content(&files, "index.html")
+            .contains("href=\"https://git.example.com/aiken-lang/test\""));
+    }
+
+    #[test]
+    fn edit_links() {
+        let mut module = check(indoc::indoc! {r#"
+            pub fn foo() {
+              1
+            }
+        "#});
+        module.input_path = PathBuf::from("lib/test_module.ak");
+
+        let repository =
+            "[repository]\nuser = \"aiken-lang\"\nproject = \"test\"\nplatform = \"github\"";
+
+        let (_, files) = generate(&config(repository), &module);
+        assert!(!files[0].content.contains("edit-page"));
+
+        let (_, files) = generate(&config("[docs]\nedit_url = \"edit/main/{path}\""), &module);
+        assert!(!files[0].content.contains("edit-page"));
+
+        let config = config(&format!(
+            "[docs]\nedit_url = \"edit/main/{{path}}\"\n{repository}"
+        ));
+        let files = generate_files(&config, vec![&module]);
+        let page = |path: &str| file_content(&files, path);
+        assert!(page("test_module.html").contains(
+            "<a class=\"edit-page\" href=\"https://github.com/aiken-lang/test/edit/main/lib/test_module.ak\">"
+        ));
+        assert!(page("index.html").contains(
+            "<a class=\"edit-page\" href=\"https://github.com/aiken-lang/test/edit/main/README.md\">"
+        ));
+        assert!(!page("all.html").contains("edit-page"));
+    }
+
+    #[test]
+    fn constructor_anchors() {
+        let module = check(indoc::indoc! {r#"
+            pub type Credential {
+              VerificationKey(ByteArray)
+              Script(ByteArray)
+            }
+
+            pub type Point {
+              x: Int,
+              y: Int,
+            }
+        "#});
+
+        let (search_indexes, files) = generate(&config(""), &module);
+
+        assert!(files[0].content.contains("id=\"Credential.Script\""));
+        assert!(files[0]
+            .content
+            .contains("href=\"#Credential.VerificationKey\""));
+
+        let titles = search_indexes
+            .iter()
+            .map(|entry| (entry.title.as_str(), entry.url.as_str()))
+            .collect_vec();
+        assert!(titles.contains(&(
+            "Credential.VerificationKey",
+            "test_module.html#Credential.VerificationKey"
+        )));
+        assert!(titles.contains(&("Credential.Script", "test_module.html#Credential.Script")));
+        assert!(!titles.iter().any(|(title, _)| title.starts_with("Point.")));
+    }
+
+    #[test]
+    fn constructor_field_anchors() {
+        let module = check(indoc::indoc! {r#"
+            pub type Point {
+              /// Horizontal coordinate.
+              x: Int,
+              y: Int,
+            }
+
+            pub type Pair {
+              Pair(Int, Int)
+            }
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        let html = &files[0].content;
+
+        assert!(html.contains("id=\"Point.Point.x\""));
+        assert!(html.contains("href=\"#Point.Point.x\""));
+        assert!(html.contains("<p>Horizontal coordinate.</p>"));
+        assert!(!html.contains("id=\"Point.Point.y\""));
+        assert_eq!(html.matches("class=\"constructor-field\"").count(), 1);
+    }
+
+    #[test]
+    fn module_table_of_contents() {
+        let module = check(indoc::indoc! {r#"
+            pub type Point {
+              x: Int,
+              y: Int,
+            }
+
+            pub fn origin() -> Point {
+              Point { x: 0, y: 0 }
+            }
+
+            pub fn norm(point: Point) -> Int {
+              point.x * point.x + point.y * point.y
+            }
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        let html = &files[0].content;
+
+        let toc = &html[html.find("<details class=\"module-toc\">").expect("no toc")..];
+        let toc = &toc[..toc.find("</details>").unwrap()];
+
+        let links = toc
+            .match_indices("href=\"#")
+            .map(|(ix, _)| &toc[ix + 7..ix + 7 + toc[ix + 7..].find('"').unwrap()])
+            .collect_vec();
+
+        assert_eq!(
+            links,
+            vec![
+                "module-types",
+                "Point",
+                "module-functions",
+                "origin",
+                "norm"
+            ]
+        );
+    }
+
+    #[test]
+    fn copyable_code_blocks() {
+        let module = check(indoc::indoc! {r#"
+            /// Use `succ` as follows:
+            ///
+            /// ```aiken
+            /// succ(41) == 42
+            /// ```
+            ///
+            /// ```
+            /// 42
+            /// ```
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        let html = &files[0].content;
+
+        assert!(html.contains(
+            "<div class=\"code-block\"><button class=\"copy-code\" title=\"Copy to clipboard\" aria-label=\"Copy to clipboard\"><svg class=\"icon icon-copy\"><use xlink:href=\"#icon-copy\"></use></svg></button>\n<pre><code class=\"language-aiken\">succ(41) == 42\n</code></pre>\n</div>"
+        ));
+        assert_eq!(html.matches("class=\"copy-code\"").count(), 1);
+    }
+
+    #[test]
+    fn validators() {
+        let module = check(indoc::indoc! {r#"
+            /// Locks funds until a deadline.
+            validator vesting(deadline: Int) {
+              /// Only after the deadline.
+              spend(_datum: Option<Data>, _redeemer: Data, _utxo: Data, _self: Data) {
+                deadline > 0
+              }
+
+              mint(_redeemer: Data, _policy_id: ByteArray, _self: Data) {
+                True
+              }
+            }
+        "#});
+
+        let (search_indexes, files) = generate(&config(""), &module);
+        let html = &files[0].content;
+
+        assert!(html.contains("id=\"module-validators\""));
+        assert!(html.contains("validator vesting(deadline: Int)"));
+        assert!(html.contains("id=\"vesting.spend\""));
+        assert!(html.contains("<p>Only after the deadline.</p>"));
+        assert!(!html.contains("id=\"vesting.else\""));
+
+        let urls = search_indexes
+            .iter()
+            .map(|entry| entry.url.as_str())
+            .collect_vec();
+        assert!(urls.contains(&"test_module.html#vesting"));
+        assert!(urls.contains(&"test_module.html#vesting.spend"));
+        assert!(urls.contains(&"test_module.html#vesting.mint"));
+    }
+
+    #[test]
+    fn analytics_snippet() {
+        let module = check("pub fn foo() { 1 }");
+
+        let (_, files) = generate(&config(""), &module);
+        assert!(!files[0].content.contains("data-domain"));
+
+        let (_, files) = generate(
+            &config(indoc::indoc! {r#"
+                [docs.analytics]
+                provider = "plausible"
+                id = "docs.example.com"
+            "#}),
+            &module,
+        );
+        assert!(files[0].content.contains(
+            r#"<script defer data-domain="docs.example.com" data-do-not-track="true" src="https://plausible.io/js/script.js"></script>"#
+        ));
+
+        let (_, files) = generate(
+            &config(indoc::indoc! {r#"
+                [docs.analytics]
+                provider = "umami"
+                id = "\"><script>alert(1)</script>"
+            "#}),
+            &module,
+        );
+        assert!(files[0].content.contains("data-website-id"));
+        assert!(!files[0].content.contains("<script>alert(1)</script>"));
+
+        let (_, files) = generate(
+            &config(indoc::indoc! {r#"
+                [docs.analytics]
+                provider = "umami"
+                id = "1234"
+                script = "http://example.com/script.js"
+            "#}),
+            &module,
+        );
+        assert!(!files[0].content.contains("data-website-id"));
+    }
+
+    #[test]
+    fn signatures_wrap_at_max_columns() {
+        let module = check(indoc::indoc! {r#"
+            pub const thresholds: List<Int> = [100000000, 200000000, 300000000, 400000000, 500000000, 600000000, 700000000]
+
+            pub fn settle(beneficiary: ByteArray, amount: Int, deadline: Int, fee: Int) -> Option<Int> {
+              todo
+            }
+        "#});
+
+        let json = |extra: &str| {
+            let (_, files) = generate(
+                &config(&format!("[docs]\nformat = \"html+json\"\n{extra}")),
+                &module,
+            );
+            json::from_str::<json::Value>(&file_content(&files, "test_module.json")).unwrap()
+        };
+
+        let wrapped = json("");
+        assert_eq!(
+            wrapped["functions"][0]["signature"],
+            indoc::indoc! {"
+                settle(
+                  beneficiary: ByteArray,
+                  amount: Int,
+                  deadline: Int,
+                  fee: Int,
+                ) -> Option<Int>"}
+        );
+        assert!(wrapped["constants"][0]["definition"]
+            .as_str()
+            .unwrap()
+            .contains('\n'));
+
+        let unwrapped = json("max_columns = 120");
+        assert_eq!(
+            unwrapped["functions"][0]["signature"],
+            "settle(beneficiary: ByteArray, amount: Int, deadline: Int, fee: Int) -> Option<Int>"
+        );
+        assert!(!unwrapped["constants"][0]["definition"]
+            .as_str()
+            .unwrap()
+            .contains('\n'));
+    }
+
+    #[test]
+    fn index_of_symbols() {
+        let module = check(indoc::indoc! {r#"
+            pub type Credential {
+              VerificationKey(ByteArray)
+              Script(ByteArray)
+            }
+
+            pub const zero: Int = 0
+
+            pub fn add(a: Int, b: Int) -> Int {
+              a + b
+            }
+
+            pub fn ceil(n: Int) -> Int {
+              n
+            }
+        "#});
+
+        let page = file_content(&generate_files(&config(""), vec![&module]), "all.html");
+
+        let add = page
+            .find("<a href=\"test_module.html#add\"><code>add</code></a>")
+            .expect("add is missing");
+        let ceil = page.find("test_module.html#ceil").expect("ceil is missing");
+        let credential = page
+            .find("test_module.html#Credential\"")
+            .expect("Credential is missing");
+        let zero = page.find("test_module.html#zero").expect("zero is missing");
+        assert!(add < ceil && ceil < credential && credential < zero);
+
+        assert!(page.contains("<h2 id=\"symbols-C\">C</h2>"));
+        assert!(!page.contains("Credential.Script"));
+        assert!(!page.contains("href=\"test_module.html\""));
+    }
+
+    #[test]
+    fn static_search_page() {
+        let module = check(indoc::indoc! {r#"
+            //// Some arithmetic.
+
+            pub type Credential {
+              Script
+              Key
+            }
+
+            /// @deprecated use add
+            pub fn plus(a: Int, b: Int) -> Int {
+              a + b
+            }
+
+            pub fn add(a: Int, b: Int) -> Int {
+              a + b
+            }
+        "#});
+
+        let page = file_content(&generate_files(&config(""), vec![&module]), "search.html");
+
+        assert!(page.contains("<h2><a href=\"test_module.html\">test_module</a></h2>"));
+        assert!(page.contains(
+            "<tr><td><a href=\"test_module.html#Credential.Script\"><code>Credential.Script</code></a></td><td></td></tr>"
+        ));
+        assert!(page.contains(
+            "<tr><td><a href=\"test_module.html#plus\"><code>plus</code></a></td><td>deprecated</td></tr>"
+        ));
+        assert!(page.contains("test_module.html#add"));
+    }
+
+    #[test]
+    fn custom_favicon_and_logo() {
+        let module = check("pub fn foo() { 1 }");
+
+        let files = |extra: &str| generate_files(&config(extra), vec![&module]);
+
+        let page = |files: &[DocFile]| file_content(files, "test_module.html");
+
+        let default = files("");
+        assert!(default
+            .iter()
+            .any(|file| file.path == Path::new("favicon.svg")));
+        let default_page = page(&default);
+        assert!(default_page.contains("href=\"./favicon.svg\""));
+        assert!(!default_page.contains("project-logo"));
+
+        let branded = files("[docs]\nfavicon = \"assets/icon.png\"\nlogo = \"assets/brand.svg\"");
+        assert!(!branded
+            .iter()
+            .any(|file| file.path == Path::new("favicon.svg")));
+        let branded_page = page(&branded);
+        assert!(branded_page.contains("<link rel=\"icon\" href=\"./favicon.png\"/>"));
+        assert!(branded_page.contains("<img class=\"project-logo\" src=\"./logo.svg\" alt=\"\"/>"));
+
+        let docs =
+            config("[docs]\nfavicon = \"assets/icon.png\"\nlogo = \"assets/brand.svg\"").docs;
+        assert_eq!(
+            docs.images(),
+            vec![
+                (Path::new("assets/icon.png"), PathBuf::from("favicon.png")),
+                (Path::new("assets/brand.svg"), PathBuf::from("logo.svg")),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_assets_leaves_out_missing_ones() {
+        let overlay = fs::OverlayFileSystem::new(&fs::NoFileSystem)
+            .with_file("project/assets/brand.svg", "<svg></svg>");
+
+        let mut branded = config(
+            "[docs]\nfavicon = \"assets/icon.png\"\nlogo = \"assets/brand.svg\"\ntheme = \"dracula\"",
+        );
+        let (images, warnings) = check_assets(&overlay, Path::new("project"), &mut branded);
+
+        assert_eq!(
+            images,
+            vec![DocImage {
+                path: PathBuf::from("logo.svg"),
+                content: b"<svg></svg>".to_vec(),
+            }]
+        );
+        assert!(matches!(
+            warnings.as_slice(),
+            [
+                Warning::MissingDocAsset { path },
+                Warning::UnknownDocTheme { name },
+            ] if path == Path::new("assets/icon.png") && name == "dracula"
+        ));
+        assert_eq!(branded.docs.favicon, None);
+        assert_eq!(branded.docs.logo, Some(PathBuf::from("assets/brand.svg")));
+        assert_eq!(branded.docs.theme, None);
+
+        let mut themed = config("[docs]\ntheme = \"assets/docs.css\"");
+        let (_, warnings) = check_assets(&overlay, Path::new("project"), &mut themed);
+        assert!(matches!(
+            warnings.as_slice(),
+            [Warning::MissingDocTheme { path }] if path == Path::new("assets/docs.css")
+        ));
+        assert_eq!(themed.docs.theme(), DocTheme::AtomOne);
+    }
+
+    #[test]
+    fn themes() {
+        let module = check("pub fn foo() { 1 }");
+
+        let files = |extra: &str| generate_files(&config(extra), vec![&module]);
+
+        let has_file =
+            |files: &[DocFile], path: &str| files.iter().any(|file| file.path == Path::new(path));
+
+        let default = files("");
+        assert!(has_file(&default, "css/atom-one-light.min.css"));
+        assert!(has_file(&default, "css/atom-one-dark.min.css"));
+        assert!(!has_file(&default, "css/theme.css"));
+
+        let github = files("[docs]\ntheme = \"github\"");
+        assert!(has_file(&github, "css/github-light.min.css"));
+        assert!(has_file(&github, "css/github-dark.min.css"));
+        assert!(has_file(&github, "css/theme.css"));
+        assert!(!has_file(&github, "css/atom-one-light.min.css"));
+
+        let page = &file_content(&github, "test_module.html");
+        assert!(page.contains("href=\"./css/theme.css?v="));
+        assert!(page.contains("href=\"./css/github-light.min.css?v="));
+        assert!(page.contains("dark: \"github-dark\""));
+
+        let custom = files("[docs]\ntheme = \"assets/docs.css\"");
+        assert!(has_file(&custom, "css/atom-one-light.min.css"));
+        assert!(!has_file(&custom, "css/theme.css"));
+
+        let overlay = fs::OverlayFileSystem::new(&fs::NoFileSystem)
+            .with_file("assets/docs.css", ":root { --color-primary: red; }");
+        let custom = generate_all(
+            &overlay,
+            Path::new(""),
+            &config("[docs]\ntheme = \"assets/docs.css\""),
+            vec![&module],
+        )
+        .0;
+        assert!(custom
+            .iter()
+            .any(|file| file.path == Path::new("css/theme.css")
+                && file.content == ":root { --color-primary: red; }"));
+    }
+
+    #[test]
+    fn search_input_controls_results() {
+        let module = check("pub fn foo() { 1 }");
+
+        let (_, files) = generate(&config(""), &module);
+
+        let page = &files[0].content;
+        assert!(page.contains("role=\"combobox\" aria-autocomplete=\"list\" aria-controls=\"search-results\" aria-expanded=\"false\""));
+        assert!(page.contains("<div id=\"search-results\" class=\"search-results\"></div>"));
+    }
+
+    #[test]
+    fn plain_pages() {
+        let module = check(indoc::indoc! {r#"
+            /// ```aiken
+            /// foo()
+            /// ```
+            pub fn foo() { 1 }
+        "#});
+
+        let files = generate_files(&config("[docs]\nplain = true"), vec![&module]);
+
+        let assets = files
+            .iter()
+            .map(|file| file.path.to_string_lossy().to_string())
+            .filter(|path| !path.ends_with(".html"))
+            .sorted()
+            .collect_vec();
+        assert_eq!(assets, vec!["css/plain.css", "favicon.svg"]);
+
+        let page = &file_content(&files, "test_module.html");
+        assert!(!page.contains("<script"));
+        assert!(!page.contains("copy-code"));
+        assert!(page.contains("css/plain.css"));
+        assert!(page.contains("<a class=\"search-fallback\" href=\"./search.html\">"));
+    }
+
+    #[test]
+    fn fetched_search_data() {
+        let module = check("pub fn foo() { 1 }");
+
+        let files = |extra: &str| generate_files(&config(extra), vec![&module]);
+
+        let find = |files: &[DocFile], path: &str| {
+            files
+                .iter()
+                .find(|file| file.path == Path::new(path))
+                .map(|file| file.content.clone())
+        };
+
+        let default = files("");
+        let search_data = find(&default, "search-data.json").expect("no search data");
+        assert!(search_data.starts_with('['));
+        assert_eq!(
+            find(&default, "search-data.js"),
+            Some(format!("window.Aiken.initSearch({search_data});"))
+        );
+        let page = find(&default, "test_module.html").expect("no module page");
+        assert!(page.contains("src=\"./search-data.js"));
+        assert!(!page.contains("data-search-data"));
+
+        let fetched = files("[docs]\nsearch_data = \"fetch\"");
+        assert_eq!(find(&fetched, "search-data.json"), Some(search_data));
+        assert!(find(&fetched, "search-data.js").is_none());
+        let page = find(&fetched, "test_module.html").expect("no module page");
+        assert!(!page.contains("src=\"./search-data.js"));
+        assert!(page.contains("data-search-data=\"./search-data.json"));
+    }
+
+    #[test]
+    fn markdown_extensions() {
+        let module = check(indoc::indoc! {r#"
+            /// | Input | Output |
+            /// | :---- | -----: |
+            /// | 1     | 2      |
+            ///
+            /// ~~Subtracts~~ Adds one[^why], see the notes[^notes].
+            ///
+            /// - [x] positive numbers
+            /// - [ ] negative numbers
+            ///
+            /// [^why]: Because.
+            /// [^notes]: Some notes.
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        let page = &files[0].content;
+
+        assert!(page.contains("<th style=\"text-align: left\">Input</th>"));
+        assert!(page.contains("<del>Subtracts</del>"));
+        assert!(page.contains(
+            "<input class=\"task-list-item-checkbox\" type=\"checkbox\" disabled checked/>"
+        ));
+        assert!(
+            page.contains("<input class=\"task-list-item-checkbox\" type=\"checkbox\" disabled/>")
+        );
+        assert!(page.contains(
+            "<sup class=\"footnote-reference\" id=\"fnref-succ-why\"><a href=\"#fn-succ-why\">1</a></sup>"
+        ));
+        assert!(page.contains("<sup class=\"footnote-reference\" id=\"fnref-succ-notes\"><a href=\"#fn-succ-notes\">2</a></sup>"));
+        assert!(page.contains(
+            "<div class=\"footnote-definition\" id=\"fn-succ-notes\"><sup class=\"footnote-definition-label\">2</sup>"
+        ));
+        assert!(page.contains("<a class=\"footnote-backref\" href=\"#fnref-succ-why\""));
+    }
+
+    #[test]
+    fn footnotes_across_definitions() {
+        let module = check(indoc::indoc! {r#"
+            /// Adds one[^why], really[^why].
+            ///
+            /// [^why]: Because.
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+
+            /// Subtracts one[^why].
+            ///
+            /// [^why]: Why not.
+            pub fn pred(n: Int) -> Int {
+              n - 1
+            }
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        let page = &files[0].content;
+
+        let ids = page
+            .match_indices(" id=\"fn")
+            .map(|(ix, _)| {
+                let id = &page[ix + 5..];
+                &id[..id.find('"').unwrap()]
+            })
+            .collect_vec();
+
+        assert_eq!(
+            ids,
+            vec![
+                "fnref-succ-why",
+                "fnref-succ-why-2",
+                "fn-succ-why",
+                "fnref-pred-why",
+                "fn-pred-why"
+            ]
+        );
+        assert!(page.contains("<a href=\"#fn-succ-why\">1</a></sup>, really"));
+    }
+
+    #[test]
+    fn static_highlighting() {
+        let module = check(indoc::indoc! {r#"
+            /// Sums two numbers:
+            ///
+            /// ```aiken
+            /// let sum = add(1, b) // the sum
+            /// ```
+            ///
+            /// ```sh
+            /// aiken docs
+            /// ```
+            pub fn add(a: Int, b: Int) -> Int {
+              a + b
+            }
+        "#});
+
+        let files = |extra: &str| generate_files(&config(extra), vec![&module]);
+
+        let default = files("");
+        assert!(default
+            .iter()
+            .any(|file| file.path == Path::new("js/highlight.min.js")));
+
+        let highlighted = files("[docs]\nhighlighting = \"static\"");
+        assert!(!highlighted
+            .iter()
+            .any(|file| file.path.starts_with("js/highlight")));
+
+        let page = file_content(&highlighted, "test_module.html");
+        assert!(!page.contains("highlight.min.js"));
+        assert!(page.contains(concat!(
+            "<code class=\"language-aiken hljs\">",
+            "<span class=\"hljs-keyword\">let</span> <span class=\"hljs-variable\">sum</span> ",
+            "<span class=\"hljs-operator\">=</span> ",
+            "<span class=\"hljs-title function_ invoke__\">add</span>(",
+            "<span class=\"hljs-number\">1</span>, <span class=\"hljs-variable\">b</span>) ",
+            "<span class=\"hljs-comment\">// the sum</span>\n</code></pre>",
+        )));
+        assert!(page.contains("<code class=\"language-sh\">aiken docs\n</code>"));
+    }
+
+    #[test]
+    fn module_last_modified() {
+        struct ModifiedFileSystem;
+
+        impl fs::FileSystem for ModifiedFileSystem {
+            fn read_to_string(&self, _path: &Path) -> Option<String> {
+                None
+            }
+
+            fn modified(&self, _path: &Path) -> Option<SystemTime> {
+                Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+            }
+        }
+
+        let module = check("pub fn foo() { 1 }");
+
+        let files = generate_all(
+            &ModifiedFileSystem,
+            Path::new(""),
+            &config(""),
+            vec![&module],
+        )
+        .0;
+        let page = file_content(&files, "test_module.html");
+        assert!(page.contains("<time datetime=\"2023-11-14\">2023-11-14</time>"));
+
+        let build = Duration::from_secs(1_600_000_000);
+        assert_eq!(last_modified(&ModifiedFileSystem, &module, &build), build);
+        assert_eq!(last_modified(&fs::NoFileSystem, &module, &build), build);
+    }
+
+    #[test]
+    fn readme_in_memory() {
+        let module = check("pub fn foo() { 1 }");
+
+        let index = |fs: &dyn fs::FileSystem| {
+            file_content(
+                &generate_all(fs, Path::new(""), &config(""), vec![&module]).0,
+                "index.html",
+            )
+        };
+
+        let overlay = fs::OverlayFileSystem::new(&fs::NoFileSystem)
+            .with_file("README.md", "# Hello\n\nAn unsaved introduction.");
+        assert!(index(&overlay).contains("<p>An unsaved introduction.</p>"));
+        assert!(!index(&fs::NoFileSystem).contains("An unsaved introduction."));
+    }
+
+    #[test]
+    fn build_date_in_footer() {
+        let module = check("pub fn foo() { 1 }");
+
+        let (_, files) = generate(&config(""), &module);
+
+        assert!(files[0].content.contains(concat!(
+            "<span class=\"build-time\" data-timestamp=\"0\">",
+            "Built on <time datetime=\"1970-01-01\">1970-01-01</time></span>",
+        )));
+    }
+
+    #[test]
+    fn reproducible_timestamp() {
+        assert_eq!(
+            new_timestamp_from(Some("1700000000")),
+            Duration::from_secs(1700000000)
+        );
+        assert_eq!(
+            new_timestamp_from(Some(" 1700000000\n")),
+            Duration::from_secs(1700000000)
+        );
+
+        let before = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        assert!(new_timestamp_from(None) >= before);
+        assert!(new_timestamp_from(Some("yesterday")) >= before);
+    }
+
+    #[test]
+    fn escape_search_indexes() {
+        let indexes = escape_html_contents(vec![SearchIndex {
+            doc: "foo/<bar>".to_string(),
+            title: "Foo<a>".to_string(),
+            content: "<script>alert('boo')</script>".to_string(),
+            summary: String::new(),
+            url: "foo/bar.html#Foo<a> & b".to_string(),
+            private: false,
+            deprecated: false,
+            since: None,
+            deprecated_in: None,
+        }]);
+
+        assert_eq!(indexes[0].doc, "foo/&lt;bar&gt;");
+        assert_eq!(indexes[0].title, "Foo&lt;a&gt;");
+        assert_eq!(
+            indexes[0].content,
+            "&lt;script&gt;alert(&#39;boo&#39;)&lt;/script&gt;"
+        );
+        assert_eq!(indexes[0].url, "foo/bar.html#Foo%3Ca%3E%20%26%20b");
+
+        let serialized = json::to_string(&indexes).unwrap();
+        assert!(!serialized.contains('<'));
+        assert!(!serialized.contains('>'));
+    }
+
+    #[test]
+    fn api_reference_markdown() {
+        let module = check(indoc::indoc! {r#"
+            //// Some module.
+
+            /// A colour.
+            pub type Colour {
+              /// The colour of the sky.
+              Blue
+              Red
+            }
+
+            /// Add one.
+            pub fn add_one(n: Int) -> Int {
+              n + 1
+            }
+
+            pub const answer: Int = 42
+        "#});
+
+        let api_reference = |extra: &str| {
+            generate_files(&config(extra), vec![&module])
+                .into_iter()
+                .find(|file| file.path == Path::new(api_reference::FILE_NAME))
+        };
+
+        assert!(api_reference("").is_none());
+
+        let content = api_reference("[docs]\napi_reference = true")
+            .expect("no API reference")
+            .content;
+
+        assert!(content.starts_with("# test/project - API reference\n\nVersion 1.0.0\n"));
+        assert!(content.contains("- [test_module](#test_module)"));
+        assert!(content.contains("Some module."));
+        assert!(content.contains(
+            "<a id=\"test_module.add_one\"></a>\n\n#### `add_one`\n\n```aiken\nadd_one(n: Int) -> Int\n```\n\nAdd one."
+        ));
+        assert!(content.contains("<a id=\"test_module.Colour.Blue\"></a>"));
+        assert!(content.contains("The colour of the sky."));
+        assert!(content.contains("#### `answer`"));
+        assert!(content.find("### Types") < content.find("### Constants"));
+        assert!(content.find("### Constants") < content.find("### Functions"));
+    }
+
+    #[test]
+    fn changelog() {
+        let module = check(indoc::indoc! {r#"
+            /// @since 1.2.0
+            pub fn succ(n: Int) -> Int { n + 1 }
+
+            /// @deprecated 1.10.0: use `succ` instead.
+            /// @since v1
+            pub fn incr(n: Int) -> Int { n + 1 }
+
+            /// @deprecated
+            pub fn pred(n: Int) -> Int { n - 1 }
+        "#});
+
+        let files = generate_files(&config(""), vec![&module]);
+        let page = |path: &str| {
+            files
+                .iter()
+                .find(|file| file.path == Path::new(path))
+                .map(|file| file.content.clone())
+        };
+
+        let changelog = page("changelog.html").expect("no changelog");
+        let section = |id: &str| changelog.find(&format!("<h2 id=\"{id}\">")).unwrap();
+        assert!(section("version-1.10.0") < section("version-1.2.0"));
+        assert!(section("version-1.2.0") < section("version-v1"));
+        assert!(section("version-v1") < section("deprecated"));
+
+        let entry = |name: &str| {
+            changelog
+                .find(&format!("<a href=\"test_module.html#{name}\">"))
+                .unwrap()
+        };
+        assert!(section("version-1.10.0") < entry("incr"));
+        assert!(entry("incr") < section("version-1.2.0"));
+        assert!(section("version-1.2.0") < entry("succ"));
+        assert!(section("deprecated") < entry("pred"));
+
+        assert!(page("all.html")
+            .unwrap()
+            .contains("<a href=\"changelog.html\">Changelog</a>"));
+
+        let module = check("/// @deprecated 1.0.0\npub fn foo() { 1 }");
+        let files = generate_files(&config(""), vec![&module]);
+        assert!(!files
+            .iter()
+            .any(|file| file.path == Path::new("changelog.html")));
+    }
+
+    #[test]
+    fn permalinks() {
+        let module = check(indoc::indoc! {r#"
+            /// Adds one.
+            /// @anchor increment
+            pub fn succ(n: Int) -> Int { n + 1 }
+
+            /// @anchor not a valid anchor
+            pub fn pred(n: Int) -> Int { n - 1 }
+
+            /// @anchor origin
+            pub type Point {
+              x: Int,
+              y: Int,
+            }
+
+            /// @anchor zero-constant
+            pub const zero: Int = 0
+        "#});
+
+        let (search_indexes, files) = generate(&config(""), &module);
+
+        let page = &files[0].content;
+        assert!(page.contains("<h2 id=\"succ\">"));
+        assert!(page.contains("<a id=\"increment\" class=\"member-permalink\" href=\"#increment\""));
+        assert!(page.contains("<a id=\"origin\" class=\"member-permalink\""));
+        assert!(page.contains("<a id=\"zero-constant\" class=\"member-permalink\""));
+        assert!(!page.contains("@anchor"));
+        assert!(!page.contains("not a valid anchor"));
+
+        let url = |title: &str| {
+            search_indexes
+                .iter()
+                .find(|entry| entry.title == title)
+                .map(|entry| entry.url.as_str())
+        };
+        assert_eq!(url("succ"), Some("test_module.html#increment"));
+        assert_eq!(url("pred"), Some("test_module.html#pred"));
+        assert_eq!(url("Point"), Some("test_module.html#origin"));
+        assert_eq!(url("zero"), Some("test_module.html#zero-constant"));
+    }
+
+    #[test]
+    fn since_markers() {
+        let module = check(indoc::indoc! {r#"
+            /// Adds one.
+            /// @since 1.2.0
+            pub fn succ(n: Int) -> Int { n + 1 }
+
+            /// @since soon
+            pub fn pred(n: Int) -> Int { n - 1 }
+
+            /// @since v2
+            pub const zero: Int = 0
+        "#});
+
+        let files = generate_files(&config(""), vec![&module]);
+
+        let page = &file_content(&files, "test_module.html");
+        assert!(page.contains("<span class=\"member-since\" title=\"available since version 1.2.0\">since 1.2.0</span>"));
+        assert!(page.contains(">since v2</span>"));
+        assert!(!page.contains("@since"));
+        assert!(!page.contains("soon"));
+
+        let search_data: json::Value =
+            json::from_str(&file_content(&files, "search-data.json")).unwrap();
+        let since = |title: &str| {
+            search_data
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|entry| entry["title"] == title)
+                .map(|entry| entry["since"].clone())
+        };
+        assert_eq!(since("succ"), Some(json::json!("1.2.0")));
+        assert_eq!(since("pred"), Some(json::Value::Null));
+
+        assert_eq!(
+            invalid_since_markers(&[&module]),
+            vec![(
+                module.input_path.clone(),
+                "pred".to_string(),
+                "soon".to_string()
+            )]
+        );
+
+        assert!(["1.2.0", "1.2", "3", "v1.0.0", "1.0.0-beta.1"]
+            .iter()
+            .all(|version| is_version(version)));
+        assert!(!["", "soon", "1.x", "1.2.3.4"]
+            .iter()
+            .any(|version| is_version(version)));
+    }
+
+    #[test]
+    fn constant_type_signatures() {
+        let module = check(indoc::indoc! {r#"
+            pub const origin = (0, 0)
+
+            pub const prefix = #"ff"
+        "#});
+
+        let (_, files) = generate(&config("[docs]\nformat = \"html+json\""), &module);
+        let find = |path: &str| file_content(&files, path);
+
+        let json: json::Value = json::from_str(&find("test_module.json")).unwrap();
+        assert_eq!(json["constants"][0]["name"], "origin");
+        assert_eq!(json["constants"][0]["type_signature"], "(Int, Int)");
+        assert_eq!(json["constants"][1]["type_signature"], "ByteArray");
+
+        assert!(find("test_module.html")
+            .contains("<a href=\"#prefix\" title=\"prefix: ByteArray\">prefix</a>"));
+    }
+
+    #[test]
+    fn long_constructor_lists_are_collapsible() {
+        let module = check(indoc::indoc! {r#"
+            pub type Month {
+              January
+              February
+              March
+              April
+              May
+              June
+              July
+              August
+              September
+              October
+              November
+              December
+            }
+
+            pub type Season {
+              Spring
+              Summer
+              Autumn
+              Winter
+            }
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        let page = &files[0].content;
+
+        assert!(page.contains(
+            "<ul id=\"Month-constructors\" class=\"constructor-list\" data-visible=\"10\">"
+        ));
+        assert!(page.contains(
+            "aria-controls=\"Month-constructors\" aria-expanded=\"true\" data-count=\"12\" hidden>"
+        ));
+        assert!(page.contains("<ul id=\"Season-constructors\" class=\"constructor-list\">"));
+        assert!(!page.contains("aria-controls=\"Season-constructors\""));
+    }
+
+    #[test]
+    fn deprecation_notices() {
+        assert_eq!(
+            split_deprecation(Some(" @deprecated Use `bar` instead.\n Does foo.")),
+            (
+                " Does foo.".to_string(),
+                Some("Use `bar` instead.".to_string())
+            )
+        );
+        assert_eq!(
+            split_deprecation(Some(" ---\n deprecated: Use `bar`.\n ---\n Does foo.")),
+            (" Does foo.".to_string(), Some("Use `bar`.".to_string()))
+        );
+        assert_eq!(
+            split_deprecation(Some(" @deprecated")),
+            (String::new(), Some(String::new()))
+        );
+        assert_eq!(
+            split_deprecation(Some(" Not @deprecated.")),
+            (" Not @deprecated.".to_string(), None)
+        );
+        assert_eq!(
+            split_deprecation(Some(" ---\n Horizontal rule.")),
+            (" ---\n Horizontal rule.".to_string(), None)
+        );
+        assert_eq!(split_deprecation(None), (String::new(), None));
+    }
+
+    #[test]
+    fn deprecated_definitions() {
+        let module = check(indoc::indoc! {r#"
+            /// @deprecated Use `succ` instead.
+            /// Add one.
+            pub fn add_one(n: Int) -> Int {
+              n + 1
+            }
+
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+
+            /// @deprecated
+            pub const a: Int = 1
+
+            pub const b: Int = 2
+        "#});
+
+        let (search_indexes, files) = generate(&config(""), &module);
+        let html = &files[0].content;
+
+        assert!(html.contains(
+            "<span class=\"member-deprecated-badge\">deprecated</span> Use `succ` instead."
+        ));
+        assert!(!html.contains("@deprecated"));
+        assert!(html.find("id=\"succ\"") < html.find("id=\"add_one\""));
+        assert!(html.find("id=\"b\"") < html.find("id=\"a\""));
+
+        let deprecated = search_indexes
+            .iter()
+            .filter(|entry| entry.deprecated)
+            .map(|entry| entry.title.as_str())
+            .collect_vec();
+        assert_eq!(deprecated, vec!["add_one", "a"]);
+    }
+
+    #[test]
+    fn type_parameters() {
+        let module = check(indoc::indoc! {r#"
+            pub opaque type Dict<key, value> {
+              inner: List<Pair<key, value>>,
+            }
+
+            pub type Either<left, right> {
+              Left(left)
+              Right(right)
+            }
+
+            pub type Pairs<key, value> =
+              List<Pair<key, value>>
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        let html = &files[0].content;
+
+        for name in ["Dict", "Either", "Pairs"] {
+            let parameters = if name == "Either" {
+                "left, right"
+            } else {
+                "key, value"
+            };
+            assert!(html.contains(&format!(
+                "{name}<span class=\"member-type-parameters\" title=\"type parameters\">&lt;{parameters}&gt;</span>"
+            )));
+        }
+    }
+
+    #[test]
+    fn alphabetical_functions() {
+        let module = check(indoc::indoc! {r#"
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+
+            pub fn double(n: Int) -> Int {
+              n * 2
+            }
+
+            // ## Comparison
+
+            pub fn max(a: Int, b: Int) -> Int {
+              if a > b { a } else { b }
+            }
+
+            pub fn compare(a: Int, b: Int) -> Ordering {
+              if a < b { Less } else if a > b { Greater } else { Equal }
+            }
+        "#});
+
+        let functions = |extra: &str| {
+            let (search_indexes, files) = generate(&config(extra), &module);
+            let html = &files[0].content;
+            let in_page = ["succ", "double", "Comparison", "max", "compare"]
+                .into_iter()
+                .sorted_by_key(|name| html.find(&format!("id=\"{name}\"")))
+                .collect_vec();
+            let in_search = search_indexes
+                .into_iter()
+                .filter(|entry| entry.url.contains('#'))
+                .map(|entry| entry.title)
+                .collect_vec();
+            (in_page, in_search)
+        };
+
+        assert_eq!(
+            functions(""),
+            (
+                vec!["succ", "double", "Comparison", "max", "compare"],
+                vec!["succ", "double", "max", "compare"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect_vec()
+            )
+        );
+
+        assert_eq!(
+            functions("[docs]\nsort = \"alphabetical\""),
+            (
+                vec!["double", "succ", "Comparison", "compare", "max"],
+                vec!["double", "succ", "compare", "max"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect_vec()
+            )
+        );
+    }
+
+    #[test]
+    fn include_private_definitions() {
+        let module = check(indoc::indoc! {r#"
+            /// Internal helper.
+            fn helper(n: Int) -> Int {
+              n + 1
+            }
+
+            type Secret {
+              Secret(Int)
+            }
+
+            const internal: Int = 42
+        "#});
+
+        let files = |extra: &str| generate_files(&config(extra), vec![&module]);
+
+        let default = files("");
+        assert!(!default
+            .iter()
+            .any(|file| file.path == Path::new("test_module.html")));
+
+        let with_private = files("[docs]\ninclude_private = true\nformat = \"html+json\"");
+
+        let page = file_content(&with_private, "test_module.html");
+        assert!(page.contains("id=\"helper\""));
+        assert!(page.contains("id=\"Secret\""));
+        assert!(page.contains("id=\"internal\""));
+        assert_eq!(page.matches("class=\"member-private\"").count(), 3);
+
+        let json = file_content(&with_private, "test_module.json");
+        assert!(json.contains("\"private\": true"));
+
+        let search_data = file_content(&with_private, "search-data.js");
+        assert!(search_data.contains("\"url\":\"test_module.html#helper\",\"private\":true"));
+    }
+
+    #[test]
+    fn hidden_definitions() {
+        let module = check(indoc::indoc! {r#"
+            /// Add one.
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+
+            /// Only public for other modules of the project.
+            /// @hidden
+            pub fn internal(n: Int) -> Int {
+              n - 1
+            }
+        "#});
+
+        let (indexes, files) = generate(&config(""), &module);
+
+        assert!(indexes.iter().any(|entry| entry.title == "succ"));
+        assert!(!indexes.iter().any(|entry| entry.title == "internal"));
+
+        let page = &files[0].content;
+        assert!(page.contains("id=\"succ\""));
+        assert!(!page.contains("internal"));
+
+        let module = check(indoc::indoc! {r#"
+            /// @hidden
+            pub fn internal(n: Int) -> Int {
+              n - 1
+            }
+        "#});
+
+        assert!(generate_modules_links(&[&module], false, false, DocLayout::Flat).is_empty());
+
+        let (indexes, _) = generate(&config(""), &module);
+        assert!(indexes.is_empty());
+    }
+
+    #[test]
+    fn signatures_link_to_documented_types() {
+        let mut project = TestProject::new();
+
+        let mut types = project.parse(indoc::indoc! {r#"
+            pub type Token {
+              Token(ByteArray)
+            }
+        "#});
+        types.name = "foo/types".to_string();
+        types.ast.name = "foo/types".to_string();
+        let types = project.check(types);
+
+        let module = project.check(project.parse(indoc::indoc! {r#"
+            use foo/types.{Token}
+
+            pub type Wallet {
+              tokens: List<Token>,
+            }
+
+            pub fn balance(wallet: Wallet, token: Token) -> Option<Int> {
+              todo
+            }
+        "#}));
+
+        let page = file_content(
+            &generate_files(&config(""), vec![&types, &module]),
+            "test_module.html",
+        );
+
+        assert!(page.contains(
+            "balance(wallet: <a href=\"#Wallet\">Wallet</a>, token: <a href=\"./foo/types.html#Token\">Token</a>) -&gt; Option&lt;Int&gt;"
+        ));
+    }
+
+    #[test]
+    fn intra_doc_links() {
+        let mut project = TestProject::new();
+
+        let mut types = project.parse(indoc::indoc! {r#"
+            pub type Token {
+              Token(ByteArray)
+            }
+        "#});
+        types.name = "foo/types".to_string();
+        types.ast.name = "foo/types".to_string();
+        let types = project.check(types);
+
+        let module = project.check(project.parse(indoc::indoc! {r#"
+            /// Like [succ], for a [Token] or a [types.Token.Token]; see also [foo/types]
+            /// and [nope].
+            pub fn balance() -> Int {
+              todo
+            }
+
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#}));
+
+        let page = file_content(
+            &generate_files(&config(""), vec![&types, &module]),
+            "test_module.html",
+        );
+
+        assert!(page.contains("<a href=\"#succ\">succ</a>"));
+        assert!(page.contains("<a href=\"./foo/types.html#Token\">Token</a>"));
+        assert!(page.contains("<a href=\"./foo/types.html#Token.Token\">types.Token.Token</a>"));
+        assert!(page.contains("<a href=\"./foo/types.html\">foo/types</a>"));
+        assert!(page.contains("and [nope]."));
+    }
+
+    #[test]
+    fn nested_layout() {
+        let mut project = TestProject::new();
+
+        let mut types = project.parse(indoc::indoc! {r#"
+            pub type Token {
+              Token(ByteArray)
+            }
+        "#});
+        types.name = "foo/types".to_string();
+        types.ast.name = "foo/types".to_string();
+        let types = project.check(types);
+
+        let module = project.check(project.parse(indoc::indoc! {r#"
+            use foo/types.{Token}
+
+            /// See also [foo/types].
+            pub fn balance(token: Token) -> Int {
+              todo
+            }
+        "#}));
+
+        let files = generate_files(
+            &config("[docs]\nlayout = \"nested\"\nbase_url = \"https://example.com\""),
+            vec![&types, &module],
+        );
+
+        let find = |path: &str| {
+            files
+                .iter()
+                .find(|file| file.path == Path::new(path))
+                .map(|file| file.content.as_str())
+        };
+
+        assert!(find("test_module.html").is_none());
+        assert!(find("foo/types.html").is_none());
+        assert!(find("foo/types/index.html").is_some());
+
+        let page = find("test_module/index.html").expect("no module page");
+        assert!(page.contains("<a href=\"../all.html\">All symbols</a>"));
+        assert!(page.contains("<a href=\"../foo/types/#Token\">Token</a>"));
+        assert!(page.contains("<a href=\"../foo/types/\">foo/types</a>"));
+        assert!(page.contains("<li data-indent=\"0\" data-current><a href=\"../test_module/\">"));
+        assert!(page.contains("<link rel=\"canonical\" href=\"https://example.com/test_module/\""));
+
+        assert!(find("index.html")
+            .expect("no index page")
+            .contains("<a href=\"./test_module/\">"));
+
+        let search_data: json::Value =
+            json::from_str(find("search-data.json").expect("no search data")).unwrap();
+        let urls = search_data
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["url"].as_str().unwrap())
+            .collect_vec();
+        assert!(urls.contains(&"test_module/"));
+        assert!(urls.contains(&"test_module/#balance"));
+        assert!(urls.contains(&"foo/types/#Token"));
+
+        let sitemap = find("sitemap.xml").expect("no sitemap");
+        assert!(sitemap.contains("<loc>https://example.com/index.html</loc>"));
+        assert!(sitemap.contains("<loc>https://example.com/test_module/</loc>"));
+    }
+
+    #[test]
+    fn search_summaries() {
+        let module = check(indoc::indoc! {r#"
+            //// Some **arithmetic**. And more.
+
+            /// Adds _one_ to `n`. Never overflows.
+            ///
+            /// ```aiken
+            /// succ(1) == 2
+            /// ```
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+
+            pub const zero: Int = 0
+        "#});
+
+        let (search_indexes, _) = generate(&config(""), &module);
+
+        let summary = |title: &str| {
+            search_indexes
+                .iter()
+                .find(|entry| entry.title == title)
+                .map(|entry| entry.summary.as_str())
+        };
+        assert_eq!(summary("test_module"), Some("Some arithmetic."));
+        assert_eq!(summary("succ"), Some("Adds one to n."));
+        assert_eq!(summary("zero"), Some(""));
+
+        let succ = search_indexes
+            .iter()
+            .find(|entry| entry.title == "succ")
+            .unwrap();
+        assert!(succ.content.contains("succ(1) == 2"));
+
+        let json = json::to_string(&search_indexes).unwrap();
+        assert!(json.contains("\"summary\":\"Adds one to n.\""));
+        assert_eq!(json.matches("\"summary\"").count(), 2);
+    }
+
+    #[test]
+    fn search_indexes_deduplication() {
+        let mut project = TestProject::new();
+
+        let mut other = project.parse(indoc::indoc! {r#"
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#});
+        other.name = "foo/other".to_string();
+        other.ast.name = "foo/other".to_string();
+        let other = project.check(other);
+
+        let module = project.check(project.parse(indoc::indoc! {r#"
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+        "#}));
+
+        let files = generate_files(&config(""), vec![&other, &module]);
+        let search_data: json::Value =
+            json::from_str(&file_content(&files, "search-data.json")).unwrap();
+        let urls = search_data
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry["title"] == "succ")
+            .map(|entry| entry["url"].as_str().unwrap())
+            .collect_vec();
+        assert_eq!(urls, vec!["foo/other.html#succ", "test_module.html#succ"]);
+
+        let entry = |doc: &str, title: &str, url: &str| SearchIndex {
+            doc: doc.to_string(),
+            title: title.to_string(),
+            content: String::new(),
+            summary: String::new(),
+            url: url.to_string(),
+            private: false,
+            deprecated: false,
+            since: None,
+            deprecated_in: None,
+        };
+        let urls = dedup_search_indexes(
+            vec![
+                entry("foo", "foo", "foo.html"),
+                entry("foo", "bar", "other.html#bar"),
+                entry("foo", "foo", "foo.html#foo"),
+                entry("foo", "bar", "foo.html#bar"),
+                entry("foo", "bar", "another.html#bar"),
+            ],
+            DocLayout::Flat,
+        )
+        .into_iter()
+        .map(|entry| entry.url)
+        .collect_vec();
+        assert_eq!(urls, vec!["foo.html", "foo.html#bar", "foo.html#foo"]);
+    }
+
+    #[test]
+    fn related_functions_from_co_usage() {
+        let module = check(indoc::indoc! {r#"
+            pub fn double(n: Int) -> Int {
+              n * 2
+            }
+
+            pub fn succ(n: Int) -> Int {
+              n + 1
+            }
+
+            pub fn unrelated() -> Int {
+              42
+            }
+
+            fn both(n: Int) -> Int {
+              double(succ(n))
+            }
+        "#});
+
+        let index = related::RelatedIndex::new(&[&module]);
+
+        let name = |keys: Vec<&related::FunctionKey>| {
+            keys.into_iter().map(|(_, name)| name.clone()).collect_vec()
+        };
+
+        assert_eq!(name(index.related("test_module", "double")), vec!["succ"]);
+        assert_eq!(name(index.related("test_module", "succ")), vec!["double"]);
+        assert!(index.related("test_module", "unrelated").is_empty());
+    }
+
+    #[test]
+    fn slug_anchors() {
+        let slug = |name| to_anchor(DocAnchors::Slugs, name);
+        assert_eq!(slug("from_int"), "from_int");
+        assert_eq!(slug("ValidityRange"), "validityrange");
+        assert_eq!(slug("Working with lists"), "working-with-lists");
+        assert_eq!(slug("  Pairs & Tuples (2/2)  "), "pairs--tuples-22");
+        assert_eq!(slug("non-empty_list?"), "non-empty_list");
+        assert_eq!(slug("Éléments"), "éléments");
+
+        assert_eq!(
+            to_anchor(DocAnchors::Names, "Working with lists"),
+            "Working with lists"
+        );
+    }
+
+    #[test]
+    fn slug_anchors_in_pages_and_search_index() {
+        let module = check(indoc::indoc! {r#"
+            // ## Some Section
+
+            pub type Outcome {
+              Ok
+              Ko
+            }
+
+            pub fn from_int(n: Int) -> Outcome {
+              if n > 0 {
+                Ok
+              } else {
+                Ko
+              }
+            }
+        "#});
+
+        let (indexes, files) = generate(&config("[docs]\nanchors = \"slugs\""), &module);
+
+        let urls = indexes.iter().map(|index| index.url.as_str()).collect_vec();
+        assert!(urls.contains(&"test_module.html#outcome"));
+        assert!(urls.contains(&"test_module.html#from_int"));
+
+        let html = &files[0].content;
+        assert!(html.contains(r#"id="outcome""#));
+        assert!(html.contains(r#"id="some-section""#));
+    }
+
+    #[test]
+    fn type_anchors_never_collide_with_values() {
+        let module = check(indoc::indoc! {r#"
+            /// See [Outcome] and [outcome].
+            pub type Outcome {
+              Ok
+              Ko
+            }
+
+            pub fn outcome(n: Int) -> Outcome {
+              if n > 0 {
+                Ok
+              } else {
+                Ko
+              }
+            }
+        "#});
+
+        let (_, files) = generate(&config(""), &module);
+        let html = &files[0].content;
+        assert!(html.contains(r#"id="Outcome""#));
+        assert!(html.contains(r#"id="outcome""#));
+
+        let config = config("[docs]\nanchors = \"slugs\"");
+        let (indexes, _) = generate(&config, &module);
+        let urls = indexes.iter().map(|index| index.url.as_str()).collect_vec();
+        assert!(urls.contains(&"test_module.html#outcome-type"));
+        assert!(urls.contains(&"test_module.html#outcome-type.ok"));
+        assert!(urls.contains(&"test_module.html#outcome"));
+
+        let html = file_content(&generate_files(&config, vec![&module]), "test_module.html");
+        assert!(html.contains(r#"id="outcome-type""#));
+        assert!(html.contains(r##"<a href="#outcome-type">Outcome</a>"##));
+        assert!(html.contains(r##"<a href="#outcome">outcome</a>"##));
+        assert!(html.contains(r#"id="module-types""#));
+        assert!(html.contains(r#"id="module-functions""#));
+        assert!(html.contains(r##"<a class="back-to-top" href="#module-name""##));
+    }
+
+    #[test]
+    fn function_arguments_table() {
+        let module = check(indoc::indoc! {r#"
+            /// Add two numbers.
+            pub fn add(
+              /// Left operand.
+              left: Int,
+              right,
+            ) -> Int {
+              left + right
+            }
+
+            pub fn zero() -> Int {
+              0
+            }
+        "#});
+
+        let (_, files) = generate(&config("[docs]\nformat = \"html+json\""), &module);
+
+        let json: json::Value = json::from_str(&file_content(&files, "test_module.json")).unwrap();
+        assert_eq!(json["functions"][0]["arguments"][0]["label"], "left");
+        assert_eq!(json["functions"][0]["arguments"][0]["tipo"], "Int");
+        assert_eq!(
+            json["functions"][0]["arguments"][0]["raw_documentation"],
+            " Left operand."
+        );
+        assert_eq!(json["functions"][0]["arguments"][1]["tipo"], "Int");
+        assert_eq!(json["functions"][1]["arguments"], json::json!([]));
+
+        let html = &file_content(&files, "test_module.html");
+        assert_eq!(html.matches(r#"class="member-arguments""#).count(), 1);
+
+        // Undocumented arguments are listed all the same, with an empty description.
+        let right = &html[html.find("<td><code>right</code></td>").unwrap()..];
+        let row = &right[..right.find("</tr>").unwrap()];
+        assert!(row.contains(r#"<td><code class="hljs aiken">Int</code></td>"#));
+        assert!(row.contains(r#"<td class="rendered-markdown"></td>"#));
+    }
+
+    #[test]
+    fn dependencies_page() {
+        let mut project = TestProject::new();
+
+        let mut types = project.parse(indoc::indoc! {r#"
+            pub type Token {
+              Token(ByteArray)
+            }
+        "#});
+        types.name = "foo/types".to_string();
+        types.ast.name = "foo/types".to_string();
+        let types = project.check(types);
+
+        let module = project.check(project.parse(indoc::indoc! {r#"
+            use foo/types.{Token}
+
+            pub fn balance(token: Token) -> Int {
+              todo
+            }
+        "#}));
+
+        let files = generate_files(&config(""), vec![&types, &module]);
+
+        let page = file_content(&files, "dependencies.html");
+
+        let section = |id: &str| page.find(&format!("<h2 id=\"{id}\">")).unwrap();
+        let link =
+            |module: &str| format!("<li><a href=\"{module}.html\"><code>{module}</code></a></li>");
+
+        let types_section = section("foo/types");
+        let module_section = section("test_module");
+        assert!(types_section < module_section);
+
+        let imported_by = page.find(&link("test_module")).unwrap();
+        assert!(types_section < imported_by && imported_by < module_section);
+        assert!(page[module_section..].contains(&link("foo/types")));
+
+        let all = |files: &[DocFile]| {
+            file_content(files, "all.html")
+                .contains("<a href=\"dependencies.html\">Dependencies</a>")
+        };
+        assert!(all(&files));
+
+        let files = generate_files(&config(""), vec![&types]);
+        assert!(!files
+            .iter()
+            .any(|file| file.path == Path::new("dependencies.html")));
+        assert!(!all(&files));
+    }
 }
@@ -0,0 +1,187 @@
+use super::{DocFile, DocLink, DocModule};
+use crate::config::{Config, DocMembers};
+use std::{fmt::Write, path::PathBuf};
+
+/// Name of the single markdown file holding the API reference of all modules.
+pub const FILE_NAME: &str = "API.md";
+
+/// Gather the reference of every module, in the given order, into a single markdown document.
+pub(super) fn generate(config: &Config, modules: &[(String, String)]) -> DocFile {
+    let mut content = format!(
+        "# {} - API reference\n\nVersion {}\n\n",
+        config.name, config.version
+    );
+
+    for (name, _) in modules {
+        let _ = writeln!(content, "- [{name}](#{name})");
+    }
+
+    for (_, reference) in modules {
+        content.push('\n');
+        content.push_str(reference);
+    }
+
+    DocFile {
+        path: PathBuf::from(FILE_NAME),
+        content,
+    }
+}
+
+/// The reference of a single module, as a markdown section. Every member gets an explicit
+/// anchor, qualified by its module, so that cross-references work across the whole document.
+pub(super) fn module(module: &DocModule<'_>, members_order: &[DocMembers]) -> String {
+    let mut content = format!("<a id=\"{}\"></a>\n\n## {}\n\n", module.name, module.name);
+
+    push_documentation(&mut content, &module.documentation);
+
+    for members in members_order {
+        match members {
+            DocMembers::Validators if !module.validators.is_empty() => {
+                content.push_str("### Validators\n\n");
+                for validator in module.validators {
+                    push_member(
+                        &mut content,
+                        module.name,
+                        &validator.anchor,
+                        &validator.name,
+                        false,
+                    );
+                    push_code(&mut content, &validator.signature);
+                    push_documentation(&mut content, &validator.raw_documentation);
+                    for handler in validator.handlers.iter() {
+                        let _ = writeln!(
+                            content,
+                            "<a id=\"{}\"></a>\n\n##### `{}`\n",
+                            qualified(module.name, &handler.anchor),
+                            handler.name
+                        );
+                        push_code(&mut content, &handler.signature);
+                        push_documentation(&mut content, &handler.raw_documentation);
+                    }
+                }
+            }
+            DocMembers::Types if !module.types.is_empty() => {
+                content.push_str("### Types\n\n");
+                for tipo in module.types {
+                    push_member(
+                        &mut content,
+                        module.name,
+                        &tipo.anchor,
+                        &tipo.name,
+                        tipo.private,
+                    );
+                    push_code(&mut content, &tipo.definition);
+                    push_deprecation(&mut content, tipo.deprecated.as_deref());
+                    push_documentation(&mut content, &tipo.raw_documentation);
+                    for constructor in tipo.constructors.iter() {
+                        if constructor.raw_documentation.is_empty() {
+                            continue;
+                        }
+                        let _ = writeln!(
+                            content,
+                            "<a id=\"{}\"></a>\n\n##### `{}`\n",
+                            qualified(module.name, &constructor.anchor),
+                            constructor.name
+                        );
+                        push_documentation(&mut content, &constructor.raw_documentation);
+                    }
+                }
+            }
+            DocMembers::Constants if !module.constants.is_empty() => {
+                content.push_str("### Constants\n\n");
+                for constant in module.constants {
+                    push_member(
+                        &mut content,
+                        module.name,
+                        &constant.anchor,
+                        &constant.name,
+                        constant.private,
+                    );
+                    push_code(&mut content, &constant.definition);
+                    push_deprecation(&mut content, constant.deprecated.as_deref());
+                    push_documentation(&mut content, &constant.raw_documentation);
+                }
+            }
+            DocMembers::Functions if !module.functions.is_empty() => {
+                content.push_str("### Functions\n\n");
+                for function in module.functions.iter() {
+                    push_member(
+                        &mut content,
+                        module.name,
+                        &function.anchor,
+                        &function.name,
+                        function.private,
+                    );
+                    push_code(&mut content, &function.signature);
+                    push_deprecation(&mut content, function.deprecated.as_deref());
+                    push_documentation(&mut content, &function.raw_documentation);
+                    if !function.related.is_empty() {
+                        let _ = writeln!(
+                            content,
+                            "See also: {}\n",
+                            function
+                                .related
+                                .iter()
+                                .map(|link| format!("[`{}`](#{})", link.name, to_anchor(link)))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        );
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    content
+}
+
+fn qualified(module: &str, anchor: &str) -> String {
+    format!("{module}.{anchor}")
+}
+
+/// Links in the HTML website point at '{module}.html#{anchor}', or '{module}/#{anchor}' with a
+/// nested layout, which become anchors within the single markdown document.
+fn to_anchor(link: &DocLink) -> String {
+    let module = |page: &str| {
+        page.strip_suffix(".html")
+            .or_else(|| page.strip_suffix('/'))
+            .unwrap_or(page)
+            .to_string()
+    };
+
+    match link.path.split_once('#') {
+        Some((page, anchor)) => qualified(&module(page), anchor),
+        None => module(&link.path),
+    }
+}
+
+fn push_member(content: &mut String, module: &str, anchor: &str, name: &str, private: bool) {
+    let _ = writeln!(
+        content,
+        "<a id=\"{}\"></a>\n\n#### `{name}`{}\n",
+        qualified(module, anchor),
+        if private { " _(private)_" } else { "" }
+    );
+}
+
+fn push_code(content: &mut String, code: &str) {
+    let _ = writeln!(content, "```aiken\n{code}\n```\n");
+}
+
+fn push_deprecation(content: &mut String, deprecated: Option<&str>) {
+    match deprecated {
+        Some("") => content.push_str("> **Deprecated**\n\n"),
+        Some(message) => {
+            let _ = writeln!(content, "> **Deprecated**: {message}\n");
+        }
+        None => (),
+    }
+}
+
+fn push_documentation(content: &mut String, documentation: &str) {
+    let documentation = documentation.trim();
+    if !documentation.is_empty() {
+        let _ = writeln!(content, "{documentation}\n");
+    }
+}
@@ -0,0 +1,175 @@
+use super::DocFile;
+use regex::Regex;
+use serde::Serialize;
+use serde_json as json;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+/// Differences between two documentation builds, meant for reviewing documentation changes
+/// (e.g. as annotations in a CI pipeline).
+///
+/// Public symbols are only compared when both builds include the per-module JSON files.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DocsDiff {
+    pub added_pages: Vec<PathBuf>,
+    pub removed_pages: Vec<PathBuf>,
+    pub changed_pages: Vec<PathBuf>,
+    pub added_symbols: Vec<String>,
+    pub removed_symbols: Vec<String>,
+}
+
+impl DocsDiff {
+    pub fn new(before: &[DocFile], after: &[DocFile]) -> Self {
+        let before = pages(before);
+        let after = pages(after);
+
+        let mut diff = DocsDiff::default();
+
+        for (path, content) in after.iter() {
+            match before.get(path) {
+                None => diff.added_pages.push(path.clone()),
+                Some(previous) if previous != content => diff.changed_pages.push(path.clone()),
+                Some(_) => (),
+            }
+        }
+
+        diff.removed_pages = before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned()
+            .collect();
+
+        let symbols_before = symbols(&before);
+        let symbols_after = symbols(&after);
+
+        diff.added_symbols = symbols_after.difference(&symbols_before).cloned().collect();
+
+        diff.removed_symbols = symbols_before.difference(&symbols_after).cloned().collect();
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &DocsDiff::default()
+    }
+
+    pub fn to_json(&self) -> String {
+        json::to_string_pretty(self).expect("docs diff serialization")
+    }
+}
+
+/// Pages of a build indexed by path, without the cache-busting parameters and the build date
+/// of the footer derived from the build time, so that rebuilding identical docs doesn't show as
+/// a change.
+fn pages(files: &[DocFile]) -> BTreeMap<PathBuf, String> {
+    let cache_busting = Regex::new(r"\?v=\d+").expect("invalid cache-busting regex");
+    let build_time = Regex::new(r#"<span class="build-time"[^>]*>.*?</span>"#)
+        .expect("invalid build time regex");
+
+    files
+        .iter()
+        .map(|file| {
+            let content = cache_busting.replace_all(&file.content, "");
+            (
+                file.path.clone(),
+                build_time.replace_all(&content, "").to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Public symbols, qualified by their module name, found in per-module JSON files.
+fn symbols(pages: &BTreeMap<PathBuf, String>) -> BTreeSet<String> {
+    let mut symbols = BTreeSet::new();
+
+    for (path, content) in pages.iter() {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+
+        let Ok(module) = json::from_str::<json::Value>(content) else {
+            continue;
+        };
+
+        let Some(module_name) = module["name"].as_str() else {
+            continue;
+        };
+
+        for kind in ["functions", "types", "constants", "validators"] {
+            for symbol in module[kind].as_array().into_iter().flatten() {
+                if let Some(name) = symbol["name"].as_str() {
+                    symbols.insert(format!("{module_name}.{name}"));
+                }
+            }
+        }
+    }
+
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, content: &str) -> DocFile {
+        DocFile {
+            path: PathBuf::from(path),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_pages() {
+        let before = vec![
+            file("index.html", "<script src=\"js/index.js?v=1700000000\">"),
+            file("a.html", "a"),
+            file("b.html", "b"),
+        ];
+
+        let after = vec![
+            file("index.html", "<script src=\"js/index.js?v=1800000000\">"),
+            file("a.html", "a (changed)"),
+            file("c.html", "c"),
+        ];
+
+        let diff = DocsDiff::new(&before, &after);
+
+        assert_eq!(diff.added_pages, vec![PathBuf::from("c.html")]);
+        assert_eq!(diff.removed_pages, vec![PathBuf::from("b.html")]);
+        assert_eq!(diff.changed_pages, vec![PathBuf::from("a.html")]);
+        assert!(DocsDiff::new(&before, &before).is_empty());
+
+        let footer = |timestamp: u64, date: &str| {
+            file(
+                "foo.html",
+                &format!(
+                    "<footer>Generated with Aiken &middot; <span class=\"build-time\" data-timestamp=\"{timestamp}\">Built on <time datetime=\"{date}\">{date}</time></span></footer>"
+                ),
+            )
+        };
+
+        let first_build = vec![footer(1_700_000_000, "2023-11-14")];
+        let second_build = vec![footer(1_800_000_000, "2027-01-15")];
+        assert!(DocsDiff::new(&first_build, &second_build).is_empty());
+    }
+
+    #[test]
+    fn diff_symbols() {
+        let before = vec![file(
+            "foo.json",
+            r#"{ "name": "foo", "functions": [{ "name": "bar" }, { "name": "baz" }], "types": [], "constants": [] }"#,
+        )];
+
+        let after = vec![file(
+            "foo.json",
+            r#"{ "name": "foo", "functions": [{ "name": "bar" }], "types": [{ "name": "Qux" }], "constants": [] }"#,
+        )];
+
+        let diff = DocsDiff::new(&before, &after);
+
+        assert_eq!(diff.added_symbols, vec!["foo.Qux".to_string()]);
+        assert_eq!(diff.removed_symbols, vec!["foo.baz".to_string()]);
+    }
+}
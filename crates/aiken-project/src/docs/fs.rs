@@ -0,0 +1,113 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Disk reads performed while generating documentation. Abstracting them away allows to
+/// generate documentation where there's no actual filesystem (e.g. in a browser).
+pub trait FileSystem {
+    /// Content of the file at the given path, if any.
+    fn read_to_string(&self, path: &Path) -> Option<String>;
+
+    /// Raw content of the file at the given path, if any; e.g. that of an image.
+    fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        self.read_to_string(path).map(String::into_bytes)
+    }
+
+    /// Last modification time of the file at the given path, if known.
+    fn modified(&self, path: &Path) -> Option<SystemTime>;
+}
+
+/// Reads from the local filesystem.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NativeFileSystem;
+
+#[cfg(not(target_family = "wasm"))]
+impl FileSystem for NativeFileSystem {
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        std::fs::read(path).ok()
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
+
+/// A filesystem without any file, for targets that have no filesystem.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoFileSystem;
+
+impl FileSystem for NoFileSystem {
+    fn read_to_string(&self, _path: &Path) -> Option<String> {
+        None
+    }
+
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+}
+
+/// Files held in memory, taking precedence over those of another filesystem; e.g. to generate
+/// documentation from a README which is being edited and isn't saved yet. Files are looked up by
+/// the very path they're read from, that is, joined to the project's root.
+pub struct OverlayFileSystem<'a> {
+    files: BTreeMap<PathBuf, String>,
+    fallback: &'a dyn FileSystem,
+}
+
+impl<'a> OverlayFileSystem<'a> {
+    pub fn new(fallback: &'a dyn FileSystem) -> Self {
+        OverlayFileSystem {
+            files: BTreeMap::new(),
+            fallback,
+        }
+    }
+
+    /// Hold the given content in memory, as that of the file at the given path; which must be
+    /// joined to the project's root (e.g. 'root.join("README.md")') like the paths read are.
+    pub fn with_file(mut self, path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        self.files.insert(path.into(), content.into());
+        self
+    }
+}
+
+impl FileSystem for OverlayFileSystem<'_> {
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        match self.files.get(path) {
+            Some(content) => Some(content.clone()),
+            None => self.fallback.read_to_string(path),
+        }
+    }
+
+    fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        match self.files.get(path) {
+            Some(content) => Some(content.clone().into_bytes()),
+            None => self.fallback.read(path),
+        }
+    }
+
+    /// Files held in memory have no known modification time.
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        if self.files.contains_key(path) {
+            None
+        } else {
+            self.fallback.modified(path)
+        }
+    }
+}
+
+/// The filesystem available on the current target.
+#[cfg(not(target_family = "wasm"))]
+pub type DefaultFileSystem = NativeFileSystem;
+
+/// The filesystem available on the current target.
+#[cfg(target_family = "wasm")]
+pub type DefaultFileSystem = NoFileSystem;
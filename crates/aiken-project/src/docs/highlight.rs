@@ -0,0 +1,118 @@
+use super::escape_html_content;
+use aiken_lang::parser::{lexer, token::Token};
+
+/// Highlight a snippet of Aiken code as HTML, using the same classes as highlight.js so that
+/// its themes apply. Nothing is returned when the snippet can't be tokenized.
+pub fn aiken(code: &str) -> Option<String> {
+    let lexer::LexInfo { tokens, extra } = lexer::run(code).ok()?;
+
+    let comments = extra
+        .module_comments
+        .iter()
+        .chain(extra.doc_comments.iter())
+        .chain(extra.comments.iter())
+        .map(|span| {
+            // Comment spans start after their leading slashes.
+            let start = code[..span.start].trim_end_matches('/').len();
+            (start, span.end, "hljs-comment")
+        });
+
+    let mut scopes = tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(ix, (token, span))| {
+            let next = tokens.get(ix + 1).map(|(token, _)| token);
+            let previous = ix.checked_sub(1).map(|ix| &tokens[ix].0);
+            scope(token, previous, next).map(|scope| (span.start, span.end, scope))
+        })
+        .chain(comments)
+        .collect::<Vec<_>>();
+
+    scopes.sort_by_key(|(start, _, _)| *start);
+
+    let mut html = String::with_capacity(code.len() * 2);
+    let mut cursor = 0;
+    for (start, end, scope) in scopes {
+        if start < cursor || end > code.len() {
+            continue;
+        }
+        html.push_str(&escape_html_content(&code[cursor..start]));
+        html.push_str(&format!(
+            "<span class=\"{scope}\">{}</span>",
+            escape_html_content(&code[start..end])
+        ));
+        cursor = end;
+    }
+    html.push_str(&escape_html_content(&code[cursor..]));
+
+    Some(html)
+}
+
+/// Class of a token, as given by the highlight.js grammar of Aiken. Punctuation is left as-is.
+fn scope(token: &Token, previous: Option<&Token>, next: Option<&Token>) -> Option<&'static str> {
+    match token {
+        Token::Name { .. } => Some(match (previous, next) {
+            (Some(Token::Fn), _) => "hljs-title function_",
+            (_, Some(Token::LeftParen)) => "hljs-title function_ invoke__",
+            (_, Some(Token::Colon)) => "hljs-symbol",
+            _ => "hljs-variable",
+        }),
+        Token::UpName { .. } => Some("hljs-title"),
+        Token::DiscardName { .. } => Some("hljs-comment"),
+        Token::Int { .. } => Some("hljs-number"),
+        Token::String { .. } | Token::ByteString { .. } => Some("hljs-string"),
+        Token::As
+        | Token::Const
+        | Token::Fn
+        | Token::If
+        | Token::Else
+        | Token::Fail
+        | Token::Once
+        | Token::Expect
+        | Token::Is
+        | Token::Let
+        | Token::Opaque
+        | Token::Pub
+        | Token::Use
+        | Token::Test
+        | Token::Todo
+        | Token::Type
+        | Token::When
+        | Token::Trace
+        | Token::Validator
+        | Token::Via
+        | Token::And
+        | Token::Or => Some("hljs-keyword"),
+        Token::Plus
+        | Token::Minus
+        | Token::NewLineMinus
+        | Token::Star
+        | Token::Slash
+        | Token::Less
+        | Token::Greater
+        | Token::LessEqual
+        | Token::GreaterEqual
+        | Token::Percent
+        | Token::PlusDot
+        | Token::MinusDot
+        | Token::StarDot
+        | Token::SlashDot
+        | Token::LessDot
+        | Token::GreaterDot
+        | Token::LessEqualDot
+        | Token::GreaterEqualDot
+        | Token::Bang
+        | Token::Equal
+        | Token::EqualEqual
+        | Token::NotEqual
+        | Token::Vbar
+        | Token::VbarVbar
+        | Token::AmperAmper
+        | Token::Pipe
+        | Token::NewLinePipe
+        | Token::RArrow
+        | Token::LArrow
+        | Token::DotDot => Some("hljs-operator"),
+        _ => None,
+    }
+}
@@ -0,0 +1,217 @@
+use super::{
+    dedup_search_indexes, fs, generate_changelog, generate_dependencies_page,
+    generate_index_of_symbols, generate_module, generate_readme, generate_search_data,
+    generate_search_page, generate_static_assets, last_modified, new_timestamp, without_excluded,
+    Context, DocFailure, DocFile, SearchIndex,
+};
+use crate::{config::Config, CheckedModule};
+use pallas_crypto::hash::Hasher;
+use serde_json as json;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+/// Documentation regenerated as modules change, e.g. from an editor. Only the pages of modules
+/// whose source changed since the previous generation are rendered again; the search entries of
+/// every module are kept, so that the search data of the whole project can be rewritten.
+#[derive(Default)]
+pub struct DocCache {
+    /// Fingerprint of the configuration and of what all pages share (links, anchors, sidebar...).
+    context: String,
+    /// Fingerprint of the source of each module, alongside its search entries.
+    modules: BTreeMap<String, (String, Vec<SearchIndex>)>,
+}
+
+impl DocCache {
+    /// Generate the pages of the modules which changed since the last call, as well as the
+    /// search data and pages listing the definitions of all modules. Every page is generated on
+    /// the first call, or whenever the configuration or what pages share changes: e.g. when
+    /// modules are added or removed, or definitions other modules may link to. Unlike
+    /// 'generate_all', neither the API reference, JSON files, sitemap nor manifest are generated.
+    pub fn regenerate(
+        &mut self,
+        fs: &dyn fs::FileSystem,
+        root: &Path,
+        config: &Config,
+        modules: Vec<&CheckedModule>,
+    ) -> (Vec<DocFile>, Vec<DocFailure>) {
+        let timestamp = new_timestamp();
+
+        let mut modules = without_excluded(config, modules);
+        let mut failures: Vec<DocFailure> = vec![];
+
+        // As for 'generate_all', modules which fail to render are left out altogether and the
+        // remaining ones rendered again; which the change of context takes care of.
+        let (context, fresh, mut output_files) = loop {
+            let context = Context::new(config, &modules);
+
+            let documented = modules
+                .iter()
+                .filter(|module| !module.skip_doc_generation())
+                .collect::<Vec<_>>();
+
+            let names = documented
+                .iter()
+                .map(|module| &module.name)
+                .collect::<BTreeSet<_>>();
+
+            let fingerprint = Hasher::<256>::hash(
+                format!(
+                    "{context:?}{}",
+                    json::to_string(config).expect("config is always serializable")
+                )
+                .as_bytes(),
+            )
+            .to_string();
+
+            if fingerprint != self.context || !names.into_iter().eq(self.modules.keys()) {
+                self.context = fingerprint;
+                self.modules.clear();
+            }
+
+            let fresh = self.modules.is_empty();
+
+            let mut output_files: Vec<DocFile> = vec![];
+            let mut failed: Vec<DocFailure> = vec![];
+
+            for module in documented {
+                let fingerprint = Hasher::<256>::hash(module.code.as_bytes()).to_string();
+
+                if self
+                    .modules
+                    .get(&module.name)
+                    .map_or(false, |(known, _)| known == &fingerprint)
+                {
+                    continue;
+                }
+
+                let generated = generate_module(
+                    root,
+                    config,
+                    module,
+                    &context.modules_links,
+                    &context.unstable_modules_links,
+                    &context.related,
+                    &context.types,
+                    &context.symbols,
+                    &context.source,
+                    &timestamp,
+                    &last_modified(fs, module, &timestamp),
+                );
+
+                match generated {
+                    Ok((indexes, files, _, _)) => {
+                        if !indexes.is_empty() {
+                            output_files.extend(files);
+                        }
+                        self.modules
+                            .insert(module.name.clone(), (fingerprint, indexes));
+                    }
+                    Err(error) => {
+                        self.modules.remove(&module.name);
+                        failed.push(DocFailure {
+                            path: module.input_path.clone(),
+                            module: module.name.clone(),
+                            reason: error.to_string(),
+                        });
+                    }
+                }
+            }
+
+            if failed.is_empty() {
+                break (context, fresh, output_files);
+            }
+
+            modules.retain(|module| !failed.iter().any(|failure| failure.module == module.name));
+            failures.extend(failed);
+        };
+
+        let search_indexes = dedup_search_indexes(
+            self.modules
+                .values()
+                .flat_map(|(_, indexes)| indexes.iter().cloned())
+                .collect(),
+            config.docs.layout,
+        );
+
+        // Modules without any documented definition have no page, as in 'generate_all'.
+        let documented = modules
+            .iter()
+            .filter(|module| {
+                self.modules
+                    .get(&module.name)
+                    .map_or(false, |(_, indexes)| !indexes.is_empty())
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        let pages = [
+            generate_index_of_symbols(
+                config,
+                &context.modules_links,
+                &context.unstable_modules_links,
+                &context.source,
+                &timestamp,
+                &search_indexes,
+                &documented,
+            )
+            .map(Some),
+            generate_search_page(
+                config,
+                &context.modules_links,
+                &context.unstable_modules_links,
+                &context.source,
+                &timestamp,
+                &search_indexes,
+            )
+            .map(Some),
+            generate_changelog(
+                config,
+                &context.modules_links,
+                &context.unstable_modules_links,
+                &context.source,
+                &timestamp,
+                &search_indexes,
+            ),
+            generate_dependencies_page(
+                config,
+                &context.modules_links,
+                &context.unstable_modules_links,
+                &context.source,
+                &timestamp,
+                &documented,
+            ),
+        ];
+
+        for page in pages {
+            match page {
+                Ok(file) => output_files.extend(file),
+                Err(failure) => failures.push(failure),
+            }
+        }
+
+        // Assets and the index page don't depend on any module, so they're only generated along
+        // the first pages. Plain pages come without any search data.
+        if fresh {
+            output_files.extend(generate_static_assets(fs, root, config, search_indexes));
+            match generate_readme(
+                fs,
+                root,
+                config,
+                &context.modules_links,
+                &context.unstable_modules_links,
+                &context.symbols,
+                &context.source,
+                &timestamp,
+            ) {
+                Ok(file) => output_files.push(file),
+                Err(failure) => failures.push(failure),
+            }
+        } else if !config.docs.plain {
+            output_files.extend(generate_search_data(config, search_indexes));
+        }
+
+        (output_files, failures)
+    }
+}
@@ -217,12 +217,14 @@ impl LinkTree {
                                 indent: path.len() + offset,
                                 name: segment.to_string(),
                                 path: module_path.to_string(),
+                                summary: String::new(),
                             }
                         } else {
                             DocLink {
                                 indent: path.len() + offset,
                                 name: segment.to_string(),
                                 path: String::new(),
+                                summary: String::new(),
                             }
                         }
                     })
@@ -245,6 +247,7 @@ impl LinkTree {
                         } else {
                             mk_path(prefix)
                         },
+                        summary: String::new(),
                     }]
                 };
 
@@ -290,6 +293,7 @@ fn link_tree_1() {
             indent: 0,
             name: "foo".to_string(),
             path: "foo.html".to_string(),
+            summary: String::new(),
         }]
     )
 }
@@ -306,11 +310,13 @@ fn link_tree_2() {
                 indent: 0,
                 name: "bar".to_string(),
                 path: "bar.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 0,
                 name: "foo".to_string(),
                 path: "foo.html".to_string(),
+                summary: String::new(),
             }
         ]
     )
@@ -328,16 +334,19 @@ fn link_tree_3() {
                 indent: 0,
                 name: "aiken".to_string(),
                 path: String::new(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "bytearray".to_string(),
                 path: "aiken/bytearray.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "list".to_string(),
                 path: "aiken/list.html".to_string(),
+                summary: String::new(),
             },
         ]
     )
@@ -357,31 +366,37 @@ fn link_tree_4() {
                 indent: 0,
                 name: "aiken".to_string(),
                 path: String::new(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "cbor".to_string(),
                 path: "aiken/cbor.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "math".to_string(),
                 path: "aiken/math.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 2,
                 name: "rational".to_string(),
                 path: "aiken/math/rational.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 0,
                 name: "cardano".to_string(),
                 path: "".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "foo".to_string(),
                 path: "cardano/foo.html".to_string(),
+                summary: String::new(),
             }
         ]
     )
@@ -402,31 +417,37 @@ fn link_tree_5() {
                 indent: 0,
                 name: "aiken".to_string(),
                 path: String::new(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "cbor".to_string(),
                 path: "aiken/cbor.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "math".to_string(),
                 path: "aiken/math.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 2,
                 name: "rational".to_string(),
                 path: "aiken/math/rational.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 0,
                 name: "cardano".to_string(),
                 path: "cardano.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "foo".to_string(),
                 path: "cardano/foo.html".to_string(),
+                summary: String::new(),
             }
         ]
     )
@@ -448,26 +469,31 @@ fn link_tree_6() {
                 indent: 0,
                 name: "cardano".to_string(),
                 path: "".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "address".to_string(),
                 path: "cardano/address.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 2,
                 name: "credential".to_string(),
                 path: "cardano/address/credential.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "assets".to_string(),
                 path: "cardano/assets.html".to_string(),
+                summary: String::new(),
             },
             DocLink {
                 indent: 1,
                 name: "certificate".to_string(),
                 path: "cardano/certificate.html".to_string(),
+                summary: String::new(),
             },
         ]
     )
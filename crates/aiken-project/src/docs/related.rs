@@ -0,0 +1,164 @@
+use super::is_hidden;
+use crate::CheckedModule;
+use aiken_lang::{
+    ast::{Definition, Function},
+    expr::TypedExpr,
+    tipo::{ModuleValueConstructor, Type, TypeVar, ValueConstructor, ValueConstructorVariant},
+};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Maximum number of related functions listed for each function.
+const MAX_RELATED: usize = 3;
+
+/// A function, identified by its module and name.
+pub type FunctionKey = (String, String);
+
+/// Affinity between public functions of a project, from how often they're used together within
+/// the same body and from the (non-prelude) types they operate on.
+#[derive(Debug, Default)]
+pub struct RelatedIndex {
+    co_usage: BTreeMap<FunctionKey, BTreeMap<FunctionKey, usize>>,
+    types: BTreeMap<FunctionKey, BTreeSet<(String, String)>>,
+}
+
+impl RelatedIndex {
+    pub fn new(modules: &[&CheckedModule]) -> Self {
+        let mut index = RelatedIndex::default();
+
+        for module in modules
+            .iter()
+            .filter(|module| !module.skip_doc_generation())
+        {
+            for def in module.ast.definitions() {
+                if let Definition::Fn(Function {
+                    public: true,
+                    name,
+                    arguments,
+                    return_type,
+                    doc,
+                    ..
+                }) = def
+                {
+                    if is_hidden(doc.as_deref()) {
+                        continue;
+                    }
+
+                    let mut types = BTreeSet::new();
+                    for arg in arguments {
+                        collect_types(&arg.tipo, &mut types);
+                    }
+                    collect_types(return_type, &mut types);
+                    index
+                        .types
+                        .insert((module.name.clone(), name.clone()), types);
+                }
+            }
+        }
+
+        for module in modules {
+            for body in module.ast.definitions().flat_map(|def| def.bodies()) {
+                let mut used = BTreeSet::new();
+
+                body.walk(&mut |expr| {
+                    if let Some(key) = function_reference(expr) {
+                        if index.types.contains_key(&key) {
+                            used.insert(key);
+                        }
+                    }
+                });
+
+                for (a, b) in used.iter().tuple_combinations() {
+                    *index
+                        .co_usage
+                        .entry(a.clone())
+                        .or_default()
+                        .entry(b.clone())
+                        .or_default() += 1;
+                    *index
+                        .co_usage
+                        .entry(b.clone())
+                        .or_default()
+                        .entry(a.clone())
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        index
+    }
+
+    /// Public functions most related to the given one, best first. Co-usage weighs more than
+    /// shared types.
+    pub fn related(&self, module: &str, name: &str) -> Vec<&FunctionKey> {
+        let key = (module.to_string(), name.to_string());
+
+        let Some(types) = self.types.get(&key) else {
+            return vec![];
+        };
+
+        let co_usage = self.co_usage.get(&key);
+
+        self.types
+            .iter()
+            .filter(|(other, _)| **other != key)
+            .map(|(other, other_types)| {
+                let used = co_usage
+                    .and_then(|co_usage| co_usage.get(other))
+                    .copied()
+                    .unwrap_or_default();
+                let shared = types.intersection(other_types).count();
+                (other, (used, shared))
+            })
+            .filter(|(_, score)| *score != (0, 0))
+            .sorted_by(|(_, a), (_, b)| b.cmp(a))
+            .take(MAX_RELATED)
+            .map(|(other, _)| other)
+            .collect()
+    }
+}
+
+fn function_reference(expr: &TypedExpr) -> Option<FunctionKey> {
+    match expr {
+        TypedExpr::Var {
+            constructor:
+                ValueConstructor {
+                    variant: ValueConstructorVariant::ModuleFn { module, name, .. },
+                    ..
+                },
+            ..
+        }
+        | TypedExpr::ModuleSelect {
+            constructor: ModuleValueConstructor::Fn { module, name, .. },
+            ..
+        } => Some((module.clone(), name.clone())),
+        _ => None,
+    }
+}
+
+fn collect_types(tipo: &Type, types: &mut BTreeSet<(String, String)>) {
+    match tipo {
+        Type::App {
+            module, name, args, ..
+        } => {
+            if !module.is_empty() {
+                types.insert((module.clone(), name.clone()));
+            }
+            args.iter().for_each(|arg| collect_types(arg, types));
+        }
+        Type::Fn { args, ret, .. } => {
+            args.iter().for_each(|arg| collect_types(arg, types));
+            collect_types(ret, types);
+        }
+        Type::Var { tipo, .. } => {
+            if let TypeVar::Link { tipo } = &*tipo.borrow() {
+                collect_types(tipo, types);
+            }
+        }
+        Type::Tuple { elems, .. } => elems.iter().for_each(|elem| collect_types(elem, types)),
+        Type::Pair { fst, snd, .. } => {
+            collect_types(fst, types);
+            collect_types(snd, types);
+        }
+    }
+}
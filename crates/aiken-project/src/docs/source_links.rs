@@ -1,18 +1,20 @@
 use crate::{
-    config::{Config, Platform},
+    config::{Config, Repository},
     CheckedModule,
 };
-use aiken_lang::{ast::Span, line_numbers::LineNumbers};
+use aiken_lang::ast::Span;
 use camino::{Utf8Component, Utf8Path};
 use std::path::Path;
 
-pub struct SourceLinker {
-    line_numbers: LineNumbers,
-    url_pattern: Option<(String, String)>,
+pub struct SourceLinker<'a> {
+    module: &'a CheckedModule,
+    repository: Option<Repository>,
+    version: String,
+    path_in_repo: String,
 }
 
-impl SourceLinker {
-    pub fn new(root: &Path, config: &Config, module: &CheckedModule) -> Self {
+impl<'a> SourceLinker<'a> {
+    pub fn new(root: &Path, config: &Config, module: &'a CheckedModule) -> Self {
         let utf8_path = <&Utf8Path>::try_from(
             module
                 .input_path
@@ -24,45 +26,32 @@ impl SourceLinker {
 
         let path_in_repo = to_url_path(utf8_path).unwrap_or_default();
 
-        let url_pattern = config
-            .repository
-            .as_ref()
-            .map(|repository| match repository.platform {
-                Platform::Github => (
-                    format!(
-                        "https://github.com/{}/{}/blob/{}/{}#L",
-                        repository.user, repository.project, config.version, path_in_repo
-                    ),
-                    "-L".into(),
-                ),
-                Platform::Gitlab => (
-                    format!(
-                        "https://gitlab.com/{}/{}/-/blob/{}/{}#L",
-                        repository.user, repository.project, config.version, path_in_repo
-                    ),
-                    "-".into(),
-                ),
-                Platform::Bitbucket => (
-                    format!(
-                        "https://bitbucket.com/{}/{}/src/{}/{}#lines-",
-                        repository.user, repository.project, config.version, path_in_repo
-                    ),
-                    ":".into(),
-                ),
-            });
+        let repository = config.repository.clone();
 
         SourceLinker {
-            line_numbers: LineNumbers::new(&module.code),
-            url_pattern,
+            module,
+            repository,
+            version: config.version.to_string(),
+            path_in_repo,
         }
     }
 
+    /// Path of the module's source file within the repository.
+    pub fn path(&self) -> &str {
+        &self.path_in_repo
+    }
+
     pub fn url(&self, span: Span) -> String {
-        match &self.url_pattern {
-            Some((base, line_sep)) => {
-                let start_line = self.line_numbers.line_number(span.start).unwrap();
-                let end_line = self.line_numbers.line_number(span.end).unwrap();
-                format!("{base}{start_line}{line_sep}{end_line}")
+        match &self.repository {
+            Some(repository) => {
+                let start_line = self.module.line_col(span.start).unwrap().line;
+                let end_line = self.module.line_col(span.end).unwrap().line;
+                repository.platform.blob_url(
+                    repository,
+                    &self.version,
+                    &self.path_in_repo,
+                    (start_line, end_line),
+                )
             }
             None => "".into(),
         }
@@ -0,0 +1,165 @@
+use super::{is_documented, to_anchor, to_breadcrumbs, to_type_anchor};
+use crate::{
+    config::{DocAnchors, DocLayout},
+    CheckedModule,
+};
+use aiken_lang::ast::{DataType, Definition, Function, ModuleConstant, TypeAlias, Validator};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Documented definitions of a project, which doc comments can refer to with intra-doc links
+/// such as '[Credential]', '[list.map]' or '[Credential.Script]'.
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    /// Modules and anchors of definitions, by name within their module.
+    symbols: BTreeMap<String, BTreeSet<(String, String)>>,
+    modules: BTreeSet<String>,
+    layout: DocLayout,
+}
+
+impl SymbolIndex {
+    pub fn new(
+        modules: &[&CheckedModule],
+        include_private: bool,
+        anchors: DocAnchors,
+        layout: DocLayout,
+    ) -> Self {
+        let mut index = SymbolIndex {
+            layout,
+            ..SymbolIndex::default()
+        };
+
+        for module in modules
+            .iter()
+            .filter(|module| !module.skip_doc_generation())
+        {
+            index.modules.insert(module.name.clone());
+
+            for def in module
+                .ast
+                .definitions()
+                .filter(|def| is_documented(def, include_private))
+            {
+                match def {
+                    Definition::Fn(Function { name, .. })
+                    | Definition::ModuleConstant(ModuleConstant { name, .. }) => {
+                        index.insert(&module.name, name.clone(), to_anchor(anchors, name));
+                    }
+                    Definition::TypeAlias(TypeAlias { alias: name, .. }) => {
+                        let anchor = to_type_anchor(anchors, module, name);
+                        index.insert(&module.name, name.clone(), anchor);
+                    }
+                    Definition::DataType(DataType {
+                        name, constructors, ..
+                    }) => {
+                        let anchor = to_type_anchor(anchors, module, name);
+                        for constructor in constructors {
+                            index.insert(
+                                &module.name,
+                                format!("{name}.{}", constructor.name),
+                                format!("{anchor}.{}", to_anchor(anchors, &constructor.name)),
+                            );
+                        }
+                        index.insert(&module.name, name.clone(), anchor);
+                    }
+                    Definition::Validator(Validator { name, handlers, .. }) => {
+                        let anchor = to_anchor(anchors, name);
+                        for handler in handlers {
+                            index.insert(
+                                &module.name,
+                                format!("{name}.{}", handler.name),
+                                format!("{anchor}.{}", handler.name),
+                            );
+                        }
+                        index.insert(&module.name, name.clone(), anchor);
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        index
+    }
+
+    fn insert(&mut self, module: &str, name: String, anchor: String) {
+        self.symbols
+            .entry(name)
+            .or_default()
+            .insert((module.to_string(), anchor));
+    }
+
+    /// URL of the definition a reference points at, relative to the page of the given module.
+    /// References are either local to that module, unambiguous across the project, or
+    /// qualified by (the last segment of) a module name.
+    fn resolve(&self, reference: &str, module: &str) -> Option<String> {
+        let reference = reference.trim().trim_matches('`');
+
+        let found = self.lookup(reference, |_| true, module).or_else(|| {
+            let (qualifier, name) = reference.split_once('.')?;
+            self.lookup(
+                name,
+                |defined_in| {
+                    defined_in == qualifier || defined_in.ends_with(&format!("/{qualifier}"))
+                },
+                module,
+            )
+        });
+
+        let breadcrumbs = to_breadcrumbs(&self.layout.module_page(module));
+
+        match found {
+            Some((defined_in, anchor)) if defined_in == module => Some(format!("#{anchor}")),
+            Some((defined_in, anchor)) => Some(format!(
+                "{breadcrumbs}/{}#{anchor}",
+                self.layout.module_link(defined_in)
+            )),
+            None => self
+                .modules
+                .get(reference)
+                .map(|defined_in| format!("{breadcrumbs}/{}", self.layout.module_link(defined_in))),
+        }
+    }
+
+    /// The definition of the given name, among the candidate modules: preferably within the
+    /// current module, or otherwise the only one there is.
+    fn lookup(
+        &self,
+        name: &str,
+        candidate: impl Fn(&str) -> bool,
+        module: &str,
+    ) -> Option<(&str, &str)> {
+        let definitions = self
+            .symbols
+            .get(name)?
+            .iter()
+            .filter(|(defined_in, _)| candidate(defined_in))
+            .collect::<Vec<_>>();
+
+        let (defined_in, anchor) = definitions
+            .iter()
+            .find(|(defined_in, _)| defined_in == module)
+            .or(match definitions[..] {
+                [_] => definitions.first(),
+                _ => None,
+            })?;
+
+        Some((defined_in.as_str(), anchor.as_str()))
+    }
+}
+
+/// Intra-doc links resolution, from the documentation of a given module (or of none, for
+/// standalone pages).
+#[derive(Debug, Clone, Copy)]
+pub struct Resolver<'a> {
+    index: &'a SymbolIndex,
+    module: &'a str,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(index: &'a SymbolIndex, module: &'a str) -> Self {
+        Resolver { index, module }
+    }
+
+    pub fn resolve(&self, reference: &str) -> Option<String> {
+        self.index.resolve(reference, self.module)
+    }
+}
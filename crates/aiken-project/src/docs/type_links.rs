@@ -0,0 +1,191 @@
+use super::{escape_html_content, is_documented, to_breadcrumbs, to_type_anchor};
+use crate::{
+    config::{DocAnchors, DocLayout},
+    CheckedModule,
+};
+use aiken_lang::{
+    ast::{DataType, Definition, TypeAlias},
+    tipo::{Type, TypeVar},
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Documented types of a project, so that signatures can link to their definitions.
+#[derive(Debug, Default)]
+pub struct TypeIndex {
+    /// Modules defining a documented type, by name of the type.
+    types: BTreeMap<String, BTreeSet<String>>,
+    /// Anchors of documented types, by module and name.
+    anchors: BTreeMap<(String, String), String>,
+    layout: DocLayout,
+}
+
+impl TypeIndex {
+    pub fn new(
+        modules: &[&CheckedModule],
+        include_private: bool,
+        anchors: DocAnchors,
+        layout: DocLayout,
+    ) -> Self {
+        let mut index = TypeIndex {
+            layout,
+            ..TypeIndex::default()
+        };
+
+        for module in modules
+            .iter()
+            .filter(|module| !module.skip_doc_generation())
+        {
+            for def in module
+                .ast
+                .definitions()
+                .filter(|def| is_documented(def, include_private))
+            {
+                let name = match def {
+                    Definition::DataType(DataType { name, .. }) => name,
+                    Definition::TypeAlias(TypeAlias { alias, .. }) => alias,
+                    _ => continue,
+                };
+
+                index
+                    .types
+                    .entry(name.clone())
+                    .or_default()
+                    .insert(module.name.clone());
+                index.anchors.insert(
+                    (module.name.clone(), name.clone()),
+                    to_type_anchor(anchors, module, name),
+                );
+            }
+        }
+
+        index
+    }
+
+    /// Render a signature, shown in the given module, as HTML; types it refers to which have
+    /// documentation become links to it. The types are those behind the signature: they tell
+    /// which module each name comes from, regardless of how it is qualified in the signature.
+    pub fn link_signature<'a>(
+        &self,
+        signature: &str,
+        types: impl IntoIterator<Item = &'a Type>,
+        module: &str,
+    ) -> String {
+        let mut referenced = Referenced::default();
+        for tipo in types {
+            referenced.collect(tipo);
+        }
+
+        let mut html = String::new();
+        let mut rest = signature;
+
+        while let Some(c) = rest.chars().next() {
+            let len = if is_word(c) {
+                rest.find(|c| !is_word(c)).unwrap_or(rest.len())
+            } else {
+                c.len_utf8()
+            };
+
+            let (word, tail) = rest.split_at(len);
+
+            let resolved = self
+                .resolve(word, &referenced, module)
+                .and_then(|defined_in| {
+                    let anchor = self
+                        .anchors
+                        .get(&(defined_in.to_string(), word.to_string()))?;
+                    Some((defined_in, anchor))
+                });
+
+            match resolved {
+                Some((defined_in, anchor)) if defined_in == module => {
+                    html.push_str(&format!("<a href=\"#{anchor}\">{word}</a>"));
+                }
+                Some((defined_in, anchor)) => {
+                    html.push_str(&format!(
+                        "<a href=\"{}/{}#{anchor}\">{word}</a>",
+                        to_breadcrumbs(&self.layout.module_page(module)),
+                        self.layout.module_link(defined_in),
+                    ));
+                }
+                None => html.push_str(&escape_html_content(word)),
+            }
+
+            rest = tail;
+        }
+
+        html
+    }
+
+    /// Module documenting the type of the given name. Aliases don't remember where they're
+    /// defined, so they're looked up in the current module first, or must otherwise be
+    /// unambiguous across the project.
+    fn resolve<'a>(&'a self, name: &str, referenced: &Referenced, module: &str) -> Option<&'a str> {
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return None;
+        }
+
+        let modules = self.types.get(name)?;
+
+        if let Some(defined_in) = modules.iter().find(|defined_in| {
+            referenced
+                .types
+                .iter()
+                .any(|(m, n)| m == *defined_in && n == name)
+        }) {
+            return Some(defined_in);
+        }
+
+        if !referenced.aliases.contains(name) {
+            return None;
+        }
+
+        modules
+            .get(module)
+            .or_else(|| modules.first().filter(|_| modules.len() == 1))
+            .map(String::as_str)
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Named types, and aliases, found within some types.
+#[derive(Debug, Default)]
+struct Referenced {
+    types: BTreeSet<(String, String)>,
+    aliases: BTreeSet<String>,
+}
+
+impl Referenced {
+    fn collect(&mut self, tipo: &Type) {
+        if let Some(alias) = tipo.alias() {
+            self.aliases.insert(alias.alias.clone());
+        }
+
+        match tipo {
+            Type::App {
+                module, name, args, ..
+            } => {
+                if !module.is_empty() {
+                    self.types.insert((module.clone(), name.clone()));
+                }
+                args.iter().for_each(|arg| self.collect(arg));
+            }
+            Type::Fn { args, ret, .. } => {
+                args.iter().for_each(|arg| self.collect(arg));
+                self.collect(ret);
+            }
+            Type::Var { tipo, .. } => {
+                if let TypeVar::Link { tipo } = &*tipo.borrow() {
+                    self.collect(tipo);
+                }
+            }
+            Type::Tuple { elems, .. } => elems.iter().for_each(|elem| self.collect(elem)),
+            Type::Pair { fst, snd, .. } => {
+                self.collect(fst);
+                self.collect(snd);
+            }
+        }
+    }
+}
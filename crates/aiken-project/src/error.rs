@@ -1,4 +1,4 @@
-use crate::{blueprint, deps::manifest::Package, package_name::PackageName};
+use crate::{blueprint, config::DocTheme, deps::manifest::Package, package_name::PackageName};
 use aiken_lang::{
     ast::{self, Span},
     error::ExtraData,
@@ -572,6 +572,24 @@ pub enum Warning {
     CompilerVersionMismatch { demanded: String, current: String },
     #[error("No configuration found for environment {env}.")]
     NoConfigurationForEnv { env: String },
+    #[error("Ignoring missing documentation asset at: {path:?}")]
+    MissingDocAsset { path: PathBuf },
+    #[error("Ignoring unknown documentation theme: {name}")]
+    UnknownDocTheme { name: String },
+    #[error("Ignoring missing documentation theme at: {path:?}")]
+    MissingDocTheme { path: PathBuf },
+    #[error("Ignoring invalid '@since' marker of {name}: {since}")]
+    InvalidSinceMarker {
+        path: PathBuf,
+        name: String,
+        since: String,
+    },
+    #[error("Skipping documentation of {module}: {reason}")]
+    DocRenderingFailed {
+        path: PathBuf,
+        module: String,
+        reason: String,
+    },
 }
 
 impl ExtraData for Warning {
@@ -581,7 +599,12 @@ impl ExtraData for Warning {
             | Warning::DependencyAlreadyExists { .. }
             | Warning::InvalidModuleName { .. }
             | Warning::CompilerVersionMismatch { .. }
-            | Warning::NoConfigurationForEnv { .. } => None,
+            | Warning::NoConfigurationForEnv { .. }
+            | Warning::MissingDocAsset { .. }
+            | Warning::UnknownDocTheme { .. }
+            | Warning::MissingDocTheme { .. }
+            | Warning::InvalidSinceMarker { .. }
+            | Warning::DocRenderingFailed { .. } => None,
             Warning::Type { warning, .. } => warning.extra_data(),
         }
     }
@@ -590,11 +613,17 @@ impl ExtraData for Warning {
 impl GetSource for Warning {
     fn path(&self) -> Option<PathBuf> {
         match self {
-            Warning::InvalidModuleName { path } | Warning::Type { path, .. } => Some(path.clone()),
+            Warning::InvalidModuleName { path }
+            | Warning::Type { path, .. }
+            | Warning::MissingDocAsset { path }
+            | Warning::MissingDocTheme { path }
+            | Warning::InvalidSinceMarker { path, .. }
+            | Warning::DocRenderingFailed { path, .. } => Some(path.clone()),
             Warning::NoValidators
             | Warning::DependencyAlreadyExists { .. }
             | Warning::NoConfigurationForEnv { .. }
-            | Warning::CompilerVersionMismatch { .. } => None,
+            | Warning::CompilerVersionMismatch { .. }
+            | Warning::UnknownDocTheme { .. } => None,
         }
     }
 
@@ -605,7 +634,12 @@ impl GetSource for Warning {
             | Warning::InvalidModuleName { .. }
             | Warning::DependencyAlreadyExists { .. }
             | Warning::NoConfigurationForEnv { .. }
-            | Warning::CompilerVersionMismatch { .. } => None,
+            | Warning::CompilerVersionMismatch { .. }
+            | Warning::MissingDocAsset { .. }
+            | Warning::UnknownDocTheme { .. }
+            | Warning::MissingDocTheme { .. }
+            | Warning::InvalidSinceMarker { .. }
+            | Warning::DocRenderingFailed { .. } => None,
         }
     }
 }
@@ -622,7 +656,12 @@ impl Diagnostic for Warning {
             | Warning::InvalidModuleName { .. }
             | Warning::NoConfigurationForEnv { .. }
             | Warning::DependencyAlreadyExists { .. }
-            | Warning::CompilerVersionMismatch { .. } => None,
+            | Warning::CompilerVersionMismatch { .. }
+            | Warning::MissingDocAsset { .. }
+            | Warning::UnknownDocTheme { .. }
+            | Warning::MissingDocTheme { .. }
+            | Warning::InvalidSinceMarker { .. }
+            | Warning::DocRenderingFailed { .. } => None,
         }
     }
 
@@ -633,7 +672,12 @@ impl Diagnostic for Warning {
             | Warning::NoValidators
             | Warning::DependencyAlreadyExists { .. }
             | Warning::NoConfigurationForEnv { .. }
-            | Warning::CompilerVersionMismatch { .. } => None,
+            | Warning::CompilerVersionMismatch { .. }
+            | Warning::MissingDocAsset { .. }
+            | Warning::UnknownDocTheme { .. }
+            | Warning::MissingDocTheme { .. }
+            | Warning::InvalidSinceMarker { .. }
+            | Warning::DocRenderingFailed { .. } => None,
         }
     }
 
@@ -654,6 +698,11 @@ impl Diagnostic for Warning {
             Warning::NoConfigurationForEnv { .. } => {
                 Some(Box::new("aiken::project::config::missing::env"))
             }
+            Warning::MissingDocAsset { .. } => Some(Box::new("aiken::docs::missing::asset")),
+            Warning::UnknownDocTheme { .. } => Some(Box::new("aiken::docs::unknown::theme")),
+            Warning::MissingDocTheme { .. } => Some(Box::new("aiken::docs::missing::theme")),
+            Warning::InvalidSinceMarker { .. } => Some(Box::new("aiken::docs::invalid::since")),
+            Warning::DocRenderingFailed { .. } => Some(Box::new("aiken::docs::rendering")),
         }
     }
 
@@ -674,6 +723,22 @@ impl Diagnostic for Warning {
             Warning::NoConfigurationForEnv { .. } => Some(Box::new(
                 "When configuration keys are missing for a target environment, no 'config' module will be created. This may lead to issues down the line.",
             )),
+            Warning::MissingDocAsset { .. } => Some(Box::new(
+                "Paths of the favicon and logo of the documentation are relative to the project's root. The default ones are used meanwhile.",
+            )),
+            Warning::UnknownDocTheme { .. } => Some(Box::new(format!(
+                "Built-in themes are {}, while custom ones are paths to a '.css' file. The default theme is used meanwhile.",
+                DocTheme::BUILT_IN.map(|name| format!("'{name}'")).join(", ")
+            ))),
+            Warning::MissingDocTheme { .. } => Some(Box::new(
+                "Custom themes are paths to a '.css' file, relative to the project's root. The default theme is used meanwhile.",
+            )),
+            Warning::InvalidSinceMarker { .. } => Some(Box::new(
+                "A '@since' marker gives the version which introduced a definition, such as '@since 1.2.0'.",
+            )),
+            Warning::DocRenderingFailed { .. } => Some(Box::new(
+                "The page of this module is missing from the documentation, while the other modules are documented as usual.",
+            )),
         }
     }
 }
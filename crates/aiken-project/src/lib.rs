@@ -172,6 +172,10 @@ where
         std::mem::take(&mut self.warnings)
     }
 
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
     pub fn modules(&self) -> Vec<CheckedModule> {
         self.checked_modules.values().cloned().collect()
     }
@@ -211,6 +215,7 @@ where
         &mut self,
         destination: Option<PathBuf>,
         include_dependencies: bool,
+        include_private: bool,
     ) -> Result<(), Vec<Error>> {
         self.event_listener
             .handle_event(Event::BuildingDocumentation {
@@ -233,7 +238,7 @@ where
             output_path: destination.clone(),
         });
 
-        let modules = self
+        let modules: Vec<&CheckedModule> = self
             .checked_modules
             .values_mut()
             .filter(|CheckedModule { package, .. }| {
@@ -245,7 +250,33 @@ where
             })
             .collect();
 
-        let doc_files = docs::generate_all(&self.root, &self.config, modules);
+        for (path, name, since) in docs::invalid_since_markers(&modules) {
+            self.warnings
+                .push(Warning::InvalidSinceMarker { path, name, since });
+        }
+
+        let mut config = self.config.clone();
+        config.docs.include_private |= include_private;
+
+        let doc_fs = docs::fs::DefaultFileSystem::default();
+
+        let (images, warnings) = docs::check_assets(&doc_fs, &self.root, &mut config);
+        self.warnings.extend(warnings);
+
+        let (doc_files, failures) = docs::generate_all(&doc_fs, &self.root, &config, modules);
+
+        for docs::DocFailure {
+            path,
+            module,
+            reason,
+        } in failures
+        {
+            self.warnings.push(Warning::DocRenderingFailed {
+                path,
+                module,
+                reason,
+            });
+        }
 
         for file in doc_files {
             let path = destination.join(file.path);
@@ -253,6 +284,10 @@ where
             fs::write(&path, file.content).map_err(Error::from)?;
         }
 
+        for image in images {
+            fs::write(destination.join(image.path), image.content).map_err(Error::from)?;
+        }
+
         Ok(())
     }
 
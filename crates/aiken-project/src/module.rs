@@ -6,9 +6,10 @@ use aiken_lang::{
         Validator,
     },
     expr::TypedExpr,
-    line_numbers::LineNumbers,
+    format,
+    line_numbers::{LineColumn, LineNumbers},
     parser::extra::{comments_before, Comment, ModuleExtra},
-    tipo::TypeInfo,
+    tipo::{self, TypeInfo},
     IdGenerator,
 };
 use indexmap::IndexMap;
@@ -39,6 +40,25 @@ impl ParsedModule {
         (name, deps)
     }
 
+    fn lint(&self) -> Vec<tipo::error::Warning> {
+        format::redundant_parentheses(&self.ast, &self.code)
+            .into_iter()
+            .map(|location| tipo::error::Warning::RedundantParentheses { location })
+            .chain(
+                self.ast
+                    .single_use_type_aliases()
+                    .into_iter()
+                    .map(
+                        |(alias, use_location)| tipo::error::Warning::SingleUseTypeAlias {
+                            location: alias.location,
+                            use_location,
+                            name: alias.alias.clone(),
+                        },
+                    ),
+            )
+            .collect()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn infer(
         self,
@@ -53,7 +73,12 @@ impl ParsedModule {
         constants: &mut IndexMap<FunctionAccessKey, TypedExpr>,
         data_types: &mut IndexMap<DataTypeKey, TypedDataType>,
     ) -> Result<(CheckedModule, Vec<Warning>), Error> {
-        let mut warnings = Vec::new();
+        // Warnings from dependencies are discarded anyway, so only lint the project's own modules.
+        let mut warnings = if self.package == package {
+            self.lint()
+        } else {
+            Vec::new()
+        };
 
         let ast = self
             .ast
@@ -302,6 +327,15 @@ impl CheckedModule {
             == "@hidden"
     }
 
+    pub fn is_unstable(&self) -> bool {
+        self.ast.docs.iter().any(|s| s.trim() == "@unstable")
+    }
+
+    /// Line and column (both starting at 1) of a byte offset within the module's source code.
+    pub fn line_col(&self, offset: usize) -> Option<LineColumn> {
+        self.ast.lines.line_and_column_number(offset)
+    }
+
     pub fn to_cbor(&self) -> Vec<u8> {
         let mut module_bytes = vec![];
 
@@ -406,7 +440,14 @@ impl CheckedModule {
                         }
                     }
 
-                    for handler in handlers.iter_mut() {
+                    for handler in handlers.iter_mut().chain(std::iter::once(fallback)) {
+                        let docs: Vec<&str> =
+                            comments_before(&mut doc_comments, handler.location.start, &self.code);
+
+                        if !docs.is_empty() {
+                            handler.doc = Some(docs.join("\n"));
+                        }
+
                         for argument in handler.arguments.iter_mut() {
                             let docs: Vec<&str> = comments_before(
                                 &mut doc_comments,
@@ -420,16 +461,6 @@ impl CheckedModule {
                             }
                         }
                     }
-
-                    for argument in fallback.arguments.iter_mut() {
-                        let docs: Vec<&str> =
-                            comments_before(&mut doc_comments, argument.location.start, &self.code);
-
-                        if !docs.is_empty() {
-                            let doc = docs.join("\n");
-                            argument.put_doc(doc);
-                        }
-                    }
                 }
                 _ => (),
             }
@@ -163,6 +163,94 @@ window.Aiken = (function () {
     }
   };
 
+  // Long constructor lists are clipped rather than hidden, so that their content remains
+  // reachable to in-page search and assistive technologies.
+  const collapseConstructors = function () {
+    document.querySelectorAll(".constructors-toggle").forEach(function (toggle) {
+      const list = document.getElementById(
+        toggle.getAttribute("aria-controls")
+      );
+      const firstHidden = list.children[parseInt(list.dataset.visible)];
+
+      const setExpanded = function (expanded) {
+        toggle.setAttribute("aria-expanded", expanded);
+        toggle.textContent = expanded
+          ? "Show fewer constructors"
+          : `Show all ${toggle.dataset.count} constructors`;
+        list.classList.toggle("collapsed", !expanded);
+        list.style.maxHeight = expanded
+          ? ""
+          : `${firstHidden.offsetTop - list.offsetTop}px`;
+      };
+
+      toggle.addEventListener("click", function (_event) {
+        setExpanded(toggle.getAttribute("aria-expanded") !== "true");
+      });
+
+      // Reveal constructors as soon as they're reached, from a link or the keyboard.
+      const expandOnTarget = function () {
+        const target =
+          window.location.hash &&
+          document.getElementById(window.location.hash.slice(1));
+        if (target && list.contains(target)) {
+          setExpanded(true);
+        }
+      };
+      list.addEventListener("focusin", function (_event) {
+        setExpanded(true);
+      });
+      window.addEventListener("hashchange", expandOnTarget);
+
+      toggle.hidden = false;
+      setExpanded(false);
+      expandOnTarget();
+    });
+  };
+
+  // The control to go back to the top of a page only shows once it's been scrolled past the
+  // first screen.
+  const showBackToTop = function () {
+    const backToTop = document.querySelector(".back-to-top");
+    if (!backToTop) {
+      return;
+    }
+
+    const update = function () {
+      backToTop.hidden = window.scrollY < window.innerHeight;
+    };
+    window.addEventListener("scroll", update, { passive: true });
+    update();
+  };
+
+  // Turn the date of the build into a time relative to now (e.g. '3 days ago'), keeping the
+  // exact time as a tooltip.
+  const showBuildTime = function () {
+    const buildTime = document.querySelector(".build-time");
+    if (!buildTime) {
+      return;
+    }
+
+    const built = new Date(parseInt(buildTime.dataset.timestamp) * 1000);
+    const elapsed = Math.max(0, Math.floor((Date.now() - built.getTime()) / 1000));
+    const units = [
+      ["year", 31536000],
+      ["month", 2592000],
+      ["week", 604800],
+      ["day", 86400],
+      ["hour", 3600],
+      ["minute", 60],
+    ];
+    const [unit, seconds] = units.find(([_unit, seconds]) => elapsed >= seconds) || [];
+    const relative = unit
+      ? new Intl.RelativeTimeFormat("en").format(-Math.floor(elapsed / seconds), unit)
+      : "just now";
+
+    const time = buildTime.querySelector("time");
+    time.title = built.toISOString();
+    time.textContent = relative;
+    buildTime.firstChild.textContent = "Built ";
+  };
+
   const addEvent = function (el, type, handler) {
     if (el.attachEvent) el.attachEvent("on" + type, handler);
     else el.addEventListener(type, handler);
@@ -181,6 +269,7 @@ window.Aiken = (function () {
 
     function showSearch() {
       document.documentElement.classList.add("search-active");
+      searchInput.setAttribute("aria-expanded", "true");
     }
 
     searchNavButton.addEventListener("click", function (e) {
@@ -193,6 +282,25 @@ window.Aiken = (function () {
 
     function hideSearch() {
       document.documentElement.classList.remove("search-active");
+      searchInput.setAttribute("aria-expanded", "false");
+    }
+
+    /* Highlight a result, as navigated to with the keyboard. The input keeps the focus, and
+     * points assistive technologies at the highlighted result. */
+    function selectResult(result) {
+      const active = searchResults.querySelector(".search-result.active");
+      if (active) {
+        active.classList.remove("active");
+        active.setAttribute("aria-selected", "false");
+      }
+      if (result) {
+        result.classList.add("active");
+        result.setAttribute("aria-selected", "true");
+        result.scrollIntoView({ block: "nearest" });
+        searchInput.setAttribute("aria-activedescendant", result.id);
+      } else {
+        searchInput.removeAttribute("aria-activedescendant");
+      }
     }
 
     function update() {
@@ -205,6 +313,7 @@ window.Aiken = (function () {
       }
       currentInput = input;
       searchResults.innerHTML = "";
+      searchInput.removeAttribute("aria-activedescendant");
       if (input === "") {
         return;
       }
@@ -232,6 +341,11 @@ window.Aiken = (function () {
         }
       }
 
+      // Deprecated definitions rank after any other match.
+      results.sort(
+        (a, b) => Boolean(docs[a.ref].deprecated) - Boolean(docs[b.ref].deprecated)
+      );
+
       if (results.length == 0) {
         const noResultsDiv = document.createElement("div");
         noResultsDiv.classList.add("search-no-result");
@@ -240,6 +354,8 @@ window.Aiken = (function () {
       } else {
         const resultsList = document.createElement("ul");
         resultsList.classList.add("search-results-list");
+        resultsList.setAttribute("role", "listbox");
+        resultsList.setAttribute("aria-label", "Search results");
         searchResults.appendChild(resultsList);
 
         addResults(resultsList, results, 0, 10, 100, currentSearchIndex);
@@ -278,10 +394,14 @@ window.Aiken = (function () {
         const doc = docs[result.ref];
         const resultsListItem = document.createElement("li");
         resultsListItem.classList.add("search-results-list-item");
+        resultsListItem.setAttribute("role", "presentation");
         resultsList.appendChild(resultsListItem);
         const resultLink = document.createElement("a");
         resultLink.classList.add("search-result");
         resultLink.setAttribute("href", `${window.breadcrumbs}/${doc.url}`);
+        resultLink.setAttribute("id", `search-result-${resultsList.children.length}`);
+        resultLink.setAttribute("role", "option");
+        resultLink.setAttribute("aria-selected", "false");
         resultsListItem.appendChild(resultLink);
         const resultTitle = document.createElement("div");
         resultTitle.classList.add("search-result-title");
@@ -436,6 +556,15 @@ window.Aiken = (function () {
               resultPreview.appendChild(document.createTextNode(" ..."));
             }
           }
+        } else if (doc.summary) {
+          // Without any match within the content, the summary tells what the result is about.
+          const resultPreviews = document.createElement("div");
+          resultPreviews.classList.add("search-result-previews");
+          resultLink.appendChild(resultPreviews);
+          const resultPreview = document.createElement("div");
+          resultPreview.classList.add("search-result-preview", "search-result-summary");
+          resultPreview.innerHTML = doc.summary;
+          resultPreviews.appendChild(resultPreview);
         }
         const resultRelUrl = document.createElement("span");
         resultRelUrl.classList.add("search-result-rel-url");
@@ -468,9 +597,7 @@ window.Aiken = (function () {
 
     addEvent(searchInput, "keyup", function (e) {
       switch (e.keyCode) {
-        case 27: // When esc key is pressed, hide the results and clear the field
-          searchInput.value = "";
-          break;
+        case 27: // escape
         case 38: // arrow up
         case 40: // arrow down
         case 13: // enter
@@ -480,52 +607,37 @@ window.Aiken = (function () {
       update();
     });
 
+    /* Results are navigated from the input: up and down move the highlighted result, enter
+     * follows it (or the first result) and escape closes the results. */
     addEvent(searchInput, "keydown", function (e) {
-      let active;
+      const results = Array.from(searchResults.querySelectorAll(".search-result"));
+      const active = results.findIndex((result) => result.classList.contains("active"));
       switch (e.keyCode) {
+        case 27: // escape
+          e.preventDefault();
+          searchInput.value = "";
+          currentInput = undefined;
+          searchResults.innerHTML = "";
+          searchInput.removeAttribute("aria-activedescendant");
+          hideSearch();
+          searchInput.focus();
+          return;
         case 38: // arrow up
           e.preventDefault();
-          active = document.querySelector(".search-result.active");
-          if (active) {
-            active.classList.remove("active");
-            if (active.parentElement.previousSibling) {
-              const previous =
-                active.parentElement.previousSibling.querySelector(
-                  ".search-result"
-                );
-              previous.classList.add("active");
-            }
+          if (0 < active) {
+            selectResult(results[active - 1]);
           }
           return;
         case 40: // arrow down
           e.preventDefault();
-          active = document.querySelector(".search-result.active");
-          if (active) {
-            if (active.parentElement.nextSibling) {
-              const next =
-                active.parentElement.nextSibling.querySelector(
-                  ".search-result"
-                );
-              active.classList.remove("active");
-              next.classList.add("active");
-            }
-          } else {
-            const next = document.querySelector(".search-result");
-            if (next) {
-              next.classList.add("active");
-            }
+          if (active + 1 < results.length) {
+            selectResult(results[active + 1]);
           }
           return;
         case 13: // enter
           e.preventDefault();
-          active = document.querySelector(".search-result.active");
-          if (active) {
-            active.click();
-          } else {
-            const first = document.querySelector(".search-result");
-            if (first) {
-              first.click();
-            }
+          if (results.length > 0) {
+            results[Math.max(active, 0)].click();
           }
           return;
       }
@@ -563,6 +675,14 @@ window.Aiken = (function () {
   };
 
   const init = function () {
+    // Without a 'search-data.js' script, the search index is fetched instead.
+    const searchData = document.currentScript && document.currentScript.dataset.searchData;
+    if (searchData) {
+      fetch(searchData)
+        .then((response) => response.json())
+        .then((docs) => self.initSearch(docs));
+    }
+
     for (let property in aikenConfig) {
       initProperty(property);
       const toggle = document.querySelector(`#${property}-toggle`);
@@ -578,6 +698,22 @@ window.Aiken = (function () {
       });
     });
 
+    document.querySelectorAll(".copy-code").forEach(function (button) {
+      button.addEventListener("click", function (_event) {
+        const code = button.parentElement.querySelector("code");
+        navigator.clipboard.writeText(code.textContent).then(function () {
+          button.classList.add("copied");
+          setTimeout(function () {
+            button.classList.remove("copied");
+          }, 1500);
+        });
+      });
+    });
+
+    collapseConstructors();
+    showBackToTop();
+    showBuildTime();
+
     setHashOffset();
     window.addEventListener("load", function (_event) {
       self.scrollToHash();
@@ -19,6 +19,11 @@ pub struct Args {
     #[clap(long)]
     include_dependencies: bool,
 
+    /// When enabled, also document private definitions, in addition to the 'include_private'
+    /// option of the [docs] configuration.
+    #[clap(long)]
+    include_private: bool,
+
     /// Output directory for the documentation
     #[clap(short = 'o', long)]
     destination: Option<PathBuf>,
@@ -31,15 +36,16 @@ pub fn exec(
         watch,
         destination,
         include_dependencies,
+        include_private,
     }: Args,
 ) -> miette::Result<()> {
     let result = if watch {
         watch_project(directory.as_deref(), watch::default_filter, 500, |p| {
-            p.docs(destination.clone(), include_dependencies)
+            p.docs(destination.clone(), include_dependencies, include_private)
         })
     } else {
         with_project(directory.as_deref(), deny, |p| {
-            p.docs(destination.clone(), include_dependencies)
+            p.docs(destination.clone(), include_dependencies, include_private)
         })
     };
 